@@ -3,10 +3,15 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    parse_macro_input, Data::Struct, DataStruct, DeriveInput, Fields::Named, FieldsNamed,
-    PathSegment,
+    parse_macro_input, parse_quote, Data::Struct, DataStruct, DeriveInput, Field, Fields::Named,
+    FieldsNamed, PathSegment,
 };
 
+/// Fields marked as `#[hidden]` hold secrets, they are never displayed, mapped or shown in tables.
+fn is_hidden(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident("hidden"))
+}
+
 #[proc_macro_derive(Builder)]
 pub fn derive_builder(inp: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(inp as DeriveInput);
@@ -58,7 +63,7 @@ pub fn derive_builder(inp: TokenStream) -> TokenStream {
     res.into()
 }
 
-#[proc_macro_derive(DeriveToStr)]
+#[proc_macro_derive(DeriveToStr, attributes(hidden))]
 pub fn derive_to_str(inp: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(inp as DeriveInput);
     let fields = match data {
@@ -68,6 +73,7 @@ pub fn derive_to_str(inp: TokenStream) -> TokenStream {
         }) => named,
         _ => panic!("Not supported"),
     };
+    let fields = fields.iter().filter(|f| !is_hidden(f)).collect::<Vec<_>>();
 
     let fields_args = fields.iter().map(|f| {
         let name = &f.ident;
@@ -182,7 +188,7 @@ pub fn derive_from_str(inp: TokenStream) -> TokenStream {
     res.into()
 }
 
-#[proc_macro_derive(DeriveFromMap)]
+#[proc_macro_derive(DeriveFromMap, attributes(hidden))]
 pub fn derive_from_map(inp: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(inp as DeriveInput);
     let fields = match data {
@@ -197,6 +203,17 @@ pub fn derive_from_map(inp: TokenStream) -> TokenStream {
         let name = &f.ident;
         let ty = &f.ty;
 
+        // Hidden fields are not part of the map, so they are left empty.
+        if is_hidden(f) {
+            let key = format!("{}", name.clone().unwrap());
+            return quote! {
+                #name: match data.get(#key) {
+                    Some(val) => val.parse::<#ty>().unwrap(),
+                    None => <#ty>::default()
+                }
+            };
+        }
+
         let attrs = &f.attrs;
         let mut out = quote! {};
 
@@ -270,7 +287,7 @@ pub fn derive_from_map(inp: TokenStream) -> TokenStream {
     res.into()
 }
 
-#[proc_macro_derive(DeriveToMap)]
+#[proc_macro_derive(DeriveToMap, attributes(hidden))]
 pub fn derive_to_map(inp: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(inp as DeriveInput);
     let fields = match data {
@@ -280,6 +297,7 @@ pub fn derive_to_map(inp: TokenStream) -> TokenStream {
         }) => named,
         _ => panic!("Not supported"),
     };
+    let fields = fields.iter().filter(|f| !is_hidden(f)).collect::<Vec<_>>();
 
     let to_map_args = fields.iter().map(|f| {
         let name = &f.ident;
@@ -349,7 +367,7 @@ pub fn derive_to_map(inp: TokenStream) -> TokenStream {
     res.into()
 }
 
-#[proc_macro_derive(DeriveData, attributes(mutable_ignore))]
+#[proc_macro_derive(DeriveData, attributes(mutable_ignore, hidden))]
 pub fn derive_to_table(inp: TokenStream) -> TokenStream {
     let DeriveInput { ident, data, .. } = parse_macro_input!(inp as DeriveInput);
     let fields = match data {
@@ -360,6 +378,8 @@ pub fn derive_to_table(inp: TokenStream) -> TokenStream {
         _ => panic!("Not supported"),
     };
 
+    let fields = fields.iter().filter(|f| !is_hidden(f)).collect::<Vec<_>>();
+
     let head_attrs = fields.iter().map(|f| {
         let name = &f.ident;
         let key = format!("{}", name.clone().unwrap());
@@ -666,24 +686,38 @@ pub fn derive_view(inp: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn controller(view: TokenStream, strct: TokenStream) -> TokenStream {
     let strct_copy = strct.clone();
-    let DeriveInput { ident, .. } = parse_macro_input!(strct_copy as DeriveInput);
+    let DeriveInput {
+        ident,
+        mut generics,
+        data,
+        ..
+    } = parse_macro_input!(strct_copy as DeriveInput);
+    let model = match data {
+        Struct(DataStruct {
+            fields: Named(FieldsNamed { ref named, .. }),
+            ..
+        }) => named
+            .iter()
+            .find(|f| f.ident.as_ref().is_some_and(|i| i == "model"))
+            .map(|f| f.ty.clone())
+            .expect("A controller needs a model field"),
+        _ => panic!("Not supported"),
+    };
     let s2 = proc_macro2::TokenStream::from(strct);
     let v2 = proc_macro2::TokenStream::from(view);
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(parse_quote!(
+        M: crate::types::Model + crate::models::domain::system::LendingSystem
+    ));
+    predicates.push(parse_quote!(V: crate::types::View + #v2));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
         #s2
-        impl<M, V> crate::types::Controller for #ident<M, V>
-        where
-            M: crate::types::Model + crate::models::domain::system::LendingSystem,
-            V: crate::types::View + #v2,
-        {}
-        impl<M, V> #ident<M, V>
-        where
-            M: crate::types::Model + crate::models::domain::system::LendingSystem,
-            V: crate::types::View + #v2,
-        {
+        impl #impl_generics crate::types::Controller for #ident #ty_generics #where_clause {}
+        impl #impl_generics #ident #ty_generics #where_clause {
             /// Creates a new controller.
-            pub fn new(model: M, view: V) -> Self {
+            pub fn new(model: #model, view: V) -> Self {
                 Self { model, view }
             }
         }
@@ -1,28 +1,37 @@
 use shared::controller;
 
 use super::{
-    contract_controller::ContractController, item_controller::ItemController,
-    member_controller::MemberController, simulator_controller::SimulatorController,
+    audit_controller::AuditController, contract_controller::ContractController,
+    diagnostics_controller::DiagnosticsController, history_controller::HistoryController,
+    item_controller::ItemController, member_controller::MemberController,
+    report_controller::ReportController, simulator_controller::SimulatorController,
 };
 use crate::{
     models::domain::system::LendingSystem,
     types::{Model, View},
     views::{
+        audit_view::CliAuditView,
         contract_view::CliContractView,
+        diagnostics_view::CliDiagnosticsView,
+        history_view::CliHistoryView,
         item_view::CliItemView,
         main_view::{MainMenuOption, MainView},
         member_view::CliMemberView,
+        report_view::CliReportView,
         simulator_view::CliSimulatorView,
     },
 };
 
 /// Defines a controller struct.
-pub trait Page<T> {
+pub trait Page {
     /// Runs the application/subapplication.
-    fn show(&mut self, sys: T) -> T;
+    fn show(&mut self);
 }
 
 /// This is the main app of the program.
+///
+/// The app owns the system and lends it to the page that is shown,
+/// so navigating between pages never copies it.
 #[derive(Debug, Clone)]
 #[controller(MainView)]
 pub struct MainApp<M, V>
@@ -36,47 +45,57 @@ where
 
 impl<M, V> MainApp<M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + MainView,
 {
     /// Starts the entire application.
     pub fn start(&mut self) {
-        self.show(self.model.clone());
+        self.login();
+        self.show();
+    }
+
+    /// Shows the login screen until a member logged in successfully.
+    fn login(&mut self) {
+        let (email, password) = self.view.login();
+        if let Err(err) = self.model.login(&email, &password) {
+            self.view.wait(err.to_string().as_str());
+            self.login()
+        }
     }
 }
 
-impl<M, V> Page<M> for MainApp<M, V>
+impl<M, V> Page for MainApp<M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + MainView,
 {
-    fn show(&mut self, sys: M) -> M {
+    fn show(&mut self) {
         let choice = self.view.main_menu();
-        let state = match choice {
-            MainMenuOption::MembersPage => {
-                let member_view = CliMemberView::new();
-                let mut controller = MemberController::new(sys.clone(), member_view);
-                controller.show(sys)
-            }
-            MainMenuOption::ItemsPage => {
-                let item_view = CliItemView::new();
-                let mut controller = ItemController::new(sys.clone(), item_view);
-                controller.show(sys)
-            }
+        let sys = &mut self.model;
+        match choice {
+            MainMenuOption::MembersPage => MemberController::new(sys, CliMemberView::new()).show(),
+            MainMenuOption::ItemsPage => ItemController::new(sys, CliItemView::new()).show(),
             MainMenuOption::Simulator => {
-                let simulator_view = CliSimulatorView::new();
-                let mut controller = SimulatorController::new(sys.clone(), simulator_view);
-                controller.show(sys)
+                SimulatorController::new(sys, CliSimulatorView::new()).show()
+            }
+            MainMenuOption::Reports => ReportController::new(sys, CliReportView::new()).show(),
+            MainMenuOption::AuditLog => AuditController::new(sys, CliAuditView::new()).show(),
+            MainMenuOption::Diagnostics => {
+                DiagnosticsController::new(sys, CliDiagnosticsView::new()).show()
             }
             MainMenuOption::ContractsPage => {
-                let contract_view = CliContractView::new();
-                let mut controller = ContractController::new(sys.clone(), contract_view);
-                controller.show(sys)
+                ContractController::new(sys, CliContractView::new()).show()
+            }
+            MainMenuOption::Undo => HistoryController::new(sys, CliHistoryView::new()).undo(),
+            MainMenuOption::Redo => HistoryController::new(sys, CliHistoryView::new()).redo(),
+            MainMenuOption::Logout => {
+                sys.logout();
+                self.login()
             }
             MainMenuOption::Quit => std::process::exit(0),
-            _ => sys,
-        };
+            _ => {}
+        }
 
-        self.show(state)
+        self.show()
     }
 }
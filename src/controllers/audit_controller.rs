@@ -0,0 +1,86 @@
+use super::{app::Page, history_controller::HistoryController};
+use crate::{
+    models::domain::{auth::Action, system::LendingSystem},
+    types::{Model, View},
+    views::audit_view::{AuditOption, AuditView},
+    views::history_view::CliHistoryView,
+};
+use shared::controller;
+
+/// The audit log page.
+#[derive(Debug)]
+#[controller(AuditView)]
+pub struct AuditController<'a, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + AuditView,
+{
+    model: &'a mut M,
+    view: V,
+}
+
+impl<M, V> AuditController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + AuditView,
+{
+    fn display_entries(&self, by_entity: bool, by_actor: bool) {
+        let entity = match by_entity {
+            true => match self.view.get_entity() {
+                Some(entity) => Some(entity),
+                None => return,
+            },
+            false => None,
+        };
+        let members = self.model.get_members();
+        let actor = match by_actor {
+            true => match self.view.select_actor(members) {
+                Some(member) => Some(member.get_uuid().clone()),
+                None => return,
+            },
+            false => None,
+        };
+        let entries = self.model.get_audit_entries(entity, actor.as_ref());
+        self.view.display_entries(entries);
+        self.view.wait("");
+    }
+
+    fn authorized(&self) -> bool {
+        match self.model.authorize(Action::ViewAuditLog) {
+            Ok(_) => true,
+            Err(err) => {
+                self.view.wait(err.to_string().as_str());
+                false
+            }
+        }
+    }
+}
+
+impl<M, V> Page for AuditController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + AuditView,
+{
+    fn show(&mut self) {
+        if !self.authorized() {
+            return;
+        }
+        let choice = self.view.audit_menu();
+        match choice {
+            AuditOption::ShowAll => self.display_entries(false, false),
+            AuditOption::FilterByEntity => self.display_entries(true, false),
+            AuditOption::FilterByActor => self.display_entries(false, true),
+            AuditOption::FilterByEntityAndActor => self.display_entries(true, true),
+            AuditOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            AuditOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
+            AuditOption::Back => return,
+            AuditOption::Quit => std::process::exit(0),
+            AuditOption::Other => {}
+        }
+        self.show()
+    }
+}
@@ -1,40 +1,51 @@
-use super::app::Page;
+use super::{app::Page, history_controller::HistoryController};
 use crate::{
-    models::domain::{contract::Contract, item::Item, system::LendingSystem},
+    errors::Context,
+    models::{
+        domain::{
+            auth::Action,
+            command::{BookItem, EditContract},
+            contract::Contract,
+            item::Item,
+            system::LendingSystem,
+        },
+        export::{Sheet, EXPORT_DIR},
+    },
     types::{Model, View},
     views::{
         contract_view::{ContractOption, ContractView},
+        history_view::CliHistoryView,
         item_view::{CliItemView, ItemView},
         member_view::{CliMemberView, MemberView},
     },
 };
 use shared::controller;
+use std::path::Path;
 
 /// The Contract controller.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 #[controller(ContractView)]
-pub struct ContractController<M, V>
+pub struct ContractController<'a, M, V>
 where
     M: Model + LendingSystem,
     V: View + ContractView,
 {
-    model: M,
+    model: &'a mut M,
     view: V,
 }
 
-impl<M, V> ContractController<M, V>
+impl<M, V> ContractController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + ContractView,
 {
-    fn ret(&self, display: &str) -> M {
+    fn ret(&self, display: &str) {
         self.view.wait(display);
-        self.model.clone()
     }
 
     fn fetch_item(&self) -> Option<Item> {
         let item_view: CliItemView = CliItemView::new();
-        let items = self.model.get_items();
+        let items = self.model.get_bookable_items();
         let item = item_view.select_item(items);
         if let Some(i) = item {
             return Some(i.clone());
@@ -42,116 +53,150 @@ where
         None
     }
 
-    fn display_contract_simple(&self) -> M {
+    fn export_contracts(&self) {
+        if let Some(format) = self.view.get_export_format() {
+            let mut models = self
+                .model
+                .get_items()
+                .into_iter()
+                .flat_map(|i| i.get_history().iter())
+                .collect::<Vec<&Contract>>();
+            models.sort_by_key(|c| (*c.get_start_date(), *c.get_end_date()));
+            let sheets = vec![Sheet::from_models("Contracts", models)];
+            match Sheet::export(&sheets, format, Path::new(EXPORT_DIR)) {
+                Ok(path) => self.ret(format!("Exported to {}.", path.display()).as_str()),
+                Err(err) => self.ret(err.to_string().as_str()),
+            }
+        }
+    }
+
+    fn display_contract_simple(&self) {
         let iview = CliItemView::new();
         let items = self.model.get_items();
         let item: Option<&Item> = iview.select_item(items);
-        match item {
-            Some(i) => {
-                let cs = i.get_history().iter().collect::<Vec<&Contract>>();
-                let contract = self.view.select_contract(cs);
-                match contract {
-                    Some(c) => {
-                        self.view.display_contract_simple(c);
-                        self.ret("")
-                    }
-                    None => self.model.clone(),
-                }
+        if let Some(i) = item {
+            let cs = i.get_history().iter().collect::<Vec<&Contract>>();
+            let contract = self.view.select_contract(cs);
+            if let Some(c) = contract {
+                self.view.display_contract_simple(c);
+                self.ret("")
             }
-            None => self.model.clone(),
         }
     }
 
-    fn create_contract(&mut self) -> M {
+    fn create_contract(&mut self) {
         let mview = CliMemberView::new();
         let iview = CliItemView::new();
-        let old_item = self.fetch_item();
-        match old_item {
-            Some(i) => match mview.select_member(self.model.get_members()) {
-                Some(lendee) => {
-                    if lendee == i.get_owner() {
-                        return self.ret("Cannot lend to yourself.");
-                    }
-                    match iview.select_date(self.model.now(), &i) {
-                        Some(start_date) => {
-                            let data = self.view.get_contract_info();
-                            let contract = Contract::new(
-                                i.get_owner().clone(),
-                                lendee.clone(),
-                                start_date,
-                                *data.get_contract_len(),
-                                *i.get_cost_per_day() * *data.get_contract_len() as f64,
-                            );
-                            let mut temp = i.clone();
-                            if let false = lendee.get_credits() >= contract.get_credits() {
-                                return self.ret("Lendee doesnt have enough credits.");
-                            };
-                            match temp.add_contract(contract) {
-                                Ok(_) => match self.model.update_item(&temp) {
-                                    Ok(_) => {
-                                        iview.display_item_info(&temp);
-                                        self.ret("Successfully created contract.")
-                                    }
-                                    Err(_) => self.ret("Failed to create contrct."),
-                                },
-                                Err(_) => self.ret("Item already booked during that period."),
-                            }
-                        }
-                        None => self.model.clone(),
+        let i = match self.fetch_item() {
+            Some(i) => i,
+            None => return,
+        };
+        let members = self
+            .model
+            .get_members()
+            .into_iter()
+            .filter(|m| m.is_active())
+            .collect();
+        let lendee = match mview.select_member(members) {
+            Some(lendee) => lendee.clone(),
+            None => return,
+        };
+        if self
+            .model
+            .authorize(Action::CreateContract(&lendee))
+            .is_err()
+        {
+            return self.ret("You can only create contracts for yourself.");
+        }
+        if lendee.get_uuid() == i.get_owner().get_uuid() {
+            return self.ret("Cannot lend to yourself.");
+        }
+        if let Some(start_date) = iview.select_date(self.model.now(), &i) {
+            let data = self.view.get_contract_info();
+            let contract = Contract::new(
+                i.get_owner().clone(),
+                lendee.clone(),
+                start_date,
+                *data.get_contract_len(),
+                *i.get_cost_per_day() * *data.get_contract_len() as f64,
+            )
+            .uuid(self.model.generate_id())
+            .build();
+            if lendee.get_credits() < contract.get_credits() {
+                return self.ret("Lendee doesnt have enough credits.");
+            }
+            let unit = match *i.get_quantity() > 1 {
+                true => iview.select_unit(&i),
+                false => None,
+            };
+            let mut command = BookItem {
+                item: i.clone(),
+                contract,
+                unit,
+            };
+            match self.model.execute(&mut command).context(&i) {
+                Ok(_) => {
+                    if let Ok(updated) = self.model.get_item(&i) {
+                        iview.display_item_info(&updated);
                     }
+                    self.ret("Successfully created contract.")
                 }
-                None => self.model.clone(),
-            },
-            None => self.model.clone(),
+                Err(err) => self.ret(err.to_string().as_str()),
+            }
         }
     }
 
-    fn edit_contract(&mut self) -> M {
+    fn edit_contract(&mut self) {
         let iview = CliItemView::new();
-        let item = iview.select_item(self.model.get_items());
-        match item {
-            Some(i) => {
-                let mut history = i.get_history().clone();
-                let contract = self
-                    .view
-                    .select_contract(history.iter().collect::<Vec<&Contract>>());
-                match contract {
-                    Some(c) => {
-                        let new_contract_info = self.view.edit_contract(c);
-                        match new_contract_info {
-                            Some(new_contract) => {
-                                let idx = history.index_of(&new_contract).unwrap();
-                                history.set(idx, &new_contract);
-                                self.view.wait("Updated contract successfully.")
-                            }
-                            None => {
-                                self.view.wait("Couldnt edit contract.");
-                            }
+        let item = iview.select_item(self.model.get_items()).cloned();
+        if let Some(i) = item {
+            let contract = self
+                .view
+                .select_contract(i.get_history().iter().collect::<Vec<&Contract>>());
+            match contract {
+                Some(c) if self.model.authorize(Action::EditContract(c)).is_err() => {
+                    self.ret("You are only allowed to edit your own contracts.")
+                }
+                Some(c) => match self.view.edit_contract(c) {
+                    Some(new_contract) => {
+                        let mut command = EditContract {
+                            item: i.clone(),
+                            contract: new_contract,
                         };
-                        self.model.clone()
+                        match self.model.execute(&mut command).context(&i) {
+                            Ok(_) => self.ret("Updated contract successfully."),
+                            Err(err) => self.ret(err.to_string().as_str()),
+                        }
                     }
-                    None => self.model.clone(),
-                }
+                    None => self.ret("Couldnt edit contract."),
+                },
+                None => {}
             }
-            None => self.model.clone(),
         }
     }
 }
 
-impl<M, V> Page<M> for ContractController<M, V>
+impl<M, V> Page for ContractController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + ContractView,
 {
-    fn show(&mut self, sys: M) -> M {
+    fn show(&mut self) {
         let choice = self.view.contract_menu();
-        let state = match choice {
+        match choice {
             ContractOption::DisplayContractSimple => self.display_contract_simple(),
             ContractOption::CreateContract => self.create_contract(),
             ContractOption::EditContract => self.edit_contract(),
+            ContractOption::ExportContracts => self.export_contracts(),
+            ContractOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            ContractOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
             ContractOption::Quit => std::process::exit(0),
-            _ => return sys,
-        };
-        self.show(state)
+            _ => return,
+        }
+        self.show()
     }
 }
@@ -0,0 +1,91 @@
+use super::{app::Page, history_controller::HistoryController};
+use crate::{
+    models::domain::{auth::Action, invariants::Violation, system::LendingSystem},
+    types::{Model, View},
+    views::diagnostics_view::{DiagnosticsOption, DiagnosticsView},
+    views::history_view::CliHistoryView,
+};
+use shared::controller;
+
+/// The diagnostics page.
+#[derive(Debug)]
+#[controller(DiagnosticsView)]
+pub struct DiagnosticsController<'a, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + DiagnosticsView,
+{
+    model: &'a mut M,
+    view: V,
+}
+
+impl<M, V> DiagnosticsController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + DiagnosticsView,
+{
+    fn check_invariants(&self) {
+        let broken = self.model.get_broken_invariants();
+        let violations = self
+            .model
+            .check_invariants()
+            .into_iter()
+            .map(|violation| {
+                let step = broken
+                    .iter()
+                    .find(|(_, known)| *known == violation)
+                    .map(|(step, _)| step.clone());
+                (violation, step)
+            })
+            .collect::<Vec<(Violation, Option<String>)>>();
+        let count = violations.len();
+        self.view.display_violations(violations);
+        match count {
+            0 => self.view.wait(""),
+            n => self.view.wait(format!("Found {} violations.", n).as_str()),
+        }
+    }
+
+    fn show_ledger(&self) {
+        let ledger = self.model.get_ledger();
+        self.view.display_ledger(ledger.get_mints(), ledger.total());
+        self.view.wait("");
+    }
+
+    fn authorized(&self) -> bool {
+        match self.model.authorize(Action::RunDiagnostics) {
+            Ok(_) => true,
+            Err(err) => {
+                self.view.wait(err.to_string().as_str());
+                false
+            }
+        }
+    }
+}
+
+impl<M, V> Page for DiagnosticsController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + DiagnosticsView,
+{
+    fn show(&mut self) {
+        if !self.authorized() {
+            return;
+        }
+        let choice = self.view.diagnostics_menu();
+        match choice {
+            DiagnosticsOption::CheckInvariants => self.check_invariants(),
+            DiagnosticsOption::ShowLedger => self.show_ledger(),
+            DiagnosticsOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            DiagnosticsOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
+            DiagnosticsOption::Back => return,
+            DiagnosticsOption::Quit => std::process::exit(0),
+            DiagnosticsOption::Other => {}
+        }
+        self.show()
+    }
+}
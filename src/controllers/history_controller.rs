@@ -0,0 +1,48 @@
+use crate::{
+    models::domain::system::LendingSystem,
+    types::SysResult,
+    types::{Model, View},
+    views::history_view::HistoryView,
+};
+use shared::controller;
+
+/// Undoes and redoes commands, reachable from every menu.
+#[derive(Debug)]
+#[controller(HistoryView)]
+pub struct HistoryController<'a, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + HistoryView,
+{
+    model: &'a mut M,
+    view: V,
+}
+
+impl<M, V> HistoryController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + HistoryView,
+{
+    /// Undoes the most recent command.
+    pub fn undo(&mut self) {
+        let result = self.model.undo();
+        self.display(result, "Undone")
+    }
+
+    /// Redoes the most recently undone command.
+    pub fn redo(&mut self) {
+        let result = self.model.redo();
+        self.display(result, "Redone")
+    }
+
+    fn display(&self, result: SysResult<String>, done: &str) {
+        self.view
+            .display_steps(self.model.get_undo_steps(), self.model.get_redo_steps());
+        match result {
+            Ok(description) => self
+                .view
+                .wait(format!("{}: {}.", done, description).as_str()),
+            Err(err) => self.view.wait(err.to_string().as_str()),
+        }
+    }
+}
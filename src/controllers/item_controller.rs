@@ -1,136 +1,241 @@
-use super::app::Page;
+use super::{app::Page, history_controller::HistoryController};
 use crate::{
-    models::domain::{item::Item, system::LendingSystem},
+    errors::{Context, LendingError},
+    models::{
+        domain::{
+            auth::Action,
+            command::{CreateItem, DeleteItem, EditItem},
+            item::Item,
+            system::LendingSystem,
+        },
+        export::{Sheet, EXPORT_DIR},
+    },
     types::{Model, View},
     views::{
+        history_view::CliHistoryView,
         item_view::{ItemMenuOption, ItemView},
         member_view::{CliMemberView, MemberView},
     },
 };
 use shared::controller;
+use std::path::Path;
 
 /// Item Controller.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 #[controller(ItemView)]
-pub struct ItemController<M, V>
+pub struct ItemController<'a, M, V>
 where
     M: Model + LendingSystem,
     V: View + ItemView,
 {
-    model: M,
+    model: &'a mut M,
     view: V,
 }
 
-impl<M, V> ItemController<M, V>
+impl<M, V> ItemController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + ItemView,
 {
-    fn ret(&self, display: &str) -> M {
+    fn ret(&self, display: &str) {
         self.view.wait(display);
-        self.model.clone()
     }
 
-    fn display_item_info(&self) -> M {
+    fn export_items(&self) {
+        if let Some(format) = self.view.get_export_format() {
+            let mut models = self.model.get_items();
+            models.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+            let sheets = vec![Sheet::from_models("Items", models)];
+            match Sheet::export(&sheets, format, Path::new(EXPORT_DIR)) {
+                Ok(path) => self.ret(format!("Exported to {}.", path.display()).as_str()),
+                Err(err) => self.ret(err.to_string().as_str()),
+            }
+        }
+    }
+
+    fn display_item_info(&self) {
         let items = self.model.get_items();
         let item: Option<&Item> = self.view.select_item(items);
-        match item {
-            Some(i) => {
-                self.view.display_item_info(i);
-                self.ret("")
-            }
-            None => self.model.clone(),
+        if let Some(i) = item {
+            self.view.display_item_info(i);
+            self.ret("")
         }
     }
 
-    fn edit_item(&mut self) -> M {
-        let model = self.model.clone();
-        let items = model.get_items();
-        let item_to_edit = self.view.select_item(items);
-        match item_to_edit {
+    fn edit_item(&mut self) {
+        let item_to_edit = self.view.select_item(self.model.get_items()).cloned();
+        match &item_to_edit {
+            Some(i)
+                if self
+                    .model
+                    .authorize(Action::ManageItems(i.get_owner()))
+                    .is_err() =>
+            {
+                self.ret("You are only allowed to edit your own items.")
+            }
             Some(i) => {
                 let new_info = self.view.edit_item_info(i);
-                match new_info {
-                    Some(info) => match self.model.update_item(&info) {
+                if let Some(info) = new_info {
+                    match self.model.execute(&mut EditItem(info)).context(i) {
                         Ok(_) => self.ret("Updated item data successfully."),
-                        Err(_) => self.ret("Unable to update item information."),
-                    },
-                    None => self.model.clone(),
+                        Err(err) => self.ret(err.to_string().as_str()),
+                    }
                 }
             }
-            None => self.model.clone(),
+            None => {}
         }
     }
 
-    fn create_item(&mut self) -> M {
-        let model = self.model.clone();
+    fn create_item(&mut self) {
         let mv: CliMemberView = CliMemberView::new();
-        let members = model.get_members();
-        let owner = mv.select_member(members);
-        match owner {
+        let owner = mv.select_member(self.model.get_members()).cloned();
+        match &owner {
+            Some(o) if self.model.authorize(Action::ManageItems(o)).is_err() => {
+                self.ret("You are only allowed to create items for yourself.")
+            }
             Some(o) => {
-                let item = self.view.get_item_info().owner(o.clone()).build();
-                match self.model.add_item(item) {
-                    Ok(_) => {
-                        let mut updated_owner = o.clone();
-                        match updated_owner.add_credits(100f64) {
-                            Ok(_) => match self.model.update_member(o, &updated_owner) {
-                                Ok(_) => self.ret("Item created successfully."),
-                                Err(err) => self.ret(err.to_string().as_str()),
-                            },
-                            Err(err) => self.ret(err.to_string().as_str()),
-                        }
-                    }
+                let item = self
+                    .view
+                    .get_item_info()
+                    .owner(o.clone())
+                    .uuid(self.model.generate_id())
+                    .build();
+                // The system rewards the owner for listing the item.
+                match self.model.execute(&mut CreateItem(item)) {
+                    Ok(_) => self.ret("Item created successfully."),
                     Err(err) => self.ret(err.to_string().as_str()),
                 }
             }
-            None => self.model.clone(),
+            None => {}
         }
     }
 
-    fn delete_item(&mut self) -> M {
-        let model = self.model.clone();
-        let items = model.get_items();
-        let item_to_delete = self.view.select_item(items);
-        match item_to_delete {
-            Some(item) => match self.model.remove_item(item) {
+    fn delete_item(&mut self) {
+        let item_to_delete = self.view.select_item(self.model.get_items()).cloned();
+        match &item_to_delete {
+            Some(item)
+                if self
+                    .model
+                    .authorize(Action::ManageItems(item.get_owner()))
+                    .is_err() =>
+            {
+                self.ret("You are only allowed to delete your own items.")
+            }
+            Some(item) => match self
+                .model
+                .execute(&mut DeleteItem(item.clone()))
+                .context(item)
+            {
                 Ok(_) => self.ret("Successfully removed item."),
-                Err(_) => self.ret("Unable to remove item"),
+                Err(err) => self.ret(err.to_string().as_str()),
             },
-            None => self.model.clone(),
+            None => {}
         }
     }
 
-    fn show_availability(&self) -> M {
-        let item = self.view.select_item(self.model.get_items());
-        match item {
+    fn search_items(&self) {
+        let query = self.view.get_search_query();
+        let results = self.model.search_items(&query);
+        if results.is_empty() {
+            return self.ret("No items match your search.");
+        }
+        if let Some(i) = self.view.select_item(results) {
+            self.view.display_item_info(i);
+            self.ret("")
+        }
+    }
+
+    fn add_blackout(&mut self) {
+        let item = self.view.select_item(self.model.get_items()).cloned();
+        match &item {
+            Some(i)
+                if self
+                    .model
+                    .authorize(Action::ManageItems(i.get_owner()))
+                    .is_err() =>
+            {
+                self.ret("You are only allowed to block your own items.")
+            }
             Some(i) => {
-                self.view.display_availability(self.model.now(), &i);
-                self.ret("")
+                self.view.display_availability(self.model.now(), i);
+                if let Some(period) = self.view.get_period(self.model.now()) {
+                    let mut updated = i.clone();
+                    match updated.add_blackout(period) {
+                        Ok(_) => match self.model.execute(&mut EditItem(updated)).context(i) {
+                            Ok(_) => self.ret("Item blocked successfully."),
+                            Err(err) => self.ret(err.to_string().as_str()),
+                        },
+                        Err(err) => self.ret(LendingError::new(i, err).to_string().as_str()),
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn remove_blackout(&mut self) {
+        let item = self.view.select_item(self.model.get_items()).cloned();
+        match &item {
+            Some(i)
+                if self
+                    .model
+                    .authorize(Action::ManageItems(i.get_owner()))
+                    .is_err() =>
+            {
+                self.ret("You are only allowed to unblock your own items.")
+            }
+            Some(i) => {
+                if let Some(period) = self.view.select_blackout(i) {
+                    let mut updated = i.clone();
+                    match updated.remove_blackout(&period) {
+                        Ok(_) => match self.model.execute(&mut EditItem(updated)).context(i) {
+                            Ok(_) => self.ret("Item unblocked successfully."),
+                            Err(err) => self.ret(err.to_string().as_str()),
+                        },
+                        Err(err) => self.ret(err.to_string().as_str()),
+                    }
+                }
             }
-            None => self.model.clone(),
+            None => {}
+        }
+    }
+
+    fn show_availability(&self) {
+        let item = self.view.select_item(self.model.get_items());
+        if let Some(i) = item {
+            self.view.display_availability(self.model.now(), i);
+            self.ret("")
         }
     }
 }
 
-impl<M, V> Page<M> for ItemController<M, V>
+impl<M, V> Page for ItemController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + ItemView,
 {
-    fn show(&mut self, sys: M) -> M {
+    fn show(&mut self) {
         let choice = self.view.item_menu();
-        self.model = sys.clone();
-        let state = match choice {
+        match choice {
             ItemMenuOption::DisplayItemInfo => self.display_item_info(),
             ItemMenuOption::EditItemInfo => self.edit_item(),
             ItemMenuOption::CreateItem => self.create_item(),
             ItemMenuOption::DeleteItem => self.delete_item(),
             ItemMenuOption::ShowAvailability => self.show_availability(),
+            ItemMenuOption::SearchItems => self.search_items(),
+            ItemMenuOption::AddBlackout => self.add_blackout(),
+            ItemMenuOption::RemoveBlackout => self.remove_blackout(),
+            ItemMenuOption::ExportItems => self.export_items(),
+            ItemMenuOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            ItemMenuOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
             ItemMenuOption::Quit => std::process::exit(0),
-            ItemMenuOption::Back => return sys,
-            ItemMenuOption::Other => sys,
-        };
-        self.show(state)
+            ItemMenuOption::Back => return,
+            ItemMenuOption::Other => {}
+        }
+        self.show()
     }
 }
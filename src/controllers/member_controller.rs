@@ -1,107 +1,223 @@
-use super::app::Page;
+use super::{app::Page, history_controller::HistoryController};
 use crate::{
-    models::domain::{item::Item, member::Member, system::LendingSystem},
-    types::{Model, Validate, View},
-    views::member_view::{MemberMenuOption, MemberView},
+    errors::{Check, Context, FieldError, MemValError, SysError},
+    models::{
+        domain::{
+            auth::Action,
+            command::{CreateMember, DeleteMember, EditMember},
+            item::Item,
+            member::{Member, MemberStatus},
+            system::LendingSystem,
+        },
+        export::{Sheet, EXPORT_DIR},
+    },
+    types::{Model, SysResult, ValResult, View},
+    views::{
+        history_view::CliHistoryView,
+        member_view::{MemberMenuOption, MemberView},
+    },
 };
 use shared::controller;
+use std::path::Path;
+
+/// Turns an email or phone number that another member already uses into an invalid
+/// field, so the form asks for it again like for any other invalid field.
+fn ask_again(unique: SysResult<()>, member: Member) -> ValResult<Member> {
+    match unique {
+        Err(SysError::Validation(MemValError::NotUnique(field))) => {
+            Err(Check::Invalid(vec![FieldError::new(
+                field.get_field(),
+                MemValError::NotUnique(field),
+            )]))
+        }
+        _ => Ok(member),
+    }
+}
 
 /// The member controller.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 #[controller(MemberView)]
-pub struct MemberController<M, V>
+pub struct MemberController<'a, M, V>
 where
     M: Model + LendingSystem,
     V: View + MemberView,
 {
-    model: M,
+    model: &'a mut M,
     view: V,
 }
 
-impl<M, V> MemberController<M, V>
+impl<M, V> MemberController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + MemberView,
 {
-    fn ret(&self, display: &str) -> M {
+    fn ret(&self, display: &str) {
         self.view.wait(display);
-        self.model.clone()
     }
 
-    fn display_member_simple(&mut self) -> M {
+    fn export_members(&self) {
+        if let Some(format) = self.view.get_export_format() {
+            let mut models = self.model.get_members();
+            models.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+            let sheets = vec![Sheet::from_models("Members", models)];
+            match Sheet::export(&sheets, format, Path::new(EXPORT_DIR)) {
+                Ok(path) => self.ret(format!("Exported to {}.", path.display()).as_str()),
+                Err(err) => self.ret(err.to_string().as_str()),
+            }
+        }
+    }
+
+    fn display_member_simple(&mut self) {
         let members_vec = self.model.get_members();
         let member = self.view.select_member(members_vec);
-        match member {
-            Some(m) => {
-                let number_of_items = self.model.count_items_for_member(m);
-                self.view.display_member_simple(m, number_of_items);
-                self.ret("")
-            }
-            None => self.model.clone(),
+        if let Some(m) = member {
+            let number_of_items = self.model.count_items_for_member(m);
+            self.view.display_member_simple(m, number_of_items);
+            self.ret("")
         }
     }
 
-    fn display_member_verbose(&mut self) -> M {
+    fn display_member_verbose(&mut self) {
         let members_vec = self.model.get_members();
         let member = self.view.select_member(members_vec);
-        match member {
-            Some(m) => {
-                let items = self.model.get_items_for_member(m);
-                self.view.display_member_verbose(m, items);
-                self.ret("")
-            }
-            None => self.model.clone(),
+        if let Some(m) = member {
+            let items = self.model.get_items_for_member(m);
+            self.view.display_member_verbose(m, items);
+            self.ret("")
         }
     }
 
-    fn create_member(&mut self) -> M {
-        let new_member = self.view.get_member_info();
-        if self.model.exists_member(&new_member) {
-            self.view
-                .wait("A member already exists with that email/phone number.");
-            return self.create_member();
+    fn create_member(&mut self) {
+        if let Err(err) = self.model.authorize(Action::CreateMember) {
+            return self.ret(err.to_string().as_str());
         }
-        match new_member.validate_and_build() {
-            Ok(member) => match self.model.add_member(member) {
-                Ok(_) => self.ret("Member created successfully."),
-                Err(_) => self.ret("Unable to create member, please try again."),
-            },
+        let rules = *self.model.get_contact_rules();
+        let uuid = self.model.generate_id();
+        let model = &*self.model;
+        let validate = |m: Member| {
+            let member = m.uuid(uuid.clone()).build().validate_with(&rules)?;
+            ask_again(model.check_unique(&member), member)
+        };
+        let mut new_member = match self.view.get_member_info(&validate) {
+            Some(m) => m,
+            None => return,
+        };
+        new_member.set_password(self.view.get_password().as_str());
+        match self
+            .model
+            .execute(&mut CreateMember(new_member.clone()))
+            .context(&new_member)
+        {
+            Ok(_) => self.ret("Member created successfully."),
             Err(err) => self.ret(err.to_string().as_str()),
         }
     }
 
-    fn delete_member(&mut self) -> M {
-        let model = self.model.clone();
-        let member_to_delete: Option<&Member> = self.view.select_member(model.get_members());
+    fn delete_member(&mut self) {
+        let member_to_delete = self.view.select_member(self.model.get_members()).cloned();
 
-        match member_to_delete {
-            Some(m) => match self.model.remove_member(m) {
-                Ok(_) => self.model.clone(),
-                Err(_) => self.ret("There was a problem deleting the member."),
+        match &member_to_delete {
+            Some(m) if self.model.authorize(Action::DeleteMember(m)).is_err() => {
+                self.ret("You are only allowed to delete your own account.")
+            }
+            Some(m) => match self.model.execute(&mut DeleteMember(m.clone())).context(m) {
+                Ok(_) => self.ret("Member deleted successfully."),
+                Err(err) => self.ret(err.to_string().as_str()),
             },
-            None => self.model.clone(),
+            None => {}
         }
     }
 
-    fn edit_member(&mut self) -> M {
-        let model = self.model.clone();
-        let member_to_edit: Option<&Member> = self.view.select_member(model.get_members());
-        match member_to_edit {
+    fn edit_member(&mut self) {
+        let member_to_edit = self.view.select_member(self.model.get_members()).cloned();
+        match &member_to_edit {
+            Some(mem) if self.model.authorize(Action::EditMember(mem)).is_err() => {
+                self.ret("You are only allowed to edit your own profile.")
+            }
             Some(mem) => {
-                let new_info = self.view.edit_member_info(mem);
-                match new_info {
-                    Some(info) => match self.model.update_member(mem, &info) {
+                let rules = *self.model.get_contact_rules();
+                let model = &*self.model;
+                let validate = |m: Member| {
+                    let member = m.validate_with(&rules)?;
+                    ask_again(model.check_unique_contact(&member), member)
+                };
+                let new_info = self.view.edit_member_info(mem, &validate);
+                if let Some(info) = new_info {
+                    let mut command = EditMember {
+                        old: mem.clone(),
+                        new: info,
+                    };
+                    match self.model.execute(&mut command).context(mem) {
                         Ok(_) => self.ret("Member updated successfully."),
-                        Err(_) => self.ret("There was a problem updating the member information."),
-                    },
-                    None => todo!(),
+                        Err(err) => self.ret(err.to_string().as_str()),
+                    }
                 }
             }
-            None => self.model.clone(),
+            None => {}
+        }
+    }
+
+    fn show_inbox(&mut self) {
+        match self.model.get_current_user() {
+            Some(user) => {
+                self.view
+                    .display_inbox(user, self.model.get_notifications(user));
+            }
+            None => self.ret("Nobody is logged in."),
         }
     }
 
-    fn display_all_members_simple(&self) -> M {
+    fn change_password(&mut self) {
+        let member = self.view.select_member(self.model.get_members()).cloned();
+        match &member {
+            Some(mem) if self.model.authorize(Action::EditMember(mem)).is_err() => {
+                self.ret("You are only allowed to change your own password.")
+            }
+            Some(mem) => {
+                let mut updated = mem.clone();
+                updated.set_password(self.view.get_password().as_str());
+                let mut command = EditMember {
+                    old: mem.clone(),
+                    new: updated,
+                };
+                match self.model.execute(&mut command).context(mem) {
+                    Ok(_) => self.ret("Password changed successfully."),
+                    Err(err) => self.ret(err.to_string().as_str()),
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn moderate_member(&mut self) {
+        let member = self.view.select_member(self.model.get_members()).cloned();
+        match &member {
+            Some(mem) if self.model.authorize(Action::ModerateMember(mem)).is_err() => {
+                self.ret("Only admins are allowed to moderate members.")
+            }
+            Some(mem) => {
+                if let Some((status, reason)) = self.view.get_moderation(self.model.now()) {
+                    let mut updated = mem.clone();
+                    match status {
+                        MemberStatus::Active => updated.reinstate(),
+                        MemberStatus::Suspended(until) => updated.suspend(until, &reason),
+                        MemberStatus::Banned => updated.ban(&reason),
+                    }
+                    let mut command = EditMember {
+                        old: mem.clone(),
+                        new: updated,
+                    };
+                    match self.model.execute(&mut command).context(mem) {
+                        Ok(_) => self.ret(format!("Member is now {}.", status).as_str()),
+                        Err(err) => self.ret(err.to_string().as_str()),
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn display_all_members_simple(&self) {
         let members = self.model.get_members();
         let mut item_counts: Vec<usize> = Vec::new();
         for member in members.iter() {
@@ -113,10 +229,9 @@ where
             .zip(item_counts)
             .collect::<Vec<(&Member, usize)>>();
         self.view.display_all_simple(tples);
-        self.model.clone()
     }
 
-    fn display_all_members_verbose(&self) -> M {
+    fn display_all_members_verbose(&self) {
         let members = self.model.get_members();
         let mut items: Vec<Vec<&Item>> = Vec::new();
         for member in members.iter() {
@@ -128,19 +243,17 @@ where
             .zip(items)
             .collect::<Vec<(&Member, Vec<&Item>)>>();
         self.view.display_all_verbose(tples);
-        self.model.clone()
     }
 }
 
-impl<M, V> Page<M> for MemberController<M, V>
+impl<M, V> Page for MemberController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + MemberView,
 {
-    fn show(&mut self, sys: M) -> M {
+    fn show(&mut self) {
         let choice = self.view.member_menu();
-        self.model = sys.clone();
-        let state = match choice {
+        match choice {
             MemberMenuOption::DisplayMemberSimple => self.display_member_simple(),
             MemberMenuOption::DisplayMemberVerbose => self.display_member_verbose(),
             MemberMenuOption::ListAllMembersSimple => self.display_all_members_simple(),
@@ -148,10 +261,20 @@ where
             MemberMenuOption::CreateMember => self.create_member(),
             MemberMenuOption::DeleteMember => self.delete_member(),
             MemberMenuOption::EditMember => self.edit_member(),
+            MemberMenuOption::ChangePassword => self.change_password(),
+            MemberMenuOption::ModerateMember => self.moderate_member(),
+            MemberMenuOption::ShowInbox => self.show_inbox(),
+            MemberMenuOption::ExportMembers => self.export_members(),
+            MemberMenuOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            MemberMenuOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
             MemberMenuOption::Quit => std::process::exit(0),
-            MemberMenuOption::Back => return sys,
-            MemberMenuOption::Other => sys,
-        };
-        self.show(state)
+            MemberMenuOption::Back => return,
+            MemberMenuOption::Other => {}
+        }
+        self.show()
     }
 }
@@ -1,10 +1,18 @@
 /// Main App.
 pub mod app;
+/// Audit Log.
+pub mod audit_controller;
 /// Contract Controller.
 pub mod contract_controller;
+/// Diagnostics.
+pub mod diagnostics_controller;
+/// Undo and Redo.
+pub mod history_controller;
 /// Item Controller.
 pub mod item_controller;
 /// Member Controller.
 pub mod member_controller;
+/// Reports.
+pub mod report_controller;
 /// Simulator,
 pub mod simulator_controller;
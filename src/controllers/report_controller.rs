@@ -0,0 +1,95 @@
+use super::{app::Page, history_controller::HistoryController};
+use crate::{
+    models::{
+        domain::{
+            report::{ReportKind, Reports},
+            system::LendingSystem,
+        },
+        export::{Sheet, EXPORT_DIR},
+    },
+    types::{Model, View},
+    views::history_view::CliHistoryView,
+    views::report_view::{ReportOption, ReportView},
+};
+use shared::controller;
+use std::path::Path;
+
+/// The reports page.
+#[derive(Debug)]
+#[controller(ReportView)]
+pub struct ReportController<'a, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + ReportView,
+{
+    model: &'a mut M,
+    view: V,
+}
+
+impl<M, V> ReportController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + ReportView,
+{
+    fn display_reports(&self, kinds: Vec<ReportKind>) {
+        if let Some(period) = self.view.get_range(self.model.now()) {
+            let reports = Reports::new(self.model, period);
+            let sections = kinds.into_iter().map(|k| reports.section(k)).collect();
+            self.view.display_reports(&period, sections);
+            self.view.wait("");
+        }
+    }
+
+    fn export_reports(&self) {
+        let period = match self.view.get_range(self.model.now()) {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(format) = self.view.get_export_format() {
+            let sheets = Reports::new(self.model, period)
+                .sections()
+                .into_iter()
+                .map(Sheet::from)
+                .collect::<Vec<Sheet>>();
+            let message = match Sheet::export(&sheets, format, Path::new(EXPORT_DIR)) {
+                Ok(path) => format!("Exported to {}.", path.display()),
+                Err(err) => err.to_string(),
+            };
+            self.view.wait(message.as_str());
+        }
+    }
+}
+
+impl<M, V> Page for ReportController<'_, M, V>
+where
+    M: Model + LendingSystem,
+    V: View + ReportView,
+{
+    fn show(&mut self) {
+        let choice = self.view.report_menu();
+        match choice {
+            ReportOption::Utilization => self.display_reports(vec![ReportKind::Utilization]),
+            ReportOption::ItemRevenue => self.display_reports(vec![ReportKind::ItemRevenue]),
+            ReportOption::OwnerRevenue => self.display_reports(vec![ReportKind::OwnerRevenue]),
+            ReportOption::TopBorrowers => self.display_reports(vec![ReportKind::TopBorrowers]),
+            ReportOption::CreditDistribution => {
+                self.display_reports(vec![ReportKind::CreditDistribution])
+            }
+            ReportOption::CategoryPopularity => {
+                self.display_reports(vec![ReportKind::CategoryPopularity])
+            }
+            ReportOption::AllReports => self.display_reports(ReportKind::all()),
+            ReportOption::ExportReports => self.export_reports(),
+            ReportOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            ReportOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
+            ReportOption::Back => return,
+            ReportOption::Quit => std::process::exit(0),
+            ReportOption::Other => {}
+        }
+        self.show()
+    }
+}
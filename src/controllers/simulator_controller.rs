@@ -1,59 +1,142 @@
-use super::app::Page;
+use super::{app::Page, history_controller::HistoryController};
 use crate::{
-    models::domain::system::LendingSystem,
+    errors::SysError,
+    models::domain::{
+        auth::Action,
+        command::AdvanceTime,
+        community::simulate_community,
+        simulation::Advance,
+        system::{LendingSystem, System},
+    },
     types::{Model, View},
     views::{
         console::{Console, Ui},
+        history_view::CliHistoryView,
         simulator_view::{SimulatorOption, SimulatorView},
     },
 };
 use shared::controller;
 
 /// The simulator.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 #[controller(SimulatorView)]
-pub struct SimulatorController<M, V>
+pub struct SimulatorController<'a, M, V>
 where
     M: Model + LendingSystem,
     V: View + SimulatorView,
 {
-    model: M,
+    model: &'a mut M,
     view: V,
 }
 
-impl<M, V> SimulatorController<M, V>
+impl<M, V> SimulatorController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + SimulatorView,
 {
-    fn incr_day(&mut self) -> M {
-        match self.model.incr_time() {
-            Ok(_) => {}
-            Err(err) => {
-                let c = Console::new();
-                c.write(err.to_string().as_str());
-                c.wait("")
-            }
+    fn incr_day(&mut self) {
+        if !self.authorized() {
+            return;
+        }
+        let mut command = AdvanceTime::new(Advance::Days(1));
+        let settled = self.model.execute(&mut command).is_ok()
+            && command
+                .get_simulation()
+                .is_some_and(|s| s.get_days().iter().all(|d| *d.get_settled()));
+        if !settled {
+            let c = Console::new();
+            c.write(SysError::CannotUpdate.to_string().as_str());
+            c.wait("")
         }
         self.view
             .wait(format!("Day: {}", self.model.now()).as_str());
-        self.model.clone()
+    }
+
+    fn advance(&mut self, advance: Option<Advance>) {
+        let advance = match advance {
+            Some(a) => a,
+            None => return,
+        };
+        let mut command = AdvanceTime::new(advance.clone());
+        if let Err(err) = self.model.execute(&mut command) {
+            self.view.wait(err.to_string().as_str());
+            return;
+        }
+        let result = match command.get_simulation() {
+            Some(simulation) => simulation,
+            None => return,
+        };
+        self.view.display_summary(result.get_days());
+        let message = match (&advance, result.get_condition_met()) {
+            (Advance::Until(_), true) => format!("Stopped on day {}.", self.model.now()),
+            (Advance::Until(_), false) => format!(
+                "The condition did not hold, stopped on day {}.",
+                self.model.now()
+            ),
+            _ => format!("Day: {}", self.model.now()),
+        };
+        self.view.wait(message.as_str());
+    }
+
+    fn simulate_community(&mut self) {
+        if let Some(config) = self.view.get_community_config() {
+            // The synthetic members and items are kept out of the real system.
+            let mut sys = System::new();
+            let stats = simulate_community(&mut sys, &config);
+            self.view.display_community_stats(&stats);
+            self.view.wait("");
+        }
+    }
+
+    fn authorized(&self) -> bool {
+        match self.model.authorize(Action::AdvanceTime) {
+            Ok(_) => true,
+            Err(err) => {
+                self.view.wait(err.to_string().as_str());
+                false
+            }
+        }
     }
 }
 
-impl<M, V> Page<M> for SimulatorController<M, V>
+impl<M, V> Page for SimulatorController<'_, M, V>
 where
-    M: Model + LendingSystem + Clone,
+    M: Model + LendingSystem,
     V: View + SimulatorView,
 {
-    fn show(&mut self, sys: M) -> M {
+    fn show(&mut self) {
         let choice = self.view.simulator_menu();
-        let state = match choice {
+        match choice {
             SimulatorOption::IncrDay => self.incr_day(),
-            SimulatorOption::Back => return sys,
+            SimulatorOption::AdvanceDays if self.authorized() => {
+                let advance = self.view.get_days().map(Advance::Days);
+                self.advance(advance)
+            }
+            SimulatorOption::AdvanceToDay if self.authorized() => {
+                let advance = self.view.get_day(self.model.now()).map(Advance::ToDay);
+                self.advance(advance)
+            }
+            SimulatorOption::AdvanceUntil if self.authorized() => {
+                let advance = self
+                    .view
+                    .get_stop_condition(self.model.get_members())
+                    .map(Advance::Until);
+                self.advance(advance)
+            }
+            SimulatorOption::SimulateCommunity => self.simulate_community(),
+            SimulatorOption::AdvanceDays
+            | SimulatorOption::AdvanceToDay
+            | SimulatorOption::AdvanceUntil => {}
+            SimulatorOption::Undo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).undo()
+            }
+            SimulatorOption::Redo => {
+                HistoryController::new(&mut *self.model, CliHistoryView::new()).redo()
+            }
+            SimulatorOption::Back => return,
             SimulatorOption::Quit => std::process::exit(0),
-            SimulatorOption::Other => sys,
-        };
-        self.show(state)
+            SimulatorOption::Other => {}
+        }
+        self.show()
     }
 }
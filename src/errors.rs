@@ -1,7 +1,25 @@
+//! Errors of the lending system.
+//!
+//! `SysError` is the error every operation of the system returns, the other errors feed
+//! into it:
+//! - `MemValError` tells why a single value is invalid. It is small and `Copy`, so every
+//!   `FieldError` carries one and `SysError::Validation` wraps one.
+//! - `Check` is the outcome of validating a whole model. It lists every invalid field at
+//!   once, so forms can ask for each of them again, and becomes `SysError::InvalidFields`.
+//! - `LendingError` is a `SysError` together with the member, item or contract it concerns,
+//!   which controllers show to the user.
+//!
+//! They stay separate types, because validation runs before there is a system to ask,
+//! and the system does not know which entity the user acted on.
+use crate::models::{
+    domain::{audit::Entity, member::MemberStatus, member_index::UniqueField},
+    id::{Id, Kind},
+};
+use derive_getters::Getters;
 use thiserror::Error;
 
 /// Member Validation Error.
-#[derive(Debug, Error, PartialEq, Eq)]
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
 pub enum MemValError {
     /// Negative Credits
     NegativeCreditInput,
@@ -21,6 +39,10 @@ pub enum MemValError {
     PhoneNumberContainsNonNumeric,
     /// If the phone number doesnt match the validation pattern.
     PhoneNumberPattern,
+    /// If the member status is unknown.
+    Status,
+    /// Another member already uses the value.
+    NotUnique(UniqueField),
 }
 
 impl std::fmt::Display for MemValError {
@@ -42,31 +64,88 @@ impl std::fmt::Display for MemValError {
             MemValError::NegativeCreditInput => {
                 f.write_str("Tried adding/substracting negative amount of credits.")
             }
+            MemValError::Status => f.write_str("Unknown member status."),
+            MemValError::NotUnique(field) => {
+                write!(f, "A member with this {} already exists.", field)
+            }
             MemValError::DeduceAmountToHigh => f.write_str("The amount of credits to deduce is higher than the amount that the member currently owns.")
         }
     }
 }
 
-/// Member Validation Error.
-#[derive(Debug, Error, PartialEq, Eq)]
+/// A field that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct FieldError {
+    /// The name of the field, as in the map of the model.
+    #[getter(rename = "get_field")]
+    field: String,
+    /// Why the field is invalid.
+    #[getter(rename = "get_kind")]
+    kind: MemValError,
+    /// The message shown to the user.
+    #[getter(rename = "get_message")]
+    message: String,
+}
+
+impl FieldError {
+    /// Creates the error of a field.
+    pub fn new(field: &str, kind: MemValError) -> Self {
+        Self {
+            field: field.to_owned(),
+            kind,
+            message: kind.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// The outcome of validating a model.
+///
+/// An invalid model reports every field that failed validation, not only the first one.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum Check {
     Ok,
-    Invalid(String),
+    Invalid(Vec<FieldError>),
+}
+
+impl Check {
+    /// Returns the fields that failed validation.
+    pub fn get_errors(&self) -> &[FieldError] {
+        match self {
+            Check::Ok => &[],
+            Check::Invalid(errors) => errors,
+        }
+    }
+
+    /// Checks if the field failed validation.
+    pub fn is_invalid(&self, field: &str) -> bool {
+        self.get_errors().iter().any(|err| err.get_field() == field)
+    }
 }
 
 impl std::fmt::Display for Check {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Check::Ok => f.write_str("Ok"),
-            Check::Invalid(s) => f.write_str(s.as_str()),
+            Check::Invalid(errors) => f.write_str(
+                errors
+                    .iter()
+                    .map(|err| err.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+                    .as_str(),
+            ),
         }
     }
 }
 
-/// System error result.
-
 /// System Error.
-#[derive(Debug, Error, PartialEq, Eq)]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum SysError {
     /// If an object already exists.
     AlreadyExists,
@@ -78,16 +157,192 @@ pub enum SysError {
     CannotDelete,
     /// Cannot update an object.
     CannotUpdate,
+    /// The email/password combination is wrong.
+    InvalidCredentials,
+    /// Nobody is logged in.
+    NotLoggedIn,
+    /// The logged in member is not allowed to do this.
+    PermissionDenied,
+    /// The item is not available during that period.
+    Unavailable,
+    /// The item is already booked during that period.
+    Booked,
+    /// The member has been banned.
+    Banned,
+    /// A suspended or banned member cannot lend or borrow items.
+    Restricted(MemberStatus),
+    /// The input could not be parsed.
+    InvalidInput,
+    /// The export file could not be written.
+    CannotExport,
+    /// There is no command to undo.
+    NothingToUndo,
+    /// There is no undone command to redo.
+    NothingToRedo,
+    /// There is no transaction to commit or roll back.
+    NoTransaction,
+    /// The item needs at least this many units.
+    Quantity(usize),
+    /// The item still has contracts that have not started yet.
+    FutureContracts(usize),
+    /// The item is lent out in contracts that are running.
+    ActiveContracts(usize),
+    /// The member still owns this many items.
+    OwnsItems(usize),
+    /// A value failed validation.
+    Validation(MemValError),
+    /// Fields of a model failed validation.
+    InvalidFields(Vec<FieldError>),
 }
 
 impl std::fmt::Display for SysError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
+        match self {
             SysError::AlreadyExists => f.write_str("This object already exists."),
             SysError::DoesntExist => f.write_str("This object doesnt exists."),
             SysError::CannotInsert => f.write_str("There was an problem inserting this object."),
             SysError::CannotDelete => f.write_str("There was a problem deleting this object."),
             SysError::CannotUpdate => f.write_str("There was a problem updating this object."),
+            SysError::InvalidCredentials => f.write_str("Invalid email or password."),
+            SysError::NotLoggedIn => f.write_str("You have to be logged in to do this."),
+            SysError::PermissionDenied => f.write_str("You are not allowed to do this."),
+            SysError::Unavailable => f.write_str("The item is not available during that period."),
+            SysError::Booked => f.write_str("The item is already booked during that period."),
+            SysError::InvalidInput => f.write_str("Invalid input."),
+            SysError::Banned => f.write_str("This account has been banned."),
+            SysError::Restricted(status) => {
+                write!(f, "The member cannot lend or borrow items ({}).", status)
+            }
+            SysError::CannotExport => f.write_str("There was a problem writing the export file."),
+            SysError::NothingToUndo => f.write_str("There is nothing to undo."),
+            SysError::NothingToRedo => f.write_str("There is nothing to redo."),
+            SysError::NoTransaction => f.write_str("There is no transaction in progress."),
+            SysError::Quantity(min) => write!(f, "The quantity has to be at least {}.", min),
+            SysError::FutureContracts(count) => {
+                write!(f, "There are {} future contracts.", count)
+            }
+            SysError::ActiveContracts(count) => {
+                write!(f, "There are {} active contracts.", count)
+            }
+            SysError::OwnsItems(count) => write!(f, "The member owns {} items.", count),
+            SysError::Validation(err) => write!(f, "{}", err),
+            SysError::InvalidFields(errors) => f.write_str(
+                errors
+                    .iter()
+                    .map(|err| err.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+                    .as_str(),
+            ),
+        }
+    }
+}
+
+impl From<MemValError> for SysError {
+    fn from(err: MemValError) -> Self {
+        SysError::Validation(err)
+    }
+}
+
+impl From<Check> for SysError {
+    fn from(check: Check) -> Self {
+        SysError::InvalidFields(check.get_errors().to_vec())
+    }
+}
+
+/// An entity errors can concern.
+pub trait Subject {
+    /// The kind of the id of the entity.
+    type Kind: Kind;
+    /// Returns the kind of the entity.
+    fn get_entity(&self) -> Entity;
+    /// Returns the typed id of the entity.
+    fn get_typed_id(&self) -> &Id<Self::Kind>;
+    /// Returns the id of the entity.
+    fn get_id(&self) -> String {
+        self.get_typed_id().to_string()
+    }
+    /// Returns the name the entity is shown with, entities without a name are shown by their id.
+    fn get_label(&self) -> String;
+}
+
+/// An error of the lending system together with the entity it concerns.
+///
+/// Errors of the system and of validation become the cause, e.g.
+/// `Item 'Hammer' has 2 future contracts.`
+#[derive(Debug, Error, Clone, PartialEq, Eq, Getters)]
+pub struct LendingError {
+    /// The kind of the entity.
+    #[getter(rename = "get_entity")]
+    entity: Entity,
+    /// The id of the entity.
+    #[getter(rename = "get_id")]
+    id: String,
+    /// The name of the entity.
+    #[getter(rename = "get_label")]
+    label: String,
+    /// What went wrong.
+    #[getter(rename = "get_cause")]
+    #[source]
+    cause: SysError,
+}
+
+impl LendingError {
+    /// Creates the error of an entity.
+    pub fn new<S: Subject, E: Into<SysError>>(subject: &S, cause: E) -> Self {
+        Self {
+            entity: subject.get_entity(),
+            id: subject.get_id(),
+            label: subject.get_label(),
+            cause: cause.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for LendingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.label.is_empty() {
+            true => write!(f, "{} {}", self.entity, self.id)?,
+            false => write!(f, "{} '{}'", self.entity, self.label)?,
+        }
+        match &self.cause {
+            SysError::ActiveContracts(1) if self.entity == Entity::Member => {
+                f.write_str(" borrows in 1 active contract.")
+            }
+            SysError::ActiveContracts(count) if self.entity == Entity::Member => {
+                write!(f, " borrows in {} active contracts.", count)
+            }
+            SysError::OwnsItems(1) => f.write_str(" still owns 1 item."),
+            SysError::OwnsItems(count) => write!(f, " still owns {} items.", count),
+            SysError::FutureContracts(1) => f.write_str(" has 1 future contract."),
+            SysError::FutureContracts(count) => write!(f, " has {} future contracts.", count),
+            SysError::ActiveContracts(1) => f.write_str(" is lent out in 1 active contract."),
+            SysError::ActiveContracts(count) => {
+                write!(f, " is lent out in {} active contracts.", count)
+            }
+            SysError::DoesntExist => f.write_str(" does not exist."),
+            SysError::AlreadyExists => f.write_str(" already exists."),
+            SysError::Validation(MemValError::NotUnique(field)) => {
+                write!(f, " has the same {} as another member.", field)
+            }
+            SysError::Unavailable => f.write_str(" is blocked by its owner during that period."),
+            SysError::Booked => f.write_str(" is already booked during that period."),
+            SysError::Validation(_) | SysError::InvalidFields(_) => {
+                write!(f, " is invalid: {}", self.cause)
+            }
+            cause => write!(f, ": {}", cause),
         }
     }
 }
+
+/// Adds the entity an error concerns to a result.
+pub trait Context<T> {
+    /// Turns the error into a `LendingError` of the entity.
+    fn context<S: Subject>(self, subject: &S) -> Result<T, LendingError>;
+}
+
+impl<T, E: Into<SysError>> Context<T> for Result<T, E> {
+    fn context<S: Subject>(self, subject: &S) -> Result<T, LendingError> {
+        self.map_err(|err| LendingError::new(subject, err))
+    }
+}
@@ -3,7 +3,13 @@
 #![crate_type = "proc-macro"]
 
 use controllers::app::MainApp;
-use models::domain::system::System;
+use models::{
+    domain::{
+        contact::ContactConfig, notification::NotificationService, system::System, time::Listenable,
+    },
+    id::{IdGenerator, MAX_ID_LEN, MIN_ID_LEN},
+};
+use std::path::PathBuf;
 use types::Demo;
 use views::main_view::CliMainView;
 
@@ -36,9 +42,57 @@ pub mod errors;
 /// Contains all ui/ux related functionality.
 pub mod views;
 
+/// Reads the contact rules from the `--contact-rules` flag, e.g. `--contact-rules e164:se`.
+/// Members use local phone numbers if the flag is missing.
+fn contact_config() -> Result<ContactConfig, String> {
+    let args = std::env::args().collect::<Vec<String>>();
+    match args.iter().position(|arg| arg == "--contact-rules") {
+        Some(idx) => {
+            let value = args.get(idx + 1).map(String::as_str).unwrap_or("");
+            value.parse().map_err(|_| {
+                format!(
+                    "Unknown contact rules '{}', expected 'local' or 'e164:<country>'.",
+                    value
+                )
+            })
+        }
+        None => Ok(ContactConfig::default()),
+    }
+}
+
+/// Reads the length of the ids from the `--id-len` flag, e.g. `--id-len 12`.
+/// Ids are `MIN_ID_LEN` characters long if the flag is missing.
+fn id_len() -> Result<usize, String> {
+    let args = std::env::args().collect::<Vec<String>>();
+    match args.iter().position(|arg| arg == "--id-len") {
+        Some(idx) => {
+            let value = args.get(idx + 1).map(String::as_str).unwrap_or("");
+            match value.parse::<usize>() {
+                Ok(len) if (MIN_ID_LEN..=MAX_ID_LEN).contains(&len) => Ok(len),
+                _ => Err(format!(
+                    "Invalid id length '{}', expected a number from {} to {}.",
+                    value, MIN_ID_LEN, MAX_ID_LEN
+                )),
+            }
+        }
+        None => Ok(MIN_ID_LEN),
+    }
+}
+
 /// Main method
 fn main() {
-    let mut system = System::new();
+    let (contact, len) = match contact_config().and_then(|c| Ok((c, id_len()?))) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    let mut system = System::new()
+        .contact(contact)
+        .ids(IdGenerator::new().len(len))
+        .build();
+    system.add_listener(NotificationService::with_outbox(PathBuf::from("outbox")));
     system.init_demo();
     let main_view = CliMainView::new();
     let mut app = MainApp::new(system, main_view);
@@ -0,0 +1,232 @@
+use super::member::Member;
+use crate::{errors::SysError, models::id::MemberId};
+use chrono::{DateTime, Local};
+use derive_getters::Getters;
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// Fields that are not compared, contracts are audited as entities of their own.
+const IGNORED: [&str; 1] = ["history"];
+
+/// The kinds of entities that are audited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entity {
+    /// A member.
+    Member,
+    /// An item.
+    Item,
+    /// A contract.
+    Contract,
+}
+
+impl Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entity::Member => f.write_str("Member"),
+            Entity::Item => f.write_str("Item"),
+            Entity::Contract => f.write_str("Contract"),
+        }
+    }
+}
+
+impl FromStr for Entity {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "member" => Ok(Entity::Member),
+            "item" => Ok(Entity::Item),
+            "contract" => Ok(Entity::Contract),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+/// The kinds of mutations that are audited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// The entity has been created.
+    Create,
+    /// The entity has been changed.
+    Update,
+    /// The entity has been deleted.
+    Delete,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Create => f.write_str("Create"),
+            Operation::Update => f.write_str("Update"),
+            Operation::Delete => f.write_str("Delete"),
+        }
+    }
+}
+
+/// Entities with fields that are hidden from the audit log.
+pub trait Secrets {
+    /// Gets every secret by the name it is audited as and its current value.
+    /// Only whether a secret changed is recorded, never its value.
+    fn get_secrets(&self) -> Vec<(&'static str, &str)> {
+        Vec::new()
+    }
+}
+
+/// The value of a single field before and after a mutation.
+///
+/// Changes of secrets have neither value.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct FieldChange {
+    #[getter(rename = "get_field")]
+    field: String,
+    #[getter(rename = "get_before")]
+    before: Option<String>,
+    #[getter(rename = "get_after")]
+    after: Option<String>,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.before.is_none() && self.after.is_none() {
+            return write!(f, "{} changed", self.field);
+        }
+        let show = |value: &Option<String>| match value {
+            Some(v) => v.clone(),
+            None => "-".to_owned(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            show(&self.before),
+            show(&self.after)
+        )
+    }
+}
+
+/// Computes the changed fields between two maps created by `ToMap::to_map`.
+///
+/// The changes are sorted by field name. Secret fields are hidden from `ToMap` and never
+/// show up here, see `Secrets` for how their changes are audited.
+pub fn diff(
+    before: Option<&HashMap<String, String>>,
+    after: Option<&HashMap<String, String>>,
+) -> Vec<FieldChange> {
+    let empty = HashMap::new();
+    let (before, after) = (before.unwrap_or(&empty), after.unwrap_or(&empty));
+    let mut fields = before.keys().chain(after.keys()).collect::<Vec<&String>>();
+    fields.sort();
+    fields.dedup();
+    fields
+        .into_iter()
+        .filter(|field| !IGNORED.contains(&field.as_str()))
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| FieldChange {
+            field: field.clone(),
+            before: before.get(field).cloned(),
+            after: after.get(field).cloned(),
+        })
+        .collect()
+}
+
+/// A single audited mutation.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct AuditEntry {
+    #[getter(rename = "get_seq")]
+    seq: usize,
+    #[getter(rename = "get_actor")]
+    actor: Option<MemberId>,
+    #[getter(rename = "get_actor_name")]
+    actor_name: String,
+    #[getter(rename = "get_day")]
+    day: usize,
+    #[getter(rename = "get_time")]
+    time: DateTime<Local>,
+    #[getter(rename = "get_entity")]
+    entity: Entity,
+    #[getter(rename = "get_entity_id")]
+    entity_id: String,
+    #[getter(rename = "get_entity_name")]
+    entity_name: String,
+    #[getter(rename = "get_operation")]
+    operation: Operation,
+    #[getter(rename = "get_changes")]
+    changes: Vec<FieldChange>,
+}
+
+/// Everything that changed in the system, the oldest entry first.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Creates a new empty audit log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a mutation of the target, given as its kind, id and name, together with
+    /// the names of the secrets that changed.
+    /// Updates are recorded even if none of the fields that can be shown changed.
+    pub fn record(
+        &mut self,
+        actor: Option<&Member>,
+        day: usize,
+        target: (Entity, String, &str),
+        before: Option<&HashMap<String, String>>,
+        after: Option<&HashMap<String, String>>,
+        secrets: &[&str],
+    ) {
+        let (entity, entity_id, entity_name) = target;
+        let operation = match (before, after) {
+            (None, _) => Operation::Create,
+            (_, None) => Operation::Delete,
+            _ => Operation::Update,
+        };
+        let mut changes = diff(before, after);
+        changes.extend(secrets.iter().map(|field| FieldChange {
+            field: field.to_string(),
+            before: None,
+            after: None,
+        }));
+        self.entries.push(AuditEntry {
+            seq: self.entries.len() + 1,
+            actor: actor.map(|a| a.get_uuid().clone()),
+            actor_name: match actor {
+                Some(a) => a.get_name().clone(),
+                None => "System".to_owned(),
+            },
+            day,
+            time: Local::now(),
+            entity,
+            entity_id,
+            entity_name: entity_name.to_owned(),
+            operation,
+            changes,
+        });
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes the entries recorded after the first `len` entries.
+    pub fn truncate(&mut self, len: usize) {
+        self.entries.truncate(len);
+    }
+
+    /// Returns the entries concerning the entity kind and done by the actor, if given.
+    pub fn filter(&self, entity: Option<Entity>, actor: Option<&MemberId>) -> Vec<&AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|e| entity.is_none_or(|kind| e.entity == kind))
+            .filter(|e| actor.is_none_or(|uuid| e.actor.as_ref() == Some(uuid)))
+            .collect()
+    }
+}
@@ -0,0 +1,107 @@
+use super::{contract::Contract, member::Member};
+use crate::errors::SysError;
+use pbkdf2::pbkdf2_hmac;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::Sha256;
+use std::str::FromStr;
+
+/// How often the password is hashed, which makes guessing passwords from a stolen hash slow.
+const HASH_ROUNDS: u32 = 600_000;
+/// The length of a password hash in bytes.
+const HASH_LEN: usize = 32;
+
+/// The role a member has in the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Role {
+    /// A regular member, that can only manage his own data.
+    #[default]
+    Member,
+    /// An administrator, that can manage everything.
+    Admin,
+}
+
+impl FromStr for Role {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "member" => Ok(Role::Member),
+            "admin" => Ok(Role::Admin),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Member => f.write_str("Member"),
+            Role::Admin => f.write_str("Admin"),
+        }
+    }
+}
+
+/// Any action that requires the logged in member to have certain permissions.
+#[derive(Debug, Clone, Copy)]
+pub enum Action<'a> {
+    /// Creating a new member.
+    CreateMember,
+    /// Editing the profile of a member.
+    EditMember(&'a Member),
+    /// Deleting a member.
+    DeleteMember(&'a Member),
+    /// Creating, editing or deleting items of a member.
+    ManageItems(&'a Member),
+    /// Creating a contract for a lendee.
+    CreateContract(&'a Member),
+    /// Editing an existing contract.
+    EditContract(&'a Contract),
+    /// Suspending, banning or reinstating a member.
+    ModerateMember(&'a Member),
+    /// Advancing the time of the system.
+    AdvanceTime,
+    /// Browsing the audit log.
+    ViewAuditLog,
+    /// Checking the state of the system for violations.
+    RunDiagnostics,
+}
+
+impl Action<'_> {
+    /// Checks if the user is allowed to execute this action.
+    ///
+    /// Admins are allowed to do anything, members only what concerns themselves.
+    pub fn is_permitted_for(&self, user: &Member) -> bool {
+        if user.is_admin() {
+            return true;
+        }
+        let is_user = |member: &Member| member.get_uuid() == user.get_uuid();
+        match *self {
+            Action::CreateMember
+            | Action::ModerateMember(_)
+            | Action::AdvanceTime
+            | Action::ViewAuditLog
+            | Action::RunDiagnostics => false,
+            Action::EditMember(member) | Action::DeleteMember(member) => is_user(member),
+            Action::ManageItems(owner) => is_user(owner),
+            Action::CreateContract(lendee) => is_user(lendee),
+            Action::EditContract(contract) => is_user(contract.get_lendee()),
+        }
+    }
+}
+
+/// Generates a new random salt.
+pub fn generate_salt() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect()
+}
+
+/// Derives a key from the password and the salt with PBKDF2-HMAC-SHA256 and returns it
+/// hex encoded.
+pub fn hash_password(salt: &str, password: &str) -> String {
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), HASH_ROUNDS, &mut hash);
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
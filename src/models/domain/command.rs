@@ -0,0 +1,201 @@
+use super::{
+    contract::Contract,
+    item::Item,
+    member::Member,
+    simulation::{simulate, Advance, Simulation},
+    system::LendingSystem,
+};
+use crate::types::SysResult;
+
+/// A mutation of the lending system that can be undone and redone.
+///
+/// Commands are executed through `LendingSystem::execute`, which records every change
+/// they make. A command that fails leaves the system unchanged.
+pub trait Command {
+    /// Describes what the command did, it is asked after the command has been executed.
+    fn describe(&self) -> String;
+    /// Executes the command.
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()>;
+}
+
+/// Adds a member.
+#[derive(Debug, Clone)]
+pub struct CreateMember(pub Member);
+
+impl Command for CreateMember {
+    fn describe(&self) -> String {
+        format!("Create member {}", self.0.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.add_member(self.0.clone())
+    }
+}
+
+/// Removes a member.
+#[derive(Debug, Clone)]
+pub struct DeleteMember(pub Member);
+
+impl Command for DeleteMember {
+    fn describe(&self) -> String {
+        format!("Delete member {}", self.0.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.remove_member(&self.0)
+    }
+}
+
+/// Replaces the information of a member.
+#[derive(Debug, Clone)]
+pub struct EditMember {
+    /// The member as it is now.
+    pub old: Member,
+    /// The new information.
+    pub new: Member,
+}
+
+impl Command for EditMember {
+    fn describe(&self) -> String {
+        format!("Edit member {}", self.old.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.update_member(&self.old, &self.new)
+    }
+}
+
+/// Adds an item.
+#[derive(Debug, Clone)]
+pub struct CreateItem(pub Item);
+
+impl Command for CreateItem {
+    fn describe(&self) -> String {
+        format!("Create item {}", self.0.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.add_item(self.0.clone())
+    }
+}
+
+/// Removes an item.
+#[derive(Debug, Clone)]
+pub struct DeleteItem(pub Item);
+
+impl Command for DeleteItem {
+    fn describe(&self) -> String {
+        format!("Delete item {}", self.0.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.remove_item(&self.0)
+    }
+}
+
+/// Replaces the information of an item.
+#[derive(Debug, Clone)]
+pub struct EditItem(pub Item);
+
+impl Command for EditItem {
+    fn describe(&self) -> String {
+        format!("Edit item {}", self.0.get_name())
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.update_item(&self.0)
+    }
+}
+
+/// Books an item, a specific unit or any free unit if no unit is given.
+#[derive(Debug, Clone)]
+pub struct BookItem {
+    /// The item to book.
+    pub item: Item,
+    /// The booking.
+    pub contract: Contract,
+    /// The unit to book.
+    pub unit: Option<usize>,
+}
+
+impl Command for BookItem {
+    fn describe(&self) -> String {
+        format!(
+            "Book {} for {} from day {} to {}",
+            self.item.get_name(),
+            self.contract.get_lendee().get_name(),
+            self.contract.get_start_date(),
+            self.contract.get_end_date()
+        )
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        sys.add_contract(&self.item, self.contract.clone(), self.unit)
+    }
+}
+
+/// Replaces the information of a booking, which then ends after its length.
+#[derive(Debug, Clone)]
+pub struct EditContract {
+    /// The item the booking belongs to.
+    pub item: Item,
+    /// The new information of the booking.
+    pub contract: Contract,
+}
+
+impl Command for EditContract {
+    fn describe(&self) -> String {
+        format!(
+            "Edit booking of {} for {}",
+            self.item.get_name(),
+            self.contract.get_lendee().get_name()
+        )
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        let mut item = sys.get_item(&self.item)?;
+        let end_date = self.contract.get_start_date() + self.contract.get_contract_len();
+        item.update_contract(&self.contract.clone().end_date(end_date).build())?;
+        sys.update_item(&item)
+    }
+}
+
+/// Advances the time, failed settlements are part of the outcome instead of an error.
+#[derive(Debug, Clone)]
+pub struct AdvanceTime {
+    advance: Advance,
+    from: usize,
+    simulation: Option<Simulation>,
+}
+
+impl AdvanceTime {
+    /// Creates a command advancing the time as given.
+    pub fn new(advance: Advance) -> Self {
+        Self {
+            advance,
+            from: 0,
+            simulation: None,
+        }
+    }
+
+    /// Returns the simulated days, once the command has been executed.
+    pub fn get_simulation(&self) -> Option<&Simulation> {
+        self.simulation.as_ref()
+    }
+}
+
+impl Command for AdvanceTime {
+    fn describe(&self) -> String {
+        let days = match &self.simulation {
+            Some(simulation) => simulation.get_days().len(),
+            None => 0,
+        };
+        format!("Advance from day {} to {}", self.from, self.from + days)
+    }
+
+    fn execute<M: LendingSystem>(&mut self, sys: &mut M) -> SysResult<()> {
+        self.from = sys.now();
+        self.simulation = Some(simulate(sys, &self.advance));
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use super::{
+    contract::Contract,
+    item::{Category, Item},
+    member::Member,
+    notification::LOW_BALANCE_THRESHOLD,
+    simulation::{simulate, Advance},
+    system::LendingSystem,
+};
+use derive_getters::Getters;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use shared::Builder;
+
+/// The categories simulated members list their items in.
+const CATEGORIES: [Category; 6] = [
+    Category::Tool,
+    Category::Vehicle,
+    Category::Game,
+    Category::Toy,
+    Category::Sport,
+    Category::Other,
+];
+
+/// Tunable parameters of a simulated lending community.
+#[derive(Debug, Clone, PartialEq, Builder, Getters)]
+pub struct CommunityConfig {
+    /// The seed all random decisions and ids are derived from.
+    #[getter(rename = "get_seed")]
+    seed: u64,
+    /// Number of simulated members.
+    #[getter(rename = "get_members")]
+    members: usize,
+    /// Number of simulated days.
+    #[getter(rename = "get_days")]
+    days: usize,
+    /// Probability that a member lists a new item on a day.
+    #[getter(rename = "get_list_probability")]
+    list_probability: f64,
+    /// Probability that a member tries to book an item on a day.
+    #[getter(rename = "get_book_probability")]
+    book_probability: f64,
+    /// Longest booking in days.
+    #[getter(rename = "get_max_booking_len")]
+    max_booking_len: usize,
+    /// Most days a booking starts in the future.
+    #[getter(rename = "get_max_lead_time")]
+    max_lead_time: usize,
+    /// Highest price per day of a listed item.
+    #[getter(rename = "get_max_cost_per_day")]
+    max_cost_per_day: f64,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            members: 20,
+            days: 30,
+            list_probability: 0.05,
+            book_probability: 0.2,
+            max_booking_len: 5,
+            max_lead_time: 7,
+            max_cost_per_day: 30.0,
+        }
+    }
+}
+
+/// Aggregate statistics of a simulated lending community.
+#[derive(Debug, Clone, PartialEq, Default, Getters)]
+pub struct CommunityStats {
+    #[getter(rename = "get_days")]
+    days: usize,
+    #[getter(rename = "get_members")]
+    members: usize,
+    #[getter(rename = "get_items")]
+    items: usize,
+    #[getter(rename = "get_bookings")]
+    bookings: usize,
+    #[getter(rename = "get_rejected_bookings")]
+    rejected_bookings: usize,
+    #[getter(rename = "get_settlement_failures")]
+    settlement_failures: usize,
+    #[getter(rename = "get_credits_transferred")]
+    credits_transferred: f64,
+    #[getter(rename = "get_total_credits")]
+    total_credits: f64,
+    #[getter(rename = "get_min_credits")]
+    min_credits: f64,
+    #[getter(rename = "get_max_credits")]
+    max_credits: f64,
+    #[getter(rename = "get_low_balance_members")]
+    low_balance_members: usize,
+}
+
+impl CommunityStats {
+    /// Returns the average credits per member.
+    pub fn get_average_credits(&self) -> f64 {
+        match self.members {
+            0 => 0.0,
+            n => self.total_credits / n as f64,
+        }
+    }
+
+    /// Returns the statistics as pairs of name and value.
+    pub fn to_pairs(&self) -> Vec<(&str, String)> {
+        vec![
+            ("Days", self.days.to_string()),
+            ("Members", self.members.to_string()),
+            ("Items", self.items.to_string()),
+            ("Bookings", self.bookings.to_string()),
+            ("Rejected Bookings", self.rejected_bookings.to_string()),
+            (
+                "Days With Settlement Failures",
+                self.settlement_failures.to_string(),
+            ),
+            ("Credits Transferred", self.credits_transferred.to_string()),
+            ("Total Credits", self.total_credits.to_string()),
+            (
+                "Average Credits",
+                format!("{:.2}", self.get_average_credits()),
+            ),
+            ("Min Credits", self.min_credits.to_string()),
+            ("Max Credits", self.max_credits.to_string()),
+            (
+                "Members With Low Balance",
+                self.low_balance_members.to_string(),
+            ),
+        ]
+    }
+}
+
+/// Generates synthetic activity through the lending system.
+///
+/// Every day each simulated member may list an item and may book an item of another
+/// member, afterwards the time advances by one day. All decisions and the ids the system
+/// generates are derived from the seed, so the same config on an empty system always has the same outcome.
+pub fn simulate_community<M>(sys: &mut M, config: &CommunityConfig) -> CommunityStats
+where
+    M: LendingSystem,
+{
+    sys.seed_ids(config.seed);
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut stats = CommunityStats::default();
+
+    let mut members: Vec<Member> = Vec::new();
+    for i in 0..config.members {
+        let member = Member::new(
+            format!("Member {}", i),
+            format!("member{}@community.sim", i),
+            format!("07{:08}", i),
+            sys.now(),
+        );
+        if let Ok(m) = member {
+            let m = m.uuid(sys.generate_id()).build();
+            if sys.add_member(m.clone()).is_ok() {
+                members.push(m);
+            }
+        }
+    }
+
+    let mut items: Vec<Item> = Vec::new();
+    for _ in 0..config.days {
+        for member in members.iter() {
+            if rng.gen_bool(config.list_probability) {
+                let owner = sys.get_member(member).unwrap_or_else(|_| member.clone());
+                let item = Item::new(
+                    format!("Item {}", items.len()),
+                    format!("Listed by {}", member.get_name()),
+                    CATEGORIES[rng.gen_range(0..CATEGORIES.len())].clone(),
+                    owner,
+                    rng.gen_range(1..=config.max_cost_per_day.max(1.0) as usize) as f64,
+                    sys.now(),
+                )
+                .uuid(sys.generate_id())
+                .build();
+                if sys.add_item(item.clone()).is_ok() {
+                    items.push(item);
+                }
+            }
+
+            if rng.gen_bool(config.book_probability) {
+                let offers = items
+                    .iter()
+                    .filter(|i| i.get_owner().get_uuid() != member.get_uuid())
+                    .collect::<Vec<&Item>>();
+                if offers.is_empty() {
+                    continue;
+                }
+                let item = offers[rng.gen_range(0..offers.len())];
+                let len = rng.gen_range(1..=config.max_booking_len.max(1));
+                let start = sys.now() + rng.gen_range(1..=config.max_lead_time.max(1));
+                let lendee = sys.get_member(member).unwrap_or_else(|_| member.clone());
+                let contract = Contract::new(
+                    item.get_owner().clone(),
+                    lendee,
+                    start,
+                    len,
+                    *item.get_cost_per_day() * len as f64,
+                )
+                .uuid(sys.generate_id())
+                .build();
+                match sys.add_contract(item, contract, None) {
+                    Ok(_) => stats.bookings += 1,
+                    Err(_) => stats.rejected_bookings += 1,
+                }
+            }
+        }
+
+        let day = simulate(sys, &Advance::Days(1));
+        for summary in day.get_days() {
+            stats.credits_transferred += summary.get_credits_transferred();
+            if !summary.get_settled() {
+                stats.settlement_failures += 1;
+            }
+        }
+    }
+
+    let credits = members
+        .iter()
+        .filter_map(|m| sys.get_member(m).ok())
+        .map(|m| *m.get_credits())
+        .collect::<Vec<f64>>();
+    stats.days = config.days;
+    stats.members = credits.len();
+    stats.items = items.len();
+    stats.total_credits = credits.iter().sum();
+    stats.min_credits = credits.iter().copied().fold(f64::INFINITY, f64::min);
+    stats.max_credits = credits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if credits.is_empty() {
+        stats.min_credits = 0.0;
+        stats.max_credits = 0.0;
+    }
+    stats.low_balance_members = credits
+        .iter()
+        .filter(|c| **c < LOW_BALANCE_THRESHOLD)
+        .count();
+    stats
+}
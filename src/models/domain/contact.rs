@@ -0,0 +1,216 @@
+use crate::{
+    errors::{MemValError, SysError},
+    types::MemValResult,
+};
+use regex::Regex;
+use std::{ops::RangeInclusive, str::FromStr, sync::OnceLock};
+
+/// The most digits an E.164 phone number can have, including the country calling code.
+const MAX_E164_LEN: usize = 15;
+/// The fewest digits an international phone number of an unknown country can have.
+const MIN_E164_LEN: usize = 8;
+
+/// Emails as they are stored, the local part may contain dots, dashes, plus signs and
+/// underscores, but neither start nor end with a dot.
+const EMAIL_PATTERN: &str =
+    r"^[a-z0-9_+\-]([a-z0-9_+.\-]*[a-z0-9_+\-])?@[a-z0-9]+([\-.][a-z0-9]+)*\.[a-z]{2,6}$";
+
+/// The compiled `EMAIL_PATTERN`, it is compiled on first use.
+fn email_regex() -> &'static Regex {
+    static EMAIL_REGEX: OnceLock<Regex> = OnceLock::new();
+    EMAIL_REGEX.get_or_init(|| Regex::new(EMAIL_PATTERN).expect("The pattern is valid."))
+}
+
+/// Rules for the email and phone number of members.
+///
+/// The rules return the value in the form it is stored in, or the reason it is invalid.
+pub trait ContactRules {
+    /// Validates and normalizes an email, the whole email is stored lowercase.
+    fn normalize_email(&self, email: &str) -> MemValResult<String> {
+        let email = email.trim().to_lowercase();
+        match email_regex().is_match(&email) {
+            true => Ok(email),
+            false => Err(MemValError::EmailPattern),
+        }
+    }
+
+    /// Validates and normalizes a phone number.
+    fn normalize_phone_nr(&self, phone_nr: &str) -> MemValResult<String>;
+}
+
+/// Local phone numbers of 8 to 12 digits, which are stored without whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocalRules;
+
+impl ContactRules for LocalRules {
+    fn normalize_phone_nr(&self, phone_nr: &str) -> MemValResult<String> {
+        if !phone_nr
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_whitespace())
+        {
+            return Err(MemValError::PhoneNumberContainsNonNumeric);
+        }
+        let digits = phone_nr
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        match (8..=12).contains(&digits.len()) {
+            true => Ok(digits),
+            false => Err(MemValError::PhoneNumber),
+        }
+    }
+}
+
+/// A country whose national phone numbers can be turned into E.164 numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Country {
+    /// Sweden, +46.
+    Sweden,
+    /// Germany, +49.
+    Germany,
+    /// France, +33.
+    France,
+    /// The United Kingdom, +44.
+    UnitedKingdom,
+    /// The United States, +1.
+    UnitedStates,
+}
+
+impl Country {
+    /// Returns the country calling code.
+    pub fn calling_code(&self) -> &'static str {
+        match self {
+            Country::Sweden => "46",
+            Country::Germany => "49",
+            Country::France => "33",
+            Country::UnitedKingdom => "44",
+            Country::UnitedStates => "1",
+        }
+    }
+
+    /// Returns the digit national numbers start with when dialed inside the country.
+    pub fn trunk_prefix(&self) -> char {
+        match self {
+            Country::UnitedStates => '1',
+            _ => '0',
+        }
+    }
+
+    /// Returns how many digits follow the country calling code.
+    pub fn national_len(&self) -> RangeInclusive<usize> {
+        match self {
+            Country::Sweden => 7..=9,
+            Country::Germany => 6..=11,
+            Country::France => 9..=9,
+            Country::UnitedKingdom => 9..=10,
+            Country::UnitedStates => 10..=10,
+        }
+    }
+}
+
+impl FromStr for Country {
+    type Err = SysError;
+
+    /// Reads the two letter country code or the name of the country, e.g. `se` or `sweden`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', '_'], " ").as_str() {
+            "se" | "sweden" => Ok(Country::Sweden),
+            "de" | "germany" => Ok(Country::Germany),
+            "fr" | "france" => Ok(Country::France),
+            "gb" | "uk" | "united kingdom" => Ok(Country::UnitedKingdom),
+            "us" | "united states" => Ok(Country::UnitedStates),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+/// Phone numbers in the international E.164 format, like `+46701234567`.
+///
+/// Numbers without a country calling code are read as national numbers of the country.
+/// Spaces, dashes, dots, parentheses and a `(0)` after the calling code are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct E164Rules {
+    country: Country,
+}
+
+impl E164Rules {
+    /// Creates the rules for the country.
+    pub fn new(country: Country) -> Self {
+        Self { country }
+    }
+}
+
+impl ContactRules for E164Rules {
+    fn normalize_phone_nr(&self, phone_nr: &str) -> MemValResult<String> {
+        let stripped = phone_nr
+            .replace("(0)", "")
+            .chars()
+            .filter(|c| !(c.is_whitespace() || matches!(c, '-' | '.' | '(' | ')')))
+            .collect::<String>();
+        let international = stripped
+            .strip_prefix('+')
+            .or_else(|| stripped.strip_prefix("00"));
+        if !international
+            .unwrap_or(&stripped)
+            .chars()
+            .all(|c| c.is_ascii_digit())
+        {
+            return Err(MemValError::PhoneNumberContainsNonNumeric);
+        }
+
+        let code = self.country.calling_code();
+        let digits = match international {
+            Some(digits) => digits.to_owned(),
+            None => {
+                let national = stripped
+                    .strip_prefix(self.country.trunk_prefix())
+                    .unwrap_or(&stripped);
+                format!("{}{}", code, national)
+            }
+        };
+        if digits.starts_with('0') {
+            return Err(MemValError::PhoneNumberPattern);
+        }
+        let valid = match digits.strip_prefix(code) {
+            Some(national) => self.country.national_len().contains(&national.len()),
+            None => (MIN_E164_LEN..=MAX_E164_LEN).contains(&digits.len()),
+        };
+        match valid && digits.len() <= MAX_E164_LEN {
+            true => Ok(format!("+{}", digits)),
+            false => Err(MemValError::PhoneNumber),
+        }
+    }
+}
+
+/// Selects the rules the contact information of members has to follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContactConfig {
+    /// Local phone numbers of 8 to 12 digits.
+    #[default]
+    Local,
+    /// E.164 phone numbers, numbers without calling code belong to the country.
+    E164(Country),
+}
+
+impl FromStr for ContactConfig {
+    type Err = SysError;
+
+    /// Reads `local`, or `e164:` followed by the country, e.g. `e164:se`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        match lower.split_once(':') {
+            Some(("e164", country)) => Ok(ContactConfig::E164(country.parse()?)),
+            None if lower == "local" => Ok(ContactConfig::Local),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+impl ContactRules for ContactConfig {
+    fn normalize_phone_nr(&self, phone_nr: &str) -> MemValResult<String> {
+        match *self {
+            ContactConfig::Local => LocalRules.normalize_phone_nr(phone_nr),
+            ContactConfig::E164(country) => E164Rules::new(country).normalize_phone_nr(phone_nr),
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use super::audit::{Entity, Secrets};
 use super::member::Member;
-use crate::errors::SysError;
-use crate::models::uuid::Uuid;
+use crate::errors::{Subject, SysError};
+use crate::models::id::{ContractId, ContractKind};
 use crate::types::FromMap;
 use derive_getters::{Dissolve, Getters};
 use shared::{
@@ -10,7 +11,7 @@ use shared::{
 use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Status {
     Active,
     Finished,
@@ -83,7 +84,7 @@ pub struct Contract {
     #[eq]
     #[getter(rename = "get_uuid")]
     #[mutable_ignore]
-    uuid: Uuid,
+    uuid: ContractId,
 
     #[getter(rename = "get_contract_len")]
     contract_len: usize,
@@ -95,6 +96,10 @@ pub struct Contract {
     #[getter(rename = "get_status")]
     #[mutable_ignore]
     status: Status,
+
+    #[getter(rename = "get_unit")]
+    #[mutable_ignore]
+    unit: usize,
 }
 
 impl Contract {
@@ -108,13 +113,14 @@ impl Contract {
     ) -> Self {
         Self {
             owner,
-            uuid: Uuid::new(),
+            uuid: ContractId::new(),
             credits,
             end_date: start_date + contract_len as usize,
             status: Status::Future,
             start_date,
             lendee,
             contract_len,
+            unit: 0,
         }
     }
 
@@ -140,3 +146,22 @@ impl Contract {
         self.clone()
     }
 }
+
+/// Contracts have no name and are shown by their id.
+impl Secrets for Contract {}
+
+impl Subject for Contract {
+    type Kind = ContractKind;
+
+    fn get_entity(&self) -> Entity {
+        Entity::Contract
+    }
+
+    fn get_typed_id(&self) -> &ContractId {
+        &self.uuid
+    }
+
+    fn get_label(&self) -> String {
+        String::new()
+    }
+}
@@ -0,0 +1,129 @@
+use super::{
+    contract::{Contract, Status},
+    item::Item,
+};
+use crate::models::id::{ContractId, ItemId, MemberId};
+use derive_getters::Getters;
+use std::collections::{HashMap, HashSet};
+
+/// A contract together with the item it books.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct StoredContract {
+    #[getter(rename = "get_item")]
+    item: ItemId,
+    #[getter(rename = "get_contract")]
+    contract: Contract,
+}
+
+/// The contracts of all listed items by their uuid.
+///
+/// Each contract references the item it books, and every member references the
+/// contracts they own or lend, so none of the lookups has to go through the items.
+#[derive(Debug, Clone, Default)]
+pub struct ContractStore {
+    contracts: HashMap<ContractId, StoredContract>,
+    by_item: HashMap<ItemId, Vec<ContractId>>,
+    by_member: HashMap<MemberId, HashSet<ContractId>>,
+    active: HashSet<ContractId>,
+}
+
+impl ContractStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the contracts of an item with the ones in its history,
+    /// or drops them if the item has been removed.
+    pub fn sync(&mut self, item: &ItemId, current: Option<&Item>) {
+        for contract in self.by_item.remove(item).unwrap_or_default() {
+            self.remove(&contract);
+        }
+        if let Some(i) = current {
+            for contract in i.get_history().iter() {
+                self.insert(item, contract);
+            }
+        }
+    }
+
+    fn insert(&mut self, item: &ItemId, contract: &Contract) {
+        let uuid = contract.get_uuid().clone();
+        for member in [contract.get_owner(), contract.get_lendee()] {
+            self.by_member
+                .entry(member.get_uuid().clone())
+                .or_default()
+                .insert(uuid.clone());
+        }
+        if *contract.get_status() == Status::Active {
+            self.active.insert(uuid.clone());
+        }
+        self.by_item
+            .entry(item.clone())
+            .or_default()
+            .push(uuid.clone());
+        let stored = StoredContract {
+            item: item.clone(),
+            contract: contract.clone(),
+        };
+        self.contracts.insert(uuid, stored);
+    }
+
+    fn remove(&mut self, uuid: &ContractId) {
+        if let Some(stored) = self.contracts.remove(uuid) {
+            let contract = stored.get_contract();
+            for member in [contract.get_owner(), contract.get_lendee()] {
+                if let Some(contracts) = self.by_member.get_mut(member.get_uuid()) {
+                    contracts.remove(uuid);
+                    if contracts.is_empty() {
+                        self.by_member.remove(member.get_uuid());
+                    }
+                }
+            }
+            self.active.remove(uuid);
+        }
+    }
+
+    /// Gets a contract and the item it books.
+    pub fn get(&self, contract: &ContractId) -> Option<&StoredContract> {
+        self.contracts.get(contract)
+    }
+
+    /// Gets the contracts a member owns or lends, ordered by their start.
+    pub fn for_member(&self, member: &MemberId) -> Vec<&Contract> {
+        match self.by_member.get(member) {
+            Some(contracts) => self.collect(contracts.iter()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Gets the contracts whose items are lent out right now, ordered by their start.
+    pub fn active(&self) -> Vec<&Contract> {
+        self.collect(self.active.iter())
+    }
+
+    fn collect<'a, I>(&'a self, uuids: I) -> Vec<&'a Contract>
+    where
+        I: Iterator<Item = &'a ContractId>,
+    {
+        let mut contracts = uuids
+            .filter_map(|uuid| self.contracts.get(uuid))
+            .map(|stored| stored.get_contract())
+            .collect::<Vec<&Contract>>();
+        contracts.sort_by(|a, b| {
+            a.get_start_date()
+                .cmp(b.get_start_date())
+                .then_with(|| a.get_uuid().get_value().cmp(b.get_uuid().get_value()))
+        });
+        contracts
+    }
+
+    /// Returns the number of stored contracts.
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    /// Checks if no contract is stored.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+}
@@ -0,0 +1,207 @@
+use super::{
+    contract::{Contract, Status},
+    period::Period,
+};
+use crate::{errors::SysError, models::vec_wrapper::VecWrapper};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// Index of half-open periods, each booking a unit under a key.
+///
+/// The periods are sorted by their first day. No period is longer than the longest
+/// one ever inserted, so only periods starting at most that many days before a
+/// queried period can overlap with it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalIndex {
+    periods: BTreeMap<(usize, usize), (usize, usize)>,
+    longest: usize,
+}
+
+impl IntervalIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the period booked by a unit under a key. Empty periods book nothing.
+    pub fn insert(&mut self, key: usize, period: Period, unit: usize) {
+        if period.is_empty() {
+            return;
+        }
+        self.longest = usize::max(self.longest, period.len());
+        self.periods
+            .insert((period.get_start(), key), (period.get_end(), unit));
+    }
+
+    /// Removes the period of a key. Returns whether it was indexed.
+    pub fn remove(&mut self, key: usize, period: &Period) -> bool {
+        self.periods.remove(&(period.get_start(), key)).is_some()
+    }
+
+    /// Returns the keys and units of all periods sharing at least one day with the period,
+    /// ordered by their first day.
+    pub fn overlapping(&self, period: &Period) -> Vec<(usize, usize)> {
+        if period.is_empty() {
+            return Vec::new();
+        }
+        let from = period.get_start().saturating_sub(self.longest);
+        self.periods
+            .range((from, 0)..(period.get_end(), 0))
+            .filter(|(_, (end, _))| *end > period.get_start())
+            .map(|((_, key), (_, unit))| (*key, *unit))
+            .collect::<Vec<(usize, usize)>>()
+    }
+
+    /// Returns the keys and units of all periods containing the day.
+    pub fn covering(&self, day: usize) -> Vec<(usize, usize)> {
+        self.overlapping(&Period::from_len(day, 1))
+    }
+
+    /// Returns the number of indexed periods.
+    pub fn len(&self) -> usize {
+        self.periods.len()
+    }
+
+    /// Checks if no period is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.periods.is_empty()
+    }
+}
+
+/// The contracts of an item in the order they were made, indexed by the days they book.
+///
+/// Canceled contracts stay in the history, but do not book their unit anymore.
+#[derive(Debug, Clone, Default)]
+pub struct ContractHistory {
+    contracts: VecWrapper<Contract>,
+    index: IntervalIndex,
+}
+
+impl ContractHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn period(contract: &Contract) -> Period {
+        Period::new(*contract.get_start_date(), *contract.get_end_date())
+    }
+
+    fn index(&mut self, idx: usize, contract: &Contract) {
+        if *contract.get_status() != Status::Canceled {
+            self.index
+                .insert(idx, Self::period(contract), *contract.get_unit());
+        }
+    }
+
+    /// Adds a contract to the end of the history.
+    pub fn push(&mut self, contract: Contract) {
+        self.index(self.contracts.len(), &contract);
+        self.contracts.push(contract);
+    }
+
+    /// Replaces the contract at the index.
+    pub fn set(&mut self, idx: usize, contract: &Contract) {
+        let old = Self::period(&self.contracts.values[idx]);
+        self.index.remove(idx, &old);
+        self.index(idx, contract);
+        self.contracts.set(idx, contract);
+    }
+
+    /// Gets the index of a contract.
+    pub fn index_of(&self, contract: &Contract) -> Option<usize> {
+        self.contracts.index_of(contract)
+    }
+
+    /// Gets the stored information of a contract.
+    pub fn get(&self, contract: &Contract) -> Option<&Contract> {
+        self.contracts.get(contract)
+    }
+
+    /// Returns an iterator over the contracts.
+    pub fn iter(&self) -> std::slice::Iter<'_, Contract> {
+        self.contracts.iter()
+    }
+
+    /// Returns the number of contracts.
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    /// Checks if the history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Returns the contracts as a vector.
+    pub fn to_vec(&self) -> Vec<Contract> {
+        self.contracts.to_vec()
+    }
+
+    /// Gets the contracts booking at least one day of the period, ordered by their start.
+    pub fn overlapping(&self, period: &Period) -> Vec<&Contract> {
+        self.index
+            .overlapping(period)
+            .into_iter()
+            .map(|(idx, _)| &self.contracts.values[idx])
+            .collect::<Vec<&Contract>>()
+    }
+
+    /// Gets the contracts booking a unit during at least one day of the period.
+    pub fn overlapping_unit(&self, period: &Period, unit: usize) -> Vec<&Contract> {
+        self.index
+            .overlapping(period)
+            .into_iter()
+            .filter(|(_, u)| *u == unit)
+            .map(|(idx, _)| &self.contracts.values[idx])
+            .collect::<Vec<&Contract>>()
+    }
+
+    /// Gets the contracts booking the day.
+    pub fn covering(&self, day: usize) -> Vec<&Contract> {
+        self.overlapping(&Period::from_len(day, 1))
+    }
+
+    /// Returns the number of units booked on the day.
+    pub fn booked_units(&self, day: usize) -> usize {
+        let mut units = self
+            .index
+            .covering(day)
+            .into_iter()
+            .map(|(_, unit)| unit)
+            .collect::<Vec<usize>>();
+        units.sort_unstable();
+        units.dedup();
+        units.len()
+    }
+}
+
+impl PartialEq for ContractHistory {
+    fn eq(&self, other: &Self) -> bool {
+        self.contracts == other.contracts
+    }
+}
+
+impl From<Vec<Contract>> for ContractHistory {
+    fn from(contracts: Vec<Contract>) -> Self {
+        let mut history = ContractHistory::new();
+        for contract in contracts {
+            history.push(contract);
+        }
+        history
+    }
+}
+
+impl Display for ContractHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.contracts.fmt(f)
+    }
+}
+
+impl FromStr for ContractHistory {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let contracts = VecWrapper::<Contract>::from_str(s)?;
+        Ok(ContractHistory::from(contracts.to_vec()))
+    }
+}
@@ -0,0 +1,194 @@
+use super::{
+    contract::{Contract, Status},
+    system::LendingSystem,
+};
+use crate::models::id::{ContractId, MemberId};
+use std::{collections::HashSet, fmt::Display};
+
+/// The largest difference between two amounts of credits that still counts as equal.
+const TOLERANCE: f64 = 1e-6;
+
+/// A rule the state of the system breaks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// Two contracts book the same unit of an item on the same day.
+    OverlappingContracts {
+        item: String,
+        unit: usize,
+        first: ContractId,
+        second: ContractId,
+    },
+    /// A member owes credits.
+    NegativeBalance { member: String, credits: f64 },
+    /// The owner of a contract is not the owner of its item.
+    WrongContractOwner { item: String, contract: ContractId },
+    /// The owner of an item is not a member of the system.
+    MissingOwner { item: String, owner: String },
+    /// The lendee of a contract that is running or has not started yet is not a member
+    /// of the system.
+    MissingLendee {
+        item: String,
+        contract: ContractId,
+        lendee: String,
+    },
+    /// The end date of a contract does not match its start date and length.
+    WrongEndDate { item: String, contract: ContractId },
+    /// The contract store does not hold the contract as it is in the history of its item.
+    StaleContract { item: String, contract: ContractId },
+    /// The credits of all members do not add up to the credits minted by the ledger.
+    CreditsNotConserved { minted: f64, total: f64 },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::OverlappingContracts {
+                item,
+                unit,
+                first,
+                second,
+            } => write!(
+                f,
+                "Contracts {} and {} of {} overlap on unit {}.",
+                first, second, item, unit
+            ),
+            Violation::NegativeBalance { member, credits } => {
+                write!(f, "{} has a negative balance of {}.", member, credits)
+            }
+            Violation::WrongContractOwner { item, contract } => write!(
+                f,
+                "Contract {} of {} belongs to another owner than the item.",
+                contract, item
+            ),
+            Violation::MissingOwner { item, owner } => {
+                write!(f, "{}, the owner of {}, is not a member.", owner, item)
+            }
+            Violation::MissingLendee {
+                item,
+                contract,
+                lendee,
+            } => write!(
+                f,
+                "{}, the lendee of contract {} of {}, is not a member.",
+                lendee, contract, item
+            ),
+            Violation::WrongEndDate { item, contract } => write!(
+                f,
+                "Contract {} of {} does not end after its length.",
+                contract, item
+            ),
+            Violation::StaleContract { item, contract } => write!(
+                f,
+                "Contract {} of {} is missing or outdated in the contract store.",
+                contract, item
+            ),
+            Violation::CreditsNotConserved { minted, total } => write!(
+                f,
+                "Members own {} credits, but {} credits have been minted.",
+                total, minted
+            ),
+        }
+    }
+}
+
+/// Verifies the whole state of the system and returns every violation.
+///
+/// Canceled contracts do not book their unit, a contract books the days from its start
+/// date up to, but excluding, its end date. Finished and canceled contracts stay in the
+/// history of their item after their lendee has left.
+pub fn check_invariants<M>(sys: &M) -> Vec<Violation>
+where
+    M: LendingSystem,
+{
+    let mut violations: Vec<Violation> = Vec::new();
+    let members = sys
+        .get_members()
+        .into_iter()
+        .map(|m| m.get_uuid())
+        .collect::<HashSet<&MemberId>>();
+
+    let mut items = sys.get_items();
+    items.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    for item in items {
+        let name = item.get_name();
+        let owner = item.get_owner();
+        if !members.contains(owner.get_uuid()) {
+            violations.push(Violation::MissingOwner {
+                item: name.clone(),
+                owner: owner.get_name().clone(),
+            });
+        }
+        let contracts = item.get_history().iter().collect::<Vec<&Contract>>();
+        for con in contracts.iter() {
+            let stored = sys.get_contract(con).map(|c| c.to_string());
+            let booked = sys.get_item_for_contract(con).map(|i| i.get_uuid());
+            if stored != Ok(con.to_string()) || booked != Some(item.get_uuid()) {
+                violations.push(Violation::StaleContract {
+                    item: name.clone(),
+                    contract: con.get_uuid().clone(),
+                });
+            }
+            if con.get_owner().get_uuid() != owner.get_uuid() {
+                violations.push(Violation::WrongContractOwner {
+                    item: name.clone(),
+                    contract: con.get_uuid().clone(),
+                });
+            }
+            let pending = matches!(con.get_status(), Status::Active | Status::Future);
+            if pending && !members.contains(con.get_lendee().get_uuid()) {
+                violations.push(Violation::MissingLendee {
+                    item: name.clone(),
+                    contract: con.get_uuid().clone(),
+                    lendee: con.get_lendee().get_name().clone(),
+                });
+            }
+            if *con.get_end_date() != con.get_start_date() + con.get_contract_len() {
+                violations.push(Violation::WrongEndDate {
+                    item: name.clone(),
+                    contract: con.get_uuid().clone(),
+                });
+            }
+        }
+        let booked = contracts
+            .into_iter()
+            .filter(|c| *c.get_status() != Status::Canceled)
+            .collect::<Vec<&Contract>>();
+        for (i, con) in booked.iter().enumerate() {
+            for other in booked.iter().skip(i + 1) {
+                if con.get_unit() == other.get_unit()
+                    && con.get_start_date() < other.get_end_date()
+                    && other.get_start_date() < con.get_end_date()
+                {
+                    violations.push(Violation::OverlappingContracts {
+                        item: name.clone(),
+                        unit: *con.get_unit(),
+                        first: con.get_uuid().clone(),
+                        second: other.get_uuid().clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut balances = sys
+        .get_members()
+        .into_iter()
+        .map(|m| (m.get_name().clone(), *m.get_credits()))
+        .collect::<Vec<(String, f64)>>();
+    balances.sort_by(|a, b| a.0.cmp(&b.0));
+    for (member, credits) in balances.iter() {
+        if *credits < 0.0 {
+            violations.push(Violation::NegativeBalance {
+                member: member.clone(),
+                credits: *credits,
+            });
+        }
+    }
+
+    let total = balances.iter().map(|(_, credits)| credits).sum::<f64>();
+    let minted = sys.get_ledger().total();
+    if (total - minted).abs() > TOLERANCE {
+        violations.push(Violation::CreditsNotConserved { minted, total });
+    }
+    violations
+}
@@ -1,7 +1,10 @@
+use super::audit::{Entity, Secrets};
 use super::contract::Status;
+use super::interval::ContractHistory;
+use super::period::Period;
 use super::{contract::Contract, member::Member};
-use crate::errors::SysError;
-use crate::models::uuid::Uuid;
+use crate::errors::{Subject, SysError};
+use crate::models::id::{ItemId, ItemKind};
 use crate::models::vec_wrapper::VecWrapper;
 use crate::types::FromMap;
 use crate::types::SysResult;
@@ -59,6 +62,74 @@ impl Display for Category {
     }
 }
 
+/// Free-form tags of an item.
+///
+/// Tags are stored lowercase and without duplicates. They are displayed separated
+/// by spaces and can be parsed from a list separated by spaces or commas.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Tags {
+    values: Vec<String>,
+}
+
+impl Tags {
+    /// Creates a new list of tags.
+    pub fn new(tags: Vec<&str>) -> Self {
+        let mut out = Tags::default();
+        for tag in tags {
+            out.add(tag);
+        }
+        out
+    }
+
+    /// Adds a tag, unless it is empty or already exists.
+    pub fn add(&mut self, tag: &str) {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !self.values.contains(&tag) {
+            self.values.push(tag);
+        }
+    }
+
+    /// Returns an iterator over the tags.
+    pub fn iter(&self) -> std::slice::Iter<'_, String> {
+        self.values.iter()
+    }
+}
+
+impl FromStr for Tags {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Tags::new(
+            s.split(|c: char| c == ',' || c.is_whitespace())
+                .collect::<Vec<&str>>(),
+        ))
+    }
+}
+
+impl Display for Tags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.values.join(" ").as_str())
+    }
+}
+
+/// Something that prevents an item from being booked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    /// A contract already books the item.
+    Booked(Box<Contract>),
+    /// The owner blocked the item.
+    Blackout(Period),
+}
+
+/// The availability of an item on a certain day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Availability {
+    /// The number of units that are free.
+    Free(usize),
+    /// The owner blocked the item.
+    Blackout,
+}
+
 /// Item.
 #[derive(
     Debug,
@@ -85,9 +156,12 @@ pub struct Item {
     #[getter(rename = "get_category")]
     category: Category,
 
+    #[getter(rename = "get_tags")]
+    tags: Tags,
+
     #[mutable_ignore]
     #[getter(rename = "get_history")]
-    history: VecWrapper<Contract>,
+    history: ContractHistory,
 
     #[mutable_ignore]
     #[getter(rename = "get_owner")]
@@ -100,6 +174,13 @@ pub struct Item {
     #[getter(rename = "get_cost_per_day")]
     cost_per_day: f64,
 
+    #[getter(rename = "get_quantity")]
+    quantity: usize,
+
+    #[mutable_ignore]
+    #[getter(rename = "get_blackouts")]
+    blackouts: VecWrapper<Period>,
+
     #[mutable_ignore]
     #[getter(rename = "get_is_available")]
     is_available: bool,
@@ -107,7 +188,7 @@ pub struct Item {
     #[eq]
     #[mutable_ignore]
     #[getter(rename = "get_uuid")]
-    uuid: Uuid,
+    uuid: ItemId,
 }
 
 impl Default for Item {
@@ -116,10 +197,13 @@ impl Default for Item {
             name: Default::default(),
             description: Default::default(),
             category: Default::default(),
+            tags: Default::default(),
             history: Default::default(),
             owner: Default::default(),
             day_of_creation: Default::default(),
             cost_per_day: Default::default(),
+            quantity: 1,
+            blackouts: Default::default(),
             is_available: true,
             uuid: Default::default(),
         }
@@ -139,58 +223,168 @@ impl Item {
         Item {
             name,
             category,
+            tags: Tags::default(),
             description,
             owner,
             cost_per_day,
+            quantity: 1,
+            blackouts: VecWrapper::new(),
             day_of_creation,
-            uuid: Uuid::new(),
-            history: VecWrapper::new(),
+            uuid: ItemId::new(),
+            history: ContractHistory::new(),
             is_available: true,
         }
     }
 
-    /// Adds a contract to history.
+    /// Adds a contract to history and books any unit that is free during the contract period.
     pub fn add_contract(&mut self, contract: Contract) -> SysResult<()> {
-        match self.get_contract_in_period(contract.get_start_date(), contract.get_end_date()) {
-            Some(_) => Err(SysError::AlreadyExists),
+        let (start_date, end_date) = (contract.get_start_date(), contract.get_end_date());
+        if self.get_blackout_in_period(start_date, end_date).is_some() {
+            return Err(SysError::Unavailable);
+        }
+        match self.get_free_unit(start_date, end_date) {
+            Some(unit) => self.add_contract_for_unit(contract, unit),
+            None => Err(SysError::Booked),
+        }
+    }
+
+    /// Blocks the item for a period, so nobody can book it.
+    /// Fails if the item is already booked during that period.
+    pub fn add_blackout(&mut self, period: Period) -> SysResult<()> {
+        if period.is_empty() {
+            return Err(SysError::InvalidInput);
+        }
+        if !self.history.overlapping(&period).is_empty() {
+            return Err(SysError::Booked);
+        }
+        self.blackouts.push(period);
+        Ok(())
+    }
+
+    /// Removes a blackout period.
+    pub fn remove_blackout(&mut self, period: &Period) -> SysResult<()> {
+        match self.blackouts.remove(*period) {
+            Some(_) => Ok(()),
+            None => Err(SysError::DoesntExist),
+        }
+    }
+
+    /// Gets a blackout period that overlaps with the period, if there is one.
+    pub fn get_blackout_in_period(&self, start_date: &usize, end_date: &usize) -> Option<Period> {
+        let period = Period::new(*start_date, *end_date);
+        self.blackouts
+            .iter()
+            .find(|blackout| blackout.overlaps(&period))
+            .copied()
+    }
+
+    /// Adds a contract to history that books a specific unit.
+    pub fn add_contract_for_unit(&mut self, contract: Contract, unit: usize) -> SysResult<()> {
+        if unit >= self.quantity {
+            return Err(SysError::DoesntExist);
+        }
+        match self.get_unit_contract_in_period(
+            unit,
+            contract.get_start_date(),
+            contract.get_end_date(),
+        ) {
+            Some(Conflict::Booked(_)) => Err(SysError::Booked),
+            Some(Conflict::Blackout(_)) => Err(SysError::Unavailable),
             None => {
                 if contract.get_lendee().get_credits() < contract.get_credits() {
                     return Err(SysError::CannotInsert);
                 }
-                self.history.push(contract);
+                self.history.push(contract.unit(unit));
                 Ok(())
             }
         }
     }
 
-    /// Gets the active contract. Returns Some(contract) if contract exists else None
-    pub fn get_active_contract(&self, now: usize) -> Option<Contract> {
-        for contract in self.history.iter() {
-            if &now > contract.get_start_date() && &now < contract.get_end_date() {
-                return Some(contract.clone());
+    /// Replaces a contract in the history with its new information.
+    pub fn update_contract(&mut self, contract: &Contract) -> SysResult<()> {
+        match self.history.index_of(contract) {
+            Some(idx) => {
+                self.history.set(idx, contract);
+                Ok(())
             }
+            None => Err(SysError::DoesntExist),
         }
-        None
     }
 
-    pub fn get_contract_in_period(&self, start_date: &usize, end_date: &usize) -> Option<Contract> {
-        for contract in self.history.iter() {
-            if start_date > contract.get_start_date() && start_date < contract.get_end_date()
-                || end_date > contract.get_start_date() && end_date < contract.get_end_date()
-            {
-                return Some(contract.clone());
-            }
+    /// Gets all active contracts, one for each unit that is currently lent out.
+    pub fn get_active_contracts(&self, now: usize) -> Vec<Contract> {
+        self.history
+            .covering(now)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<Contract>>()
+    }
+
+    /// Counts the contracts that have not started yet.
+    pub fn count_future_contracts(&self) -> usize {
+        self.count_contracts(Status::Future)
+    }
+
+    /// Counts the contracts that are running.
+    pub fn count_active_contracts(&self) -> usize {
+        self.count_contracts(Status::Active)
+    }
+
+    fn count_contracts(&self, status: Status) -> usize {
+        self.history
+            .iter()
+            .filter(|contract| *contract.get_status() == status)
+            .count()
+    }
+
+    /// Gets whatever prevents the item from being booked in the period, if anything does.
+    pub fn get_contract_in_period(&self, start_date: &usize, end_date: &usize) -> Option<Conflict> {
+        if let Some(blackout) = self.get_blackout_in_period(start_date, end_date) {
+            return Some(Conflict::Blackout(blackout));
         }
-        None
+        self.history
+            .overlapping(&Period::new(*start_date, *end_date))
+            .first()
+            .map(|contract| Conflict::Booked(Box::new((*contract).clone())))
     }
 
-    fn has_contract_on_date(&self, date: &usize) -> bool {
-        for contract in self.history.iter() {
-            if date >= contract.get_start_date() && date < contract.get_end_date() {
-                return true;
-            }
+    /// Gets whatever prevents a specific unit from being booked in the period, if anything does.
+    pub fn get_unit_contract_in_period(
+        &self,
+        unit: usize,
+        start_date: &usize,
+        end_date: &usize,
+    ) -> Option<Conflict> {
+        if let Some(blackout) = self.get_blackout_in_period(start_date, end_date) {
+            return Some(Conflict::Blackout(blackout));
         }
-        false
+        self.history
+            .overlapping_unit(&Period::new(*start_date, *end_date), unit)
+            .first()
+            .map(|contract| Conflict::Booked(Box::new((*contract).clone())))
+    }
+
+    /// Gets the first unit that is not booked during the period.
+    pub fn get_free_unit(&self, start_date: &usize, end_date: &usize) -> Option<usize> {
+        (0..self.quantity).find(|unit| {
+            self.get_unit_contract_in_period(*unit, start_date, end_date)
+                .is_none()
+        })
+    }
+
+    /// Gets the fewest units the item can have, every booked unit has to stay.
+    pub fn min_quantity(&self) -> usize {
+        self.history
+            .iter()
+            .filter(|c| *c.get_status() != Status::Canceled)
+            .map(|c| c.get_unit() + 1)
+            .fold(1, usize::max)
+    }
+
+    fn is_blacked_out(&self, date: &usize) -> bool {
+        self.blackouts
+            .iter()
+            .any(|blackout| blackout.contains(*date))
     }
 
     pub fn get_history_map(&self) -> HashMap<&str, Vec<Contract>> {
@@ -210,11 +404,35 @@ impl Item {
         HashMap::from([("past", past), ("future", future), ("active", active)])
     }
 
-    pub fn get_availability(&self, now: usize) -> Vec<(String, bool)> {
+    /// Returns the availability for each of the next 30 days.
+    pub fn get_availability(&self, now: usize) -> Vec<(String, Availability)> {
         let mut out = Vec::new();
         for i in now..(now + 30) {
-            out.push((i.to_string(), self.has_contract_on_date(&i)));
+            if self.is_blacked_out(&i) {
+                out.push((i.to_string(), Availability::Blackout));
+                continue;
+            }
+            let free = self.quantity.saturating_sub(self.history.booked_units(i));
+            out.push((i.to_string(), Availability::Free(free)));
         }
         out
     }
 }
+
+impl Secrets for Item {}
+
+impl Subject for Item {
+    type Kind = ItemKind;
+
+    fn get_entity(&self) -> Entity {
+        Entity::Item
+    }
+
+    fn get_typed_id(&self) -> &ItemId {
+        &self.uuid
+    }
+
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
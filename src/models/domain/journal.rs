@@ -0,0 +1,212 @@
+use super::{
+    item::Item,
+    ledger::{Mint, Payout},
+    member::Member,
+    time::Time,
+};
+use crate::models::id::{ItemId, MemberId};
+
+/// The most steps that are kept for undoing.
+pub const MAX_STEPS: usize = 50;
+
+/// The value something had before it was changed, `None` if it did not exist.
+///
+/// Reverting a change returns the value it replaced, so reverting that change
+/// again restores the state before the first revert.
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// A member.
+    Member(MemberId, Option<Box<Member>>),
+    /// An item.
+    Item(ItemId, Option<Box<Item>>),
+    /// The clock and all scheduled jobs.
+    Time(Time),
+    /// Credits that have been minted, reverting it mints the opposite amount.
+    Mint(Mint),
+    /// Credits that have been paid out, reverting it pays back the amount on the same day.
+    Payout(Payout),
+}
+
+impl Change {
+    /// Checks if both changes concern the same member, item or the time.
+    /// Mints and payouts are never the same target, every one of them has to be reverted.
+    pub fn is_same_target(&self, other: &Change) -> bool {
+        match (self, other) {
+            (Change::Member(a, _), Change::Member(b, _)) => a == b,
+            (Change::Item(a, _), Change::Item(b, _)) => a == b,
+            (Change::Time(_), Change::Time(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The changes recorded since a transaction began.
+///
+/// Only the first change of every member, item and the time is recorded, as that holds
+/// the information from before the transaction. The audit log, the pending events and
+/// the ledger are only ever appended to, so their length is enough to roll them back.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    changes: Vec<Change>,
+    audit_len: usize,
+    events_len: usize,
+    ledger_len: (usize, usize),
+    claims: usize,
+}
+
+impl Transaction {
+    /// Begins a transaction at the current length of the audit log, the pending events
+    /// and the ledger, and the current number of claimed ids.
+    pub fn new(
+        audit_len: usize,
+        events_len: usize,
+        ledger_len: (usize, usize),
+        claims: usize,
+    ) -> Self {
+        Self {
+            changes: Vec::new(),
+            audit_len,
+            events_len,
+            ledger_len,
+            claims,
+        }
+    }
+
+    /// Checks if a change concerning the same target has already been recorded.
+    pub fn contains(&self, change: &Change) -> bool {
+        self.changes.iter().any(|c| c.is_same_target(change))
+    }
+
+    /// Records a change, unless one concerning the same target has already been recorded.
+    pub fn record(&mut self, change: Change) {
+        if !self.contains(&change) {
+            self.changes.push(change);
+        }
+    }
+
+    /// Adds the changes of a nested transaction that has been committed.
+    pub fn append(&mut self, changes: Vec<Change>) {
+        for change in changes {
+            self.record(change);
+        }
+    }
+
+    /// Returns the length of the audit log when the transaction began.
+    pub fn get_audit_len(&self) -> usize {
+        self.audit_len
+    }
+
+    /// Returns the number of pending events when the transaction began.
+    pub fn get_events_len(&self) -> usize {
+        self.events_len
+    }
+
+    /// Returns the number of mints and payouts when the transaction began.
+    pub fn get_ledger_len(&self) -> (usize, usize) {
+        self.ledger_len
+    }
+
+    /// Returns the number of claimed ids when the transaction began.
+    pub fn get_claims(&self) -> usize {
+        self.claims
+    }
+
+    /// Returns the changes in the order they were made.
+    pub fn into_changes(self) -> Vec<Change> {
+        self.changes
+    }
+}
+
+/// The changes of a single command together with a description of the command.
+#[derive(Debug, Clone)]
+pub struct Step {
+    description: String,
+    changes: Vec<Change>,
+}
+
+impl Step {
+    /// Creates a new step from the changes in the order they were made.
+    pub fn new(description: &str, changes: Vec<Change>) -> Self {
+        Self {
+            description: description.to_owned(),
+            changes,
+        }
+    }
+
+    /// Returns the description of the command.
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the changes in the order they were made.
+    pub fn into_changes(self) -> Vec<Change> {
+        self.changes
+    }
+}
+
+/// The steps that can be undone and redone, the most recent one last.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: Vec<Step>,
+    redo: Vec<Step>,
+}
+
+impl History {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new step, which makes all undone steps impossible to redo.
+    pub fn push(&mut self, step: Step) {
+        self.redo.clear();
+        self.push_undo(step);
+    }
+
+    /// Adds a step that can be undone, keeping at most `MAX_STEPS` steps.
+    pub fn push_undo(&mut self, step: Step) {
+        self.undo.push(step);
+        if self.undo.len() > MAX_STEPS {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Adds a step that can be redone.
+    pub fn push_redo(&mut self, step: Step) {
+        self.redo.push(step);
+    }
+
+    /// Takes the most recent step that can be undone.
+    pub fn pop_undo(&mut self) -> Option<Step> {
+        self.undo.pop()
+    }
+
+    /// Takes the most recently undone step.
+    pub fn pop_redo(&mut self) -> Option<Step> {
+        self.redo.pop()
+    }
+
+    /// Returns the descriptions of the steps that can be undone, the next one first.
+    pub fn undo_steps(&self) -> Vec<&str> {
+        self.undo
+            .iter()
+            .rev()
+            .map(|s| s.get_description())
+            .collect()
+    }
+
+    /// Returns the descriptions of the steps that can be redone, the next one first.
+    pub fn redo_steps(&self) -> Vec<&str> {
+        self.redo
+            .iter()
+            .rev()
+            .map(|s| s.get_description())
+            .collect()
+    }
+
+    /// Forgets all steps.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
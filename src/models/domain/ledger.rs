@@ -0,0 +1,114 @@
+use crate::models::id::{ContractId, MemberId};
+use derive_getters::Getters;
+
+/// Credits that have been created or destroyed instead of being transferred.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Mint {
+    #[getter(rename = "get_day")]
+    day: usize,
+    #[getter(rename = "get_member")]
+    member: MemberId,
+    #[getter(rename = "get_member_name")]
+    member_name: String,
+    /// Negative if credits have been destroyed.
+    #[getter(rename = "get_amount")]
+    amount: f64,
+    #[getter(rename = "get_reason")]
+    reason: String,
+}
+
+/// Credits a lendee paid the owner for a day of a contract.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Payout {
+    #[getter(rename = "get_day")]
+    day: usize,
+    #[getter(rename = "get_contract")]
+    contract: ContractId,
+    /// Negative if an earlier payout has been reverted.
+    #[getter(rename = "get_amount")]
+    amount: f64,
+}
+
+/// Every mint and payout since the system was created, the oldest first.
+///
+/// Payouts only move credits between members, so the credits of all members together
+/// always add up to the total of the ledger.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    mints: Vec<Mint>,
+    payouts: Vec<Payout>,
+}
+
+impl Ledger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records credits that have been created for a member, or destroyed if negative.
+    /// Returns the recorded mint, nothing is recorded if the amount is zero.
+    pub fn mint(
+        &mut self,
+        day: usize,
+        member: (&MemberId, &str),
+        amount: f64,
+        reason: &str,
+    ) -> Option<Mint> {
+        if amount == 0.0 {
+            return None;
+        }
+        let mint = Mint {
+            day,
+            member: member.0.clone(),
+            member_name: member.1.to_owned(),
+            amount,
+            reason: reason.to_owned(),
+        };
+        self.mints.push(mint.clone());
+        Some(mint)
+    }
+
+    /// Returns the credits that have been created minus the credits that have been destroyed.
+    pub fn total(&self) -> f64 {
+        self.mints.iter().map(|m| m.amount).sum()
+    }
+
+    /// Returns all mints, the oldest first.
+    pub fn get_mints(&self) -> &[Mint] {
+        &self.mints
+    }
+
+    /// Records credits that have been paid for a day of the contract, or paid back if
+    /// negative.
+    pub fn pay(&mut self, day: usize, contract: &ContractId, amount: f64) -> Payout {
+        let payout = Payout {
+            day,
+            contract: contract.clone(),
+            amount,
+        };
+        self.payouts.push(payout.clone());
+        payout
+    }
+
+    /// Returns all payouts, the oldest first.
+    pub fn get_payouts(&self) -> &[Payout] {
+        &self.payouts
+    }
+
+    /// Returns the number of mints and the number of payouts.
+    pub fn len(&self) -> (usize, usize) {
+        (self.mints.len(), self.payouts.len())
+    }
+
+    /// Checks if no credits have been minted or paid out yet.
+    pub fn is_empty(&self) -> bool {
+        self.mints.is_empty() && self.payouts.is_empty()
+    }
+
+    /// Removes the mints and payouts recorded after the first ones, given by their number
+    /// as returned by `len`.
+    pub fn truncate(&mut self, len: (usize, usize)) {
+        self.mints.truncate(len.0);
+        self.payouts.truncate(len.1);
+    }
+}
@@ -1,5 +1,10 @@
-use crate::errors::{Check, MemValError};
-use crate::models::uuid::Uuid;
+use super::audit::{Entity, Secrets};
+use super::{
+    auth::{generate_salt, hash_password, Role},
+    contact::{ContactConfig, ContactRules},
+};
+use crate::errors::{Check, FieldError, MemValError, Subject};
+use crate::models::id::{MemberId, MemberKind, MAX_ID_LEN, MIN_ID_LEN};
 use crate::types::FromMap;
 use crate::types::{MemValResult, ValResult, Validate};
 use derive_getters::{Dissolve, Getters};
@@ -10,17 +15,60 @@ use shared::{
 use std::collections::HashMap;
 use std::str::FromStr;
 
+/// The status of a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemberStatus {
+    /// The member can use the system without restrictions.
+    #[default]
+    Active,
+    /// The member is suspended until the given day.
+    Suspended(usize),
+    /// The member is permanently banned.
+    Banned,
+}
+
+impl FromStr for MemberStatus {
+    type Err = MemValError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        match lower.strip_prefix("suspended until ") {
+            Some(day) => match day.parse::<usize>() {
+                Ok(day) => Ok(MemberStatus::Suspended(day)),
+                Err(_) => Err(MemValError::Status),
+            },
+            None => match lower.as_str() {
+                "active" => Ok(MemberStatus::Active),
+                "banned" => Ok(MemberStatus::Banned),
+                _ => Err(MemValError::Status),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for MemberStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberStatus::Active => f.write_str("Active"),
+            MemberStatus::Suspended(day) => write!(f, "Suspended until {}", day),
+            MemberStatus::Banned => f.write_str("Banned"),
+        }
+    }
+}
+
 /// Defines methods needed for member validation.
 pub trait MemberValidation {
     /// Validates the id.
     fn validate_id(&self) -> MemValResult<()>;
-    /// Validates the phone number.
-    fn validate_phone_nr(&self) -> MemValResult<()>;
-    /// Validates email.
-    fn validate_email(&self) -> MemValResult<()>;
+    /// Validates the phone number against the rules.
+    fn validate_phone_nr(&self, rules: &dyn ContactRules) -> MemValResult<()>;
+    /// Validates the email against the rules.
+    fn validate_email(&self, rules: &dyn ContactRules) -> MemValResult<()>;
 }
 
 /// Member.
+///
+/// Members are the same member if they have the same id, whatever their contact details.
 #[derive(
     Clone,
     Debug,
@@ -41,11 +89,9 @@ pub struct Member {
     name: String,
 
     #[getter(rename = "get_email")]
-    #[eq]
     email: String,
 
     #[getter(rename = "get_phone_nr")]
-    #[eq]
     phone_nr: String,
 
     #[getter(rename = "get_credits")]
@@ -56,13 +102,37 @@ pub struct Member {
     #[mutable_ignore]
     day_of_creation: usize,
 
+    #[eq]
     #[getter(rename = "get_uuid")]
     #[mutable_ignore]
-    uuid: Uuid,
+    uuid: MemberId,
+
+    #[getter(rename = "get_role")]
+    #[mutable_ignore]
+    role: Role,
+
+    #[getter(rename = "get_status")]
+    #[mutable_ignore]
+    status: MemberStatus,
+
+    #[getter(rename = "get_status_reason")]
+    #[mutable_ignore]
+    status_reason: String,
+
+    #[getter(rename = "get_salt")]
+    #[mutable_ignore]
+    #[hidden]
+    salt: String,
+
+    #[getter(rename = "get_password_hash")]
+    #[mutable_ignore]
+    #[hidden]
+    password_hash: String,
 }
 
 impl Member {
-    /// Creates new member.
+    /// Creates new member, whose contact information follows the default rules.
+    /// The system applies its own rules once the member is added to it.
     pub fn new(
         name: String,
         email: String,
@@ -70,16 +140,105 @@ impl Member {
         day_of_creation: usize,
     ) -> ValResult<Member> {
         let m = Member {
-            uuid: Uuid::new(),
+            uuid: MemberId::new(),
             credits: 0f64,
             day_of_creation,
             name,
             email,
             phone_nr,
+            role: Role::Member,
+            status: MemberStatus::Active,
+            status_reason: String::new(),
+            salt: String::new(),
+            password_hash: String::new(),
         };
         m.validate_and_build()
     }
 
+    /// Validates the member against the contact rules and stores the normalized
+    /// email and phone number. Every invalid field is reported.
+    pub fn validate_with(mut self, rules: &dyn ContactRules) -> ValResult<Member> {
+        let mut errors: Vec<FieldError> = Vec::new();
+        match rules.normalize_email(&self.email) {
+            Ok(email) => self.email = email,
+            Err(err) => errors.push(FieldError::new("email", err)),
+        }
+        if let Err(err) = self.validate_id() {
+            errors.push(FieldError::new("uuid", err));
+        }
+        match rules.normalize_phone_nr(&self.phone_nr) {
+            Ok(phone_nr) => self.phone_nr = phone_nr,
+            Err(err) => errors.push(FieldError::new("phone_nr", err)),
+        }
+        match errors.is_empty() {
+            true => Ok(self),
+            false => Err(Check::Invalid(errors)),
+        }
+    }
+
+    /// Sets a new password. Only the salted hash of the password is stored.
+    pub fn set_password(&mut self, password: &str) {
+        self.salt = generate_salt();
+        self.password_hash = hash_password(&self.salt, password);
+    }
+
+    /// Checks if the password matches the stored hash.
+    ///
+    /// Members without a password can never be logged in as.
+    pub fn verify_password(&self, password: &str) -> bool {
+        !self.password_hash.is_empty() && hash_password(&self.salt, password) == self.password_hash
+    }
+
+    /// Suspends the member until the given day.
+    pub fn suspend(&mut self, until: usize, reason: &str) {
+        self.status = MemberStatus::Suspended(until);
+        self.status_reason = Self::sanitize_reason(reason);
+    }
+
+    /// Bans the member permanently.
+    pub fn ban(&mut self, reason: &str) {
+        self.status = MemberStatus::Banned;
+        self.status_reason = Self::sanitize_reason(reason);
+    }
+
+    /// Lifts any suspension or ban.
+    pub fn reinstate(&mut self) {
+        self.status = MemberStatus::Active;
+        self.status_reason = String::new();
+    }
+
+    /// Checks if the member is neither suspended nor banned.
+    pub fn is_active(&self) -> bool {
+        self.status == MemberStatus::Active
+    }
+
+    /// Reinstates the member if his suspension ended. Returns true if it did.
+    pub fn expire_suspension(&mut self, now: usize) -> bool {
+        match self.status {
+            MemberStatus::Suspended(until) if now >= until => {
+                self.reinstate();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Separators of the map format are not allowed in the reason.
+    fn sanitize_reason(reason: &str) -> String {
+        reason
+            .chars()
+            .map(|c| match c {
+                ',' | ';' | '[' | ']' => ' ',
+                _ => c,
+            })
+            .collect()
+    }
+
+    /// Checks if the member is an admin.
+    pub fn is_admin(&self) -> bool {
+        self.role == Role::Admin
+    }
+
     /// Adds credits to member.
     pub fn add_credits(&mut self, credits: f64) -> MemValResult<()> {
         if credits < 0.0 {
@@ -104,7 +263,7 @@ impl Member {
 
 impl MemberValidation for Member {
     fn validate_id(&self) -> MemValResult<()> {
-        if let true = self.get_uuid().get_len() != &6 {
+        if !(MIN_ID_LEN..=MAX_ID_LEN).contains(self.get_uuid().get_len()) {
             return Err(MemValError::Id);
         }
 
@@ -121,62 +280,24 @@ impl MemberValidation for Member {
         Ok(())
     }
 
-    fn validate_phone_nr(&self) -> MemValResult<()> {
-        if let false = self
-            .get_phone_nr()
-            .chars()
-            .map(|chr| chr.is_ascii_digit() || chr.is_whitespace())
-            .all(|is_digit| is_digit)
-        {
-            return Err(MemValError::PhoneNumberContainsNonNumeric);
-        }
-
-        let reg = regex::Regex::new(r"([ 0-9]){8,12}$").unwrap();
-
-        if let false = reg.is_match(self.get_phone_nr()) {
-            return Err(MemValError::PhoneNumberPattern);
-        }
-
-        if self.get_phone_nr().len() > 12 || self.get_phone_nr().len() < 8 {
-            return Err(MemValError::PhoneNumber);
-        }
-
-        Ok(())
+    fn validate_phone_nr(&self, rules: &dyn ContactRules) -> MemValResult<()> {
+        rules.normalize_phone_nr(self.get_phone_nr()).map(|_| ())
     }
 
-    fn validate_email(&self) -> MemValResult<()> {
-        let email_regex = regex::Regex::new(
-            r"^([a-z0-9_+]([a-z0-9_+.]*[a-z0-9_+])?)@([a-z0-9]+([\-\.]{1}[a-z0-9]+)*\.[a-z]{2,6})",
-        )
-        .unwrap();
-
-        if let false = email_regex.is_match(self.get_email()) {
-            return Err(MemValError::EmailPattern);
-        }
-
-        Ok(())
+    fn validate_email(&self, rules: &dyn ContactRules) -> MemValResult<()> {
+        rules.normalize_email(self.get_email()).map(|_| ())
     }
 }
 
 impl Validate<Member> for Member {
     fn validate(&self) -> ValResult<()> {
-        if let Err(err) = self.validate_email() {
-            return Err(Check::Invalid(err.to_string()));
-        }
-        if let Err(err) = self.validate_id() {
-            return Err(Check::Invalid(err.to_string()));
-        }
-        if let Err(err) = self.validate_phone_nr() {
-            return Err(Check::Invalid(err.to_string()));
-        }
-        Ok(())
+        self.clone()
+            .validate_with(&ContactConfig::default())
+            .map(|_| ())
     }
 
     fn validate_and_build(self) -> ValResult<Member> {
-        match self.validate() {
-            Ok(_) => Ok(self),
-            Err(err) => Err(err),
-        }
+        self.validate_with(&ContactConfig::default())
     }
 }
 
@@ -188,7 +309,34 @@ impl Default for Member {
             phone_nr: String::new(),
             credits: 0f64,
             day_of_creation: 0,
-            uuid: Uuid::new(),
+            uuid: MemberId::new(),
+            role: Role::Member,
+            status: MemberStatus::Active,
+            status_reason: String::new(),
+            salt: String::new(),
+            password_hash: String::new(),
         }
     }
 }
+
+impl Secrets for Member {
+    fn get_secrets(&self) -> Vec<(&'static str, &str)> {
+        vec![("password", self.password_hash.as_str())]
+    }
+}
+
+impl Subject for Member {
+    type Kind = MemberKind;
+
+    fn get_entity(&self) -> Entity {
+        Entity::Member
+    }
+
+    fn get_typed_id(&self) -> &MemberId {
+        &self.uuid
+    }
+
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
@@ -0,0 +1,93 @@
+use super::member::Member;
+use crate::models::id::MemberId;
+use std::{collections::HashMap, fmt::Display};
+
+/// A field no two members are allowed to share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniqueField {
+    /// The id of the member.
+    Uuid,
+    /// The email address of the member.
+    Email,
+    /// The phone number of the member.
+    PhoneNr,
+}
+
+impl UniqueField {
+    /// The name of the field in the map of a member.
+    pub fn get_field(&self) -> &'static str {
+        match *self {
+            UniqueField::Uuid => "uuid",
+            UniqueField::Email => "email",
+            UniqueField::PhoneNr => "phone_nr",
+        }
+    }
+}
+
+impl Display for UniqueField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            UniqueField::Uuid => f.write_str("id"),
+            UniqueField::Email => f.write_str("email"),
+            UniqueField::PhoneNr => f.write_str("phone number"),
+        }
+    }
+}
+
+/// Finds members by their email and phone number.
+///
+/// Members themselves are stored by their uuid, so the index only maps the other
+/// unique fields to it. The system stores emails and phone numbers in the form the
+/// contact rules normalize them to, so the index compares the stored values.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MemberIndex {
+    emails: HashMap<String, MemberId>,
+    phone_nrs: HashMap<String, MemberId>,
+}
+
+impl MemberIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the uuid of the member with the email.
+    pub fn by_email(&self, email: &str) -> Option<&MemberId> {
+        self.emails.get(email)
+    }
+
+    /// Gets the uuid of the member with the phone number.
+    pub fn by_phone_nr(&self, phone_nr: &str) -> Option<&MemberId> {
+        self.phone_nrs.get(phone_nr)
+    }
+
+    /// Returns the first field the member shares with a different member, if there is one.
+    pub fn collision(&self, member: &Member) -> Option<UniqueField> {
+        let taken = |owner: Option<&MemberId>| owner.is_some_and(|uuid| uuid != member.get_uuid());
+        if taken(self.by_email(member.get_email())) {
+            return Some(UniqueField::Email);
+        }
+        if taken(self.by_phone_nr(member.get_phone_nr())) {
+            return Some(UniqueField::PhoneNr);
+        }
+        None
+    }
+
+    /// Adds the fields of a member.
+    pub fn insert(&mut self, member: &Member) {
+        let uuid = member.get_uuid().clone();
+        self.emails.insert(member.get_email().clone(), uuid.clone());
+        self.phone_nrs.insert(member.get_phone_nr().clone(), uuid);
+    }
+
+    /// Removes the fields of a member, unless they already belong to someone else.
+    pub fn remove(&mut self, member: &Member) {
+        let uuid = member.get_uuid();
+        if self.emails.get(member.get_email()) == Some(uuid) {
+            self.emails.remove(member.get_email());
+        }
+        if self.phone_nrs.get(member.get_phone_nr()) == Some(uuid) {
+            self.phone_nrs.remove(member.get_phone_nr());
+        }
+    }
+}
@@ -1,10 +1,42 @@
+/// Record of every mutation.
+pub mod audit;
+/// Member accounts, roles and permissions.
+pub mod auth;
+/// Reversible mutations of the system.
+pub mod command;
+/// Synthetic activity of a simulated lending community.
+pub mod community;
+/// Rules for the email and phone number of members.
+pub mod contact;
 /// Contract model.
 pub mod contract;
+/// Contracts by their uuid with references to their items and members.
+pub mod contract_store;
+/// Contracts of an item indexed by the days they book.
+pub mod interval;
+/// Rules the state of the system has to follow.
+pub mod invariants;
 /// Item model.
 pub mod item;
+/// Recorded changes for undoing and redoing commands.
+pub mod journal;
+/// Credits created and destroyed outside of payouts.
+pub mod ledger;
 /// Member model.
 pub mod member;
+/// Lookups of members by their unique fields.
+pub mod member_index;
+/// Notifications for members.
+pub mod notification;
+/// Ranges of days.
+pub mod period;
+/// Statistics over items, members and contracts.
+pub mod report;
+/// Full-text search over items.
+pub mod search;
+/// Advancing the time by many days at once.
+pub mod simulation;
 /// System model.
 pub mod system;
-/// Takes care of managing the time.
+/// Takes care of managing the time and the jobs scheduled for future days.
 pub mod time;
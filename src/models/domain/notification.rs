@@ -0,0 +1,286 @@
+use super::{member::Member, time::Listener};
+use crate::models::id::MemberId;
+use shared::Model;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+/// Members with less credits than this after being charged get a low balance reminder.
+pub const LOW_BALANCE_THRESHOLD: f64 = 50.0;
+
+/// Everything that happens in the system that members should be told about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A new contract was created.
+    BookingRequested {
+        /// The day the event happened.
+        day: usize,
+        /// The owner of the item.
+        owner: Member,
+        /// The member that booked the item.
+        lendee: Member,
+        /// The name of the item.
+        item: String,
+        /// The first day of the contract.
+        start_date: usize,
+        /// The first day after the contract.
+        end_date: usize,
+    },
+    /// A contract starts on the next day.
+    BookingStartsTomorrow {
+        /// The day the event happened.
+        day: usize,
+        /// The owner of the item.
+        owner: Member,
+        /// The member that booked the item.
+        lendee: Member,
+        /// The name of the item.
+        item: String,
+    },
+    /// A contract ended and the item has to be returned.
+    ReturnDue {
+        /// The day the event happened.
+        day: usize,
+        /// The owner of the item.
+        owner: Member,
+        /// The member that booked the item.
+        lendee: Member,
+        /// The name of the item.
+        item: String,
+    },
+    /// A member has been charged for a day of lending.
+    Charged {
+        /// The day the event happened.
+        day: usize,
+        /// The member that has been charged.
+        member: Member,
+        /// The number of credits.
+        amount: f64,
+        /// The name of the item.
+        item: String,
+    },
+    /// A member has less credits than `LOW_BALANCE_THRESHOLD`.
+    LowBalance {
+        /// The day the event happened.
+        day: usize,
+        /// The member with the low balance.
+        member: Member,
+    },
+}
+
+impl Event {
+    /// Renders the notifications for all members concerned by this event.
+    pub fn notifications(&self) -> Vec<Notification> {
+        match self {
+            Event::BookingRequested {
+                day,
+                owner,
+                lendee,
+                item,
+                start_date,
+                end_date,
+            } => vec![
+                Notification::new(
+                    *day,
+                    owner,
+                    "Booking requested",
+                    format!(
+                        "{} booked your item '{}' from day {} to day {}.",
+                        lendee.get_name(),
+                        item,
+                        start_date,
+                        end_date
+                    ),
+                ),
+                Notification::new(
+                    *day,
+                    lendee,
+                    "Booking confirmed",
+                    format!(
+                        "You booked '{}' from day {} to day {}.",
+                        item, start_date, end_date
+                    ),
+                ),
+            ],
+            Event::BookingStartsTomorrow {
+                day,
+                owner,
+                lendee,
+                item,
+            } => vec![
+                Notification::new(
+                    *day,
+                    owner,
+                    "Booking starts tomorrow",
+                    format!(
+                        "Your item '{}' will be lent to {} tomorrow.",
+                        item,
+                        lendee.get_name()
+                    ),
+                ),
+                Notification::new(
+                    *day,
+                    lendee,
+                    "Booking starts tomorrow",
+                    format!(
+                        "Your booking of '{}' from {} starts tomorrow.",
+                        item,
+                        owner.get_name()
+                    ),
+                ),
+            ],
+            Event::ReturnDue {
+                day,
+                owner,
+                lendee,
+                item,
+            } => vec![Notification::new(
+                *day,
+                lendee,
+                "Return due",
+                format!(
+                    "Your booking of '{}' ended, please return it to {}.",
+                    item,
+                    owner.get_name()
+                ),
+            )],
+            Event::Charged {
+                day,
+                member,
+                amount,
+                item,
+            } => vec![Notification::new(
+                *day,
+                member,
+                "Credits charged",
+                format!("You have been charged {} credits for '{}'.", amount, item),
+            )],
+            Event::LowBalance { day, member } => vec![Notification::new(
+                *day,
+                member,
+                "Low balance",
+                format!(
+                    "You only have {} credits left. Bookings you cannot pay for will fail.",
+                    member.get_credits()
+                ),
+            )],
+        }
+    }
+}
+
+/// A message for a single member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    day: usize,
+    recipient: MemberId,
+    recipient_name: String,
+    recipient_email: String,
+    subject: String,
+    message: String,
+}
+
+impl Notification {
+    /// Creates a new notification.
+    pub fn new(day: usize, recipient: &Member, subject: &str, message: String) -> Self {
+        Self {
+            day,
+            recipient: recipient.get_uuid().clone(),
+            recipient_name: recipient.get_name().clone(),
+            recipient_email: recipient.get_email().clone(),
+            subject: subject.to_owned(),
+            message,
+        }
+    }
+
+    /// Returns the day the notification was sent.
+    pub fn get_day(&self) -> usize {
+        self.day
+    }
+
+    /// Returns the uuid of the member that receives the notification.
+    pub fn get_recipient(&self) -> &MemberId {
+        &self.recipient
+    }
+
+    /// Returns the subject.
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Returns the message.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Renders the notification as a message file.
+    pub fn render(&self) -> String {
+        format!(
+            "To: {} <{}>\nDay: {}\nSubject: {}\n\n{}\n",
+            self.recipient_name, self.recipient_email, self.day, self.subject, self.message
+        )
+    }
+}
+
+/// Listens to the events of the system and delivers notifications into the inboxes
+/// of the members and, if configured, as message files into an outbox directory.
+#[derive(Debug, Clone, PartialEq, Default, Model)]
+pub struct NotificationService {
+    inboxes: HashMap<MemberId, Vec<Notification>>,
+    outbox: Option<PathBuf>,
+    sent: usize,
+    last: Option<Event>,
+}
+
+impl NotificationService {
+    /// Creates a new notification service that only delivers into inboxes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Additionally writes every notification as a file into the outbox directory.
+    pub fn with_outbox(outbox: PathBuf) -> Self {
+        Self {
+            outbox: Some(outbox),
+            ..Self::default()
+        }
+    }
+
+    /// Returns all notifications of a member, the oldest first.
+    pub fn get_inbox(&self, member: &Member) -> Vec<&Notification> {
+        match self.inboxes.get(member.get_uuid()) {
+            Some(inbox) => inbox.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn deliver(&mut self, notification: Notification) {
+        self.sent += 1;
+        if let Some(dir) = &self.outbox {
+            let file = dir.join(format!(
+                "day-{:04}-{}-{:06}.txt",
+                notification.day,
+                notification.recipient.get_value(),
+                self.sent
+            ));
+            // A failing outbox must never stop the simulation, the inbox is always delivered.
+            if fs::create_dir_all(dir).is_ok() {
+                let _ = fs::write(file, notification.render());
+            }
+        }
+        self.inboxes
+            .entry(notification.recipient.clone())
+            .or_default()
+            .push(notification);
+    }
+}
+
+impl Listener<Event> for NotificationService {
+    fn update(&mut self, new: Event) {
+        for notification in new.notifications() {
+            self.deliver(notification);
+        }
+        self.last = Some(new);
+    }
+
+    fn pull(&self) -> Option<Event> {
+        self.last.clone()
+    }
+}
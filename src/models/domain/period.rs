@@ -0,0 +1,69 @@
+use crate::errors::SysError;
+use std::{fmt::Display, str::FromStr};
+
+/// A range of days. The start day is included, the end day is not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Period {
+    start: usize,
+    end: usize,
+}
+
+impl Period {
+    /// Creates a new period from the start day (inclusive) to the end day (exclusive).
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates a new period starting at a day and lasting a number of days.
+    pub fn from_len(start: usize, days: usize) -> Self {
+        Self::new(start, start + days)
+    }
+
+    /// Returns the first day of the period.
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the first day after the period.
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of days in the period.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Checks if the period does not contain any days.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the day is part of the period.
+    pub fn contains(&self, day: usize) -> bool {
+        day >= self.start && day < self.end
+    }
+
+    /// Checks if the two periods share at least one day.
+    pub fn overlaps(&self, other: &Period) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+impl FromStr for Period {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.trim().split_once("..").ok_or(SysError::InvalidInput)?;
+        match (start.parse::<usize>(), end.parse::<usize>()) {
+            (Ok(start), Ok(end)) if start <= end => Ok(Period::new(start, end)),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
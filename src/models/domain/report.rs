@@ -0,0 +1,347 @@
+use super::{
+    contract::{Contract, Status},
+    item::Item,
+    period::Period,
+    system::LendingSystem,
+};
+use crate::models::id::ContractId;
+use derive_getters::Getters;
+use std::collections::HashMap;
+
+/// How many members are listed as top borrowers.
+pub const TOP_BORROWERS: usize = 10;
+
+/// Upper bounds of the credit ranges members are counted in, the last range is open.
+const CREDIT_BUCKETS: [f64; 4] = [50.0, 100.0, 200.0, 500.0];
+
+/// A titled table of a report.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct ReportSection {
+    #[getter(rename = "get_title")]
+    title: String,
+    #[getter(rename = "get_head")]
+    head: Vec<String>,
+    #[getter(rename = "get_rows")]
+    rows: Vec<Vec<String>>,
+}
+
+impl ReportSection {
+    fn new(title: &str, head: Vec<&str>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            title: title.to_owned(),
+            head: head.into_iter().map(|h| h.to_owned()).collect(),
+            rows,
+        }
+    }
+}
+
+/// All reports available for a range of days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportKind {
+    /// Booked days compared to the days an item existed.
+    Utilization,
+    /// Credits earned by every item.
+    ItemRevenue,
+    /// Credits earned by every owner.
+    OwnerRevenue,
+    /// The members that spent the most credits.
+    TopBorrowers,
+    /// How many members have how many credits today, independent of the period.
+    CreditDistribution,
+    /// Bookings per category.
+    CategoryPopularity,
+}
+
+impl ReportKind {
+    /// Returns all kinds of reports.
+    pub fn all() -> Vec<ReportKind> {
+        vec![
+            ReportKind::Utilization,
+            ReportKind::ItemRevenue,
+            ReportKind::OwnerRevenue,
+            ReportKind::TopBorrowers,
+            ReportKind::CreditDistribution,
+            ReportKind::CategoryPopularity,
+        ]
+    }
+}
+
+/// Computes reports over the state of a lending system for a range of days.
+///
+/// A contract books the days from its start date up to, but excluding, its end date.
+/// Canceled contracts book nothing and are left out of every report but revenue.
+/// Revenue counts the credits the ledger recorded as paid out on the days of the period,
+/// so failed payouts earn nothing and later price changes do not change past revenue.
+pub struct Reports<'a, M>
+where
+    M: LendingSystem,
+{
+    sys: &'a M,
+    period: Period,
+    /// Credits paid out per contract inside the period.
+    paid: HashMap<ContractId, f64>,
+}
+
+impl<'a, M> Reports<'a, M>
+where
+    M: LendingSystem,
+{
+    /// Creates the reports for the days in the period.
+    pub fn new(sys: &'a M, period: Period) -> Self {
+        let mut paid = HashMap::new();
+        for payout in sys.get_ledger().get_payouts() {
+            if period.contains(*payout.get_day()) {
+                *paid.entry(payout.get_contract().clone()).or_insert(0.0) += payout.get_amount();
+            }
+        }
+        Self { sys, period, paid }
+    }
+
+    /// Computes a single report.
+    pub fn section(&self, kind: ReportKind) -> ReportSection {
+        match kind {
+            ReportKind::Utilization => self.utilization(),
+            ReportKind::ItemRevenue => self.item_revenue(),
+            ReportKind::OwnerRevenue => self.owner_revenue(),
+            ReportKind::TopBorrowers => self.top_borrowers(),
+            ReportKind::CreditDistribution => self.credit_distribution(),
+            ReportKind::CategoryPopularity => self.category_popularity(),
+        }
+    }
+
+    /// Computes all reports.
+    pub fn sections(&self) -> Vec<ReportSection> {
+        ReportKind::all()
+            .into_iter()
+            .map(|kind| self.section(kind))
+            .collect()
+    }
+
+    /// Returns the items sorted by name, so the reports are stable.
+    fn items(&self) -> Vec<&'a Item> {
+        let mut items = self.sys.get_items();
+        items.sort_by(|a, b| {
+            a.get_name()
+                .cmp(b.get_name())
+                .then_with(|| a.get_uuid().get_value().cmp(b.get_uuid().get_value()))
+        });
+        items
+    }
+
+    /// Returns the contracts of the item that have not been canceled.
+    fn contracts(item: &Item) -> impl Iterator<Item = &Contract> {
+        item.get_history()
+            .iter()
+            .filter(|c| *c.get_status() != Status::Canceled)
+    }
+
+    /// Number of days of the contract inside the period.
+    fn booked_days(&self, contract: &Contract) -> usize {
+        let booked = Period::new(*contract.get_start_date(), *contract.get_end_date());
+        let start = usize::max(booked.get_start(), self.period.get_start());
+        let end = usize::min(booked.get_end(), self.period.get_end());
+        end.saturating_sub(start)
+    }
+
+    /// Credits paid out for the contract inside the period.
+    fn paid(&self, contract: &Contract) -> f64 {
+        self.paid.get(contract.get_uuid()).copied().unwrap_or(0.0)
+    }
+
+    fn revenue(&self, item: &Item) -> f64 {
+        item.get_history()
+            .iter()
+            .fold(0.0, |sum, c| sum + self.paid(c))
+    }
+
+    fn utilization(&self) -> ReportSection {
+        let rows = self
+            .items()
+            .into_iter()
+            .map(|item| {
+                let start = usize::max(*item.get_day_of_creation(), self.period.get_start());
+                let days = self.period.get_end().saturating_sub(start);
+                let available = days * item.get_quantity();
+                let booked = Self::contracts(item)
+                    .map(|c| self.booked_days(c))
+                    .sum::<usize>();
+                let utilization = match available {
+                    0 => 0.0,
+                    n => booked as f64 / n as f64 * 100.0,
+                };
+                vec![
+                    item.get_name().clone(),
+                    item.get_owner().get_name().clone(),
+                    booked.to_string(),
+                    available.to_string(),
+                    format!("{:.1}%", utilization),
+                ]
+            })
+            .collect();
+        ReportSection::new(
+            "Utilization",
+            vec![
+                "Item",
+                "Owner",
+                "Booked Days",
+                "Available Days",
+                "Utilization",
+            ],
+            rows,
+        )
+    }
+
+    fn item_revenue(&self) -> ReportSection {
+        let mut revenue = self
+            .items()
+            .into_iter()
+            .map(|item| (item, self.revenue(item)))
+            .collect::<Vec<(&Item, f64)>>();
+        revenue.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let rows = revenue
+            .into_iter()
+            .map(|(item, credits)| {
+                vec![
+                    item.get_name().clone(),
+                    item.get_owner().get_name().clone(),
+                    credits.to_string(),
+                ]
+            })
+            .collect();
+        ReportSection::new("Revenue per Item", vec!["Item", "Owner", "Credits"], rows)
+    }
+
+    fn owner_revenue(&self) -> ReportSection {
+        let mut revenue: HashMap<String, (String, usize, f64)> = HashMap::new();
+        for item in self.items() {
+            let owner = item.get_owner();
+            let entry = revenue
+                .entry(owner.get_uuid().get_value().clone())
+                .or_insert((owner.get_name().clone(), 0, 0.0));
+            entry.1 += 1;
+            entry.2 += self.revenue(item);
+        }
+        let mut revenue = revenue.into_values().collect::<Vec<_>>();
+        revenue.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        let rows = revenue
+            .into_iter()
+            .map(|(name, items, credits)| vec![name, items.to_string(), credits.to_string()])
+            .collect();
+        ReportSection::new("Revenue per Owner", vec!["Owner", "Items", "Credits"], rows)
+    }
+
+    fn top_borrowers(&self) -> ReportSection {
+        let mut borrowers: HashMap<String, (String, usize, usize, f64)> = HashMap::new();
+        for item in self.items() {
+            for contract in Self::contracts(item) {
+                let days = self.booked_days(contract);
+                if days == 0 {
+                    continue;
+                }
+                let lendee = contract.get_lendee();
+                let entry = borrowers
+                    .entry(lendee.get_uuid().get_value().clone())
+                    .or_insert((lendee.get_name().clone(), 0, 0, 0.0));
+                entry.1 += 1;
+                entry.2 += days;
+                entry.3 += self.paid(contract);
+            }
+        }
+        let mut borrowers = borrowers.into_values().collect::<Vec<_>>();
+        borrowers.sort_by(|a, b| {
+            b.3.total_cmp(&a.3)
+                .then_with(|| b.2.cmp(&a.2))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        let rows = borrowers
+            .into_iter()
+            .take(TOP_BORROWERS)
+            .map(|(name, contracts, days, credits)| {
+                vec![
+                    name,
+                    contracts.to_string(),
+                    days.to_string(),
+                    credits.to_string(),
+                ]
+            })
+            .collect();
+        ReportSection::new(
+            "Top Borrowers",
+            vec!["Member", "Contracts", "Booked Days", "Credits Spent"],
+            rows,
+        )
+    }
+
+    fn credit_distribution(&self) -> ReportSection {
+        let members = self.sys.get_members();
+        let mut counts = vec![0usize; CREDIT_BUCKETS.len() + 1];
+        for member in members.iter() {
+            let bucket = CREDIT_BUCKETS
+                .iter()
+                .position(|bound| member.get_credits() < bound)
+                .unwrap_or(CREDIT_BUCKETS.len());
+            counts[bucket] += 1;
+        }
+        let rows = counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let range = match i {
+                    0 => format!("below {}", CREDIT_BUCKETS[0]),
+                    i if i == CREDIT_BUCKETS.len() => format!("{} or more", CREDIT_BUCKETS[i - 1]),
+                    i => format!("{} to {}", CREDIT_BUCKETS[i - 1], CREDIT_BUCKETS[i]),
+                };
+                let share = match members.len() {
+                    0 => 0.0,
+                    n => *count as f64 / n as f64 * 100.0,
+                };
+                vec![range, count.to_string(), format!("{:.1}%", share)]
+            })
+            .collect();
+        ReportSection::new(
+            "Credit Distribution",
+            vec!["Credits", "Members", "Share"],
+            rows,
+        )
+    }
+
+    fn category_popularity(&self) -> ReportSection {
+        let mut categories: HashMap<String, (usize, usize, usize)> = HashMap::new();
+        for item in self.items() {
+            let entry = categories
+                .entry(item.get_category().to_string())
+                .or_insert((0, 0, 0));
+            entry.0 += 1;
+            for contract in Self::contracts(item) {
+                let days = self.booked_days(contract);
+                if days > 0 {
+                    entry.1 += 1;
+                    entry.2 += days;
+                }
+            }
+        }
+        let mut categories = categories.into_iter().collect::<Vec<_>>();
+        categories.sort_by(|a, b| {
+            b.1 .1
+                .cmp(&a.1 .1)
+                .then_with(|| b.1 .2.cmp(&a.1 .2))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        let rows = categories
+            .into_iter()
+            .map(|(category, (items, contracts, days))| {
+                vec![
+                    category,
+                    items.to_string(),
+                    contracts.to_string(),
+                    days.to_string(),
+                ]
+            })
+            .collect();
+        ReportSection::new(
+            "Category Popularity",
+            vec!["Category", "Items", "Contracts", "Booked Days"],
+            rows,
+        )
+    }
+}
@@ -0,0 +1,126 @@
+use super::item::Item;
+use crate::models::id::ItemId;
+use std::collections::{HashMap, HashSet};
+
+/// How much a match in the name of an item counts.
+const NAME_WEIGHT: f64 = 3.0;
+/// How much a match in the tags of an item counts.
+const TAG_WEIGHT: f64 = 2.0;
+/// How much a match in the description of an item counts.
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+
+/// Splits a text into lowercase alphanumeric terms.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// In-memory inverted index over the name, description and tags of items.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Maps every term to the items containing it and the weighted term frequency.
+    postings: HashMap<String, HashMap<ItemId, f64>>,
+    /// Maps every item to its terms, so it can be removed without scanning all postings.
+    terms: HashMap<ItemId, HashSet<String>>,
+}
+
+impl SearchIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item to the index.
+    pub fn insert(&mut self, item: &Item) {
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        let fields = [
+            (tokenize(item.get_name()), NAME_WEIGHT),
+            (tokenize(item.get_description()), DESCRIPTION_WEIGHT),
+            (
+                item.get_tags()
+                    .iter()
+                    .flat_map(|tag| tokenize(tag))
+                    .collect::<Vec<String>>(),
+                TAG_WEIGHT,
+            ),
+        ];
+        for (terms, weight) in fields {
+            for term in terms {
+                *weights.entry(term).or_insert(0.0) += weight;
+            }
+        }
+
+        let uuid = item.get_uuid();
+        for (term, weight) in weights.iter() {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(uuid.clone(), *weight);
+        }
+        self.terms.insert(
+            uuid.clone(),
+            weights.into_keys().collect::<HashSet<String>>(),
+        );
+    }
+
+    /// Removes an item from the index.
+    pub fn remove(&mut self, uuid: &ItemId) {
+        if let Some(terms) = self.terms.remove(uuid) {
+            for term in terms {
+                if let Some(posting) = self.postings.get_mut(&term) {
+                    posting.remove(uuid);
+                    if posting.is_empty() {
+                        self.postings.remove(&term);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the indexed information of an item.
+    pub fn update(&mut self, item: &Item) {
+        self.remove(item.get_uuid());
+        self.insert(item);
+    }
+
+    /// Returns the number of indexed items.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Checks if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Searches for items matching any of the keywords in the query.
+    ///
+    /// Every matching term adds its weighted frequency times its inverse document frequency
+    /// to the score of an item, so rare terms and items matching more keywords rank higher.
+    /// The results are sorted by descending score.
+    pub fn search(&self, query: &str) -> Vec<(ItemId, f64)> {
+        let total = self.len() as f64;
+        let mut scores: HashMap<&ItemId, f64> = HashMap::new();
+        let keywords = tokenize(query).into_iter().collect::<HashSet<String>>();
+        for keyword in keywords.iter() {
+            if let Some(posting) = self.postings.get(keyword) {
+                let idf = (1.0 + total / posting.len() as f64).ln();
+                for (uuid, weight) in posting.iter() {
+                    *scores.entry(uuid).or_insert(0.0) += weight * idf;
+                }
+            }
+        }
+
+        let mut out = scores
+            .into_iter()
+            .map(|(uuid, score)| (uuid.clone(), score))
+            .collect::<Vec<(ItemId, f64)>>();
+        out.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then_with(|| a.0.get_value().cmp(b.0.get_value()))
+        });
+        out
+    }
+}
@@ -0,0 +1,150 @@
+use super::{contract::Status, system::LendingSystem};
+use crate::models::id::{ContractId, MemberId};
+use derive_getters::Getters;
+use std::collections::HashMap;
+
+/// The most days a simulation advances while waiting for a stop condition.
+pub const MAX_SIMULATED_DAYS: usize = 365;
+
+/// Conditions that stop a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopCondition {
+    /// Any contract ends.
+    ContractEnds,
+    /// The credits of the member drop below the threshold.
+    BalanceBelow(MemberId, f64),
+    /// Any owner or lendee could not be settled.
+    SettlementFailure,
+}
+
+/// How far a simulation advances the time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Advance {
+    /// A number of days.
+    Days(usize),
+    /// Up to and including a specific day.
+    ToDay(usize),
+    /// Until the condition holds, but at most `MAX_SIMULATED_DAYS` days.
+    Until(StopCondition),
+}
+
+/// What happened on a single simulated day.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct DaySummary {
+    #[getter(rename = "get_day")]
+    day: usize,
+    #[getter(rename = "get_active_contracts")]
+    active_contracts: usize,
+    #[getter(rename = "get_ended_contracts")]
+    ended_contracts: usize,
+    #[getter(rename = "get_credits_transferred")]
+    credits_transferred: f64,
+    #[getter(rename = "get_lowest_balance")]
+    lowest_balance: Option<(String, f64)>,
+    #[getter(rename = "get_settled")]
+    settled: bool,
+}
+
+/// The outcome of a simulation.
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Simulation {
+    #[getter(rename = "get_days")]
+    days: Vec<DaySummary>,
+    #[getter(rename = "get_condition_met")]
+    condition_met: bool,
+}
+
+/// Advances the time of the system and summarizes every day.
+///
+/// Failed settlements do not stop the simulation unless waiting for
+/// `StopCondition::SettlementFailure`, they are recorded in the summary of the day.
+pub fn simulate<M>(sys: &mut M, advance: &Advance) -> Simulation
+where
+    M: LendingSystem,
+{
+    let mut days: Vec<DaySummary> = Vec::new();
+    let (limit, condition) = match advance {
+        Advance::Days(n) => (*n, None),
+        Advance::ToDay(day) => (day.saturating_sub(sys.now()), None),
+        Advance::Until(condition) => (MAX_SIMULATED_DAYS, Some(condition)),
+    };
+    for _ in 0..limit {
+        let summary = simulate_day(sys);
+        let met = match condition {
+            Some(StopCondition::ContractEnds) => summary.ended_contracts > 0,
+            Some(StopCondition::BalanceBelow(uuid, threshold)) => {
+                match sys.get_member_by_id(uuid) {
+                    Ok(member) => member.get_credits() < threshold,
+                    Err(_) => false,
+                }
+            }
+            Some(StopCondition::SettlementFailure) => !summary.settled,
+            None => false,
+        };
+        days.push(summary);
+        if met {
+            return Simulation {
+                days,
+                condition_met: true,
+            };
+        }
+    }
+    Simulation {
+        days,
+        condition_met: false,
+    }
+}
+
+/// Advances the time by a single day.
+///
+/// Canceled contracts are never active, so they are neither counted as active nor as ended.
+fn simulate_day<M>(sys: &mut M) -> DaySummary
+where
+    M: LendingSystem,
+{
+    let before = sys
+        .get_members()
+        .iter()
+        .map(|m| (m.get_uuid().clone(), *m.get_credits()))
+        .collect::<HashMap<MemberId, f64>>();
+    let running = sys
+        .get_active_contracts()
+        .iter()
+        .map(|c| c.get_uuid().clone())
+        .collect::<Vec<ContractId>>();
+    let settled = sys.incr_time().is_ok();
+    let day = sys.now();
+
+    // Credits only ever increase by being paid for lending an item.
+    let credits_transferred = sys
+        .get_members()
+        .iter()
+        .map(|m| match before.get(m.get_uuid()) {
+            Some(old) if m.get_credits() > old => m.get_credits() - old,
+            _ => 0.0,
+        })
+        .sum::<f64>();
+    let lowest_balance = sys
+        .get_members()
+        .iter()
+        .min_by(|a, b| {
+            a.get_credits()
+                .total_cmp(b.get_credits())
+                .then_with(|| a.get_name().cmp(b.get_name()))
+        })
+        .map(|m| (m.get_name().clone(), *m.get_credits()));
+    let ended_contracts = running
+        .iter()
+        .filter_map(|uuid| sys.get_contract_by_id(uuid).ok())
+        .filter(|c| *c.get_status() == Status::Finished && *c.get_end_date() == day)
+        .count();
+
+    DaySummary {
+        day,
+        active_contracts: sys.get_active_contracts().len(),
+        ended_contracts,
+        credits_transferred,
+        lowest_balance,
+        settled,
+    }
+}
@@ -1,15 +1,27 @@
 use super::{
-    contract::Contract,
-    item::{Category, Item},
-    member::Member,
+    audit::{AuditEntry, AuditLog, Entity, Secrets},
+    auth::{Action, Role},
+    command::Command,
+    contact::{ContactConfig, ContactRules},
+    contract::{Contract, Status},
+    contract_store::ContractStore,
+    invariants::{check_invariants, Violation},
+    item::{Category, Item, Tags},
+    journal::{Change, History, Step, Transaction},
+    ledger::Ledger,
+    member::{Member, MemberStatus},
+    member_index::{MemberIndex, UniqueField},
+    notification::{Event, Notification, NotificationService, LOW_BALANCE_THRESHOLD},
+    search::SearchIndex,
+    time::{Job, Listenable, Listener, Time},
 };
 use crate::{
-    errors::SysError,
-    models::uuid::Uuid,
-    types::{Demo, SysResult},
+    errors::{MemValError, SysError},
+    models::id::{ContractId, Id, IdGenerator, ItemId, Kind, MemberId},
+    types::{Demo, SysResult, ToMap},
 };
 use shared::{Builder, Model};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 /// All methods for the lending system.
 pub trait LendingSystem {
@@ -17,32 +29,71 @@ pub trait LendingSystem {
     fn get_members(&self) -> Vec<&Member>;
     /// Gets a specific member.
     fn get_member(&self, member: &Member) -> SysResult<Member>;
+    /// Gets the member with the id.
+    fn get_member_by_id(&self, id: &MemberId) -> SysResult<&Member>;
+    /// Gets the member with the email.
+    fn get_member_by_email(&self, email: &str) -> SysResult<Member>;
     /// Returns a mutable version of a member.
+    /// Its email and phone number can only be changed through `update_member`.
     fn get_member_mut(&mut self, member: &Member) -> SysResult<&mut Member>;
     /// Adds a member to the system.
     fn add_member(&mut self, member: Member) -> SysResult<()>;
-    /// Removes a member from the system.
+    /// Removes a member from the system, members who still own items or borrow in contracts
+    /// that are running or have not started yet stay.
     fn remove_member(&mut self, member: &Member) -> SysResult<()>;
     /// updates a member with the new information.
+    /// Credits the new information adds or takes away are recorded in the ledger.
     fn update_member(&mut self, old_info: &Member, new_info: &Member) -> SysResult<()>;
     /// checks if the member passed into function acutally exists in this system.
     fn exists_member(&self, member: &Member) -> bool;
+    /// Checks that no other member uses the id, email or phone number of the member, and
+    /// that no other entity has claimed the id. The email and phone number are compared in the form the contact rules store them in.
+    fn check_unique(&self, member: &Member) -> SysResult<()>;
+    /// Checks that no other member uses the email or phone number of the member,
+    /// which the member itself may keep when it is edited.
+    fn check_unique_contact(&self, member: &Member) -> SysResult<()>;
+    /// Gets the rules the email and phone number of members have to follow.
+    fn get_contact_rules(&self) -> &ContactConfig;
+    /// Creates an id that no entity of the system uses.
+    fn generate_id<K: Kind>(&mut self) -> Id<K>;
+    /// Makes the ids created from now on only depend on the seed.
+    fn seed_ids(&mut self, seed: u64);
+    /// Makes the ids created from now on this long, the length is kept between
+    /// `MIN_ID_LEN` and `MAX_ID_LEN`.
+    fn set_id_len(&mut self, len: usize);
+    /// Gets the length of the ids the system creates.
+    fn get_id_len(&self) -> usize;
     /// Gets all the items in the system.
     fn get_items(&self) -> Vec<&Item>;
     /// Gets all the items for a specific member.
     fn get_items_for_member(&self, member: &Member) -> Vec<&Item>;
+    /// Gets all items that can be booked, which excludes items of suspended or banned owners.
+    fn get_bookable_items(&self) -> Vec<&Item>;
+    /// Gets the contracts a member owns or lends, ordered by their start.
+    fn get_contracts_for_member(&self, member: &Member) -> Vec<&Contract>;
+    /// Gets all contracts whose items are currently lent out, ordered by their start.
+    fn get_active_contracts(&self) -> Vec<&Contract>;
     /// Returns Some if item exists otherwise returns None.
     fn get_item_for_contract(&self, contract: &Contract) -> Option<&Item>;
     /// Returns Some if item exists otherwise returns None.
     fn get_item(&self, item: &Item) -> SysResult<Item>;
+    /// Gets the item with the id.
+    fn get_item_by_id(&self, id: &ItemId) -> SysResult<&Item>;
     /// Gets contract for.
     fn get_contract(&self, contract: &Contract) -> SysResult<Contract>;
+    /// Gets the contract with the id.
+    fn get_contract_by_id(&self, id: &ContractId) -> SysResult<&Contract>;
     /// Adds item to the system.
     fn add_item(&mut self, item: Item) -> SysResult<()>;
-    /// Removes item from the system.
+    /// Removes item from the system, items with contracts that are running or have not started yet stay.
     fn remove_item(&mut self, item: &Item) -> SysResult<()>;
     /// Updates item with the new information.
+    /// The quantity has to keep every unit that is booked by a contract that is not canceled.
+    /// Contracts that have been added or changed are scheduled again.
     fn update_item(&mut self, info: &Item) -> SysResult<()>;
+    /// Searches for items by keywords in their name, description and tags.
+    /// The best matches come first.
+    fn search_items(&self, query: &str) -> Vec<&Item>;
     /// Counts the number of items for a certain member.
     fn count_items_for_member(&self, member: &Member) -> usize;
     /// Increments system day counter and calls all required methods to update contracts
@@ -50,14 +101,104 @@ pub trait LendingSystem {
     fn incr_time(&mut self) -> SysResult<()>;
     /// Gets current time.
     fn now(&self) -> usize;
+    /// Gets all jobs that are scheduled for the following days, the next one first.
+    fn get_scheduled_jobs(&self) -> Vec<(usize, &Job)>;
+    /// Books a specific unit of an item, or any free unit if no unit is given.
+    /// Suspended or banned lendees and owners cannot book, and every contract needs an id
+    /// no other contract uses.
+    fn add_contract(
+        &mut self,
+        item: &Item,
+        contract: Contract,
+        unit: Option<usize>,
+    ) -> SysResult<()>;
+    /// Gets all notifications of a member, the oldest first.
+    /// The system keeps the inboxes itself, added listeners get every event as well.
+    fn get_notifications(&self, member: &Member) -> Vec<Notification>;
+    /// Logs in the member with the given email and password.
+    fn login(&mut self, email: &str, password: &str) -> SysResult<Member>;
+    /// Logs out the current member.
+    fn logout(&mut self);
+    /// Returns the member that is currently logged in.
+    fn get_current_user(&self) -> Option<&Member>;
+    /// Checks if the currently logged in member is allowed to execute an action.
+    fn authorize(&self, action: Action) -> SysResult<()>;
+    /// Gets the audit entries concerning the entity kind and done by the actor, if given.
+    fn get_audit_entries(
+        &self,
+        entity: Option<Entity>,
+        actor: Option<&MemberId>,
+    ) -> Vec<&AuditEntry>;
+    /// Gets the credits that have been created and destroyed outside of payouts.
+    fn get_ledger(&self) -> &Ledger;
+    /// Verifies the whole state of the system and returns every violation.
+    fn check_invariants(&self) -> Vec<Violation>;
+    /// Gets the violations that changes introduced in debug builds, together with
+    /// the description of the step that introduced them.
+    fn get_broken_invariants(&self) -> &[(String, Violation)];
+    /// Begins a transaction, transactions can be nested.
+    fn begin(&mut self);
+    /// Keeps all changes since the transaction began.
+    fn commit(&mut self) -> SysResult<()>;
+    /// Reverts all changes since the transaction began.
+    fn rollback(&mut self) -> SysResult<()>;
+    /// Runs the operation in a transaction, which is committed if the operation succeeds
+    /// and rolled back if it fails.
+    fn transaction<T, F>(&mut self, operation: F) -> SysResult<T>
+    where
+        F: FnOnce(&mut Self) -> SysResult<T>,
+        Self: Sized,
+    {
+        self.begin();
+        match operation(self) {
+            Ok(value) => {
+                self.commit()?;
+                Ok(value)
+            }
+            Err(err) => {
+                self.rollback()?;
+                Err(err)
+            }
+        }
+    }
+    /// Executes a command, so that it can be undone afterwards.
+    /// A command that fails leaves the system unchanged.
+    fn execute<C: Command>(&mut self, command: &mut C) -> SysResult<()>;
+    /// Undoes the most recent command and returns its description.
+    /// Notifications that were already sent stay in the inboxes.
+    fn undo(&mut self) -> SysResult<String>;
+    /// Redoes the most recently undone command and returns its description.
+    /// The notifications of the command are not sent again.
+    fn redo(&mut self) -> SysResult<String>;
+    /// Gets the descriptions of the commands that can be undone, the next one first.
+    fn get_undo_steps(&self) -> Vec<&str>;
+    /// Gets the descriptions of the commands that can be redone, the next one first.
+    fn get_redo_steps(&self) -> Vec<&str>;
 }
 
+/// The credits the owner of an item gets for listing it.
+const LISTING_REWARD: f64 = 100f64;
+
 /// system struct.
 #[derive(Debug, Clone, Model, Builder)]
 pub struct System {
-    members: HashMap<Uuid, Member>,
-    items: HashMap<Uuid, Item>,
-    day: usize,
+    members: HashMap<MemberId, Member>,
+    contacts: MemberIndex,
+    items: HashMap<ItemId, Item>,
+    contracts: ContractStore,
+    time: Time,
+    session: Option<MemberId>,
+    index: SearchIndex,
+    inbox: NotificationService,
+    listeners: Vec<NotificationService>,
+    events: Vec<Event>,
+    audit: AuditLog,
+    journal: Vec<Transaction>,
+    history: History,
+    ledger: Ledger,
+    broken: Vec<(String, Violation)>,
+    ids: IdGenerator,
+    contact: ContactConfig,
 }
 
 impl System {
@@ -65,8 +206,448 @@ impl System {
     pub fn new() -> System {
         System {
             members: HashMap::new(),
+            contacts: MemberIndex::new(),
             items: HashMap::new(),
-            day: 0,
+            contracts: ContractStore::new(),
+            time: Time::new(0),
+            session: None,
+            index: SearchIndex::new(),
+            inbox: NotificationService::new(),
+            listeners: Vec::new(),
+            events: Vec::new(),
+            audit: AuditLog::new(),
+            journal: Vec::new(),
+            history: History::new(),
+            ledger: Ledger::new(),
+            broken: Vec::new(),
+            ids: IdGenerator::new(),
+            contact: ContactConfig::default(),
+        }
+    }
+
+    /// Returns the current information of a member, or the given information
+    /// if the member is not part of the system (anymore).
+    fn fresh(&self, member: &Member) -> Member {
+        match self.members.get(member.get_uuid()) {
+            Some(m) => m.clone(),
+            None => member.clone(),
+        }
+    }
+
+    /// Returns the member with the email and phone number in the form they are stored in,
+    /// or the member as it is if they break the contact rules.
+    fn normalized(&self, member: &Member) -> Member {
+        member
+            .clone()
+            .validate_with(&self.contact)
+            .unwrap_or_else(|_| member.clone())
+    }
+
+    /// Keeps the violations the step introduced, so the diagnostics page can show them.
+    /// Only debug builds check the invariants, and changes that are part of a transaction
+    /// are checked once the outermost transaction is done.
+    fn watch_invariants(&mut self, step: &str) {
+        if !cfg!(debug_assertions) || !self.journal.is_empty() {
+            return;
+        }
+        for violation in self.check_invariants() {
+            if !self.broken.iter().any(|(_, known)| *known == violation) {
+                self.broken.push((step.to_owned(), violation));
+            }
+        }
+    }
+
+    /// Records a mutation done by the logged in member in the audit log.
+    fn record<T: ToMap + Secrets, I: Display>(
+        &mut self,
+        target: (Entity, &I, &str),
+        before: Option<&T>,
+        after: Option<&T>,
+    ) {
+        let actor = self.get_current_user().cloned();
+        self.record_as(actor.as_ref(), target, before, after);
+    }
+
+    /// Records a mutation the system did on its own, like scheduled status changes,
+    /// payouts and rewards, in the audit log.
+    fn record_system<T: ToMap + Secrets, I: Display>(
+        &mut self,
+        target: (Entity, &I, &str),
+        before: Option<&T>,
+        after: Option<&T>,
+    ) {
+        self.record_as(None, target, before, after);
+    }
+
+    fn record_as<T: ToMap + Secrets, I: Display>(
+        &mut self,
+        actor: Option<&Member>,
+        target: (Entity, &I, &str),
+        before: Option<&T>,
+        after: Option<&T>,
+    ) {
+        let (entity, id, name) = target;
+        let secrets = match (before, after) {
+            (Some(b), Some(a)) => b
+                .get_secrets()
+                .into_iter()
+                .zip(a.get_secrets())
+                .filter(|(old, new)| old != new)
+                .map(|((field, _), _)| field)
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.audit.record(
+            actor,
+            self.now(),
+            (entity, id.to_string(), name),
+            before.map(|b| b.to_map()).as_ref(),
+            after.map(|a| a.to_map()).as_ref(),
+            &secrets,
+        );
+    }
+
+    /// Changes the credits of a member by the amount, which is negative to take credits,
+    /// and records the change as done by the system.
+    fn transfer(&mut self, uuid: &MemberId, amount: f64) -> SysResult<Member> {
+        let member = self.member_mut(uuid).ok_or(SysError::DoesntExist)?;
+        let before = member.clone();
+        let changed = match amount < 0f64 {
+            true => member.deduce_credits(-amount),
+            false => member.add_credits(amount),
+        };
+        changed.map_err(|_| SysError::CannotUpdate)?;
+        let after = member.clone();
+        let target = (Entity::Member, uuid, after.get_name().as_str());
+        self.record_system(target, Some(&before), Some(&after));
+        Ok(after)
+    }
+
+    /// Records credits of a member that have been created, or destroyed if negative.
+    fn mint(&mut self, member: &Member, amount: f64, reason: &str) {
+        let day = self.now();
+        let member = (member.get_uuid(), member.get_name().as_str());
+        let minted = self.ledger.mint(day, member, amount, reason);
+        if let (Some(mint), Some(transaction)) = (minted, self.journal.last_mut()) {
+            transaction.record(Change::Mint(mint));
+        }
+    }
+
+    /// Records credits that have been paid for a day of the contract.
+    fn pay(&mut self, contract: &Contract, amount: f64) {
+        let payout = self.ledger.pay(self.now(), contract.get_uuid(), amount);
+        if let Some(transaction) = self.journal.last_mut() {
+            transaction.record(Change::Payout(payout));
+        }
+    }
+
+    /// Checks if a transaction is in progress and nothing has been recorded yet for the target.
+    fn needs_journal(&self, target: &Change) -> bool {
+        match self.journal.last() {
+            Some(transaction) => !transaction.contains(target),
+            None => false,
+        }
+    }
+
+    /// Ends the current transaction and returns its changes, which become part of the
+    /// enclosing transaction if there is one.
+    fn finish(&mut self) -> Option<Vec<Change>> {
+        let changes = self.journal.pop()?.into_changes();
+        if let Some(outer) = self.journal.last_mut() {
+            outer.append(changes.clone());
+        }
+        Some(changes)
+    }
+
+    /// Remembers the current information of a member before it is changed.
+    fn journal_member(&mut self, uuid: &MemberId) {
+        if self.needs_journal(&Change::Member(uuid.clone(), None)) {
+            let old = self.members.get(uuid).cloned().map(Box::new);
+            if let Some(transaction) = self.journal.last_mut() {
+                transaction.record(Change::Member(uuid.clone(), old));
+            }
+        }
+    }
+
+    /// Remembers the current information of an item before it is changed.
+    fn journal_item(&mut self, uuid: &ItemId) {
+        if self.needs_journal(&Change::Item(uuid.clone(), None)) {
+            let old = self.items.get(uuid).cloned().map(Box::new);
+            if let Some(transaction) = self.journal.last_mut() {
+                transaction.record(Change::Item(uuid.clone(), old));
+            }
+        }
+    }
+
+    /// Remembers the clock and scheduled jobs before they are changed.
+    fn journal_time(&mut self) {
+        if self.needs_journal(&Change::Time(Time::default())) {
+            let old = self.time.clone();
+            if let Some(transaction) = self.journal.last_mut() {
+                transaction.record(Change::Time(old));
+            }
+        }
+    }
+
+    fn member_mut(&mut self, uuid: &MemberId) -> Option<&mut Member> {
+        self.journal_member(uuid);
+        self.members.get_mut(uuid)
+    }
+
+    fn item_mut(&mut self, uuid: &ItemId) -> Option<&mut Item> {
+        self.journal_item(uuid);
+        self.items.get_mut(uuid)
+    }
+
+    fn time_mut(&mut self) -> &mut Time {
+        self.journal_time();
+        &mut self.time
+    }
+
+    /// Puts back the recorded information and returns the changes that undo the revert.
+    fn revert(&mut self, changes: Vec<Change>) -> Vec<Change> {
+        changes
+            .into_iter()
+            .rev()
+            .map(|change| self.restore(change))
+            .collect()
+    }
+
+    fn restore(&mut self, change: Change) -> Change {
+        match change {
+            Change::Member(uuid, old) => {
+                let old = old.map(|m| *m);
+                let current = match old.clone() {
+                    Some(m) => self.members.insert(uuid.clone(), m),
+                    None => self.members.remove(&uuid),
+                };
+                if let Some(m) = current.as_ref() {
+                    self.contacts.remove(m);
+                }
+                if let Some(m) = old.as_ref() {
+                    self.contacts.insert(m);
+                }
+                if let Some(m) = old.as_ref().or(current.as_ref()) {
+                    let target = (Entity::Member, &uuid, m.get_name().as_str());
+                    self.record(target, current.as_ref(), old.as_ref());
+                }
+                Change::Member(uuid, current.map(Box::new))
+            }
+            Change::Item(uuid, old) => {
+                let current = match old.clone() {
+                    Some(i) => {
+                        self.index.update(&i);
+                        self.items.insert(uuid.clone(), *i)
+                    }
+                    None => {
+                        self.index.remove(&uuid);
+                        self.items.remove(&uuid)
+                    }
+                };
+                self.contracts.sync(&uuid, self.items.get(&uuid));
+                if let Some(i) = old.as_deref().or(current.as_ref()) {
+                    let target = (Entity::Item, &uuid, i.get_name().as_str());
+                    self.record(target, current.as_ref(), old.as_deref());
+                }
+                Change::Item(uuid, current.map(Box::new))
+            }
+            Change::Time(old) => Change::Time(std::mem::replace(&mut self.time, old)),
+            Change::Mint(mint) => {
+                let member = (mint.get_member(), mint.get_member_name().as_str());
+                let reverted =
+                    self.ledger
+                        .mint(self.time.now(), member, -mint.get_amount(), "Reverted");
+                Change::Mint(reverted.unwrap_or(mint))
+            }
+            Change::Payout(payout) => {
+                let (day, contract) = (*payout.get_day(), payout.get_contract());
+                Change::Payout(self.ledger.pay(day, contract, -payout.get_amount()))
+            }
+        }
+    }
+
+    /// Returns the current information of a contract and the item it belongs to.
+    /// Canceled contracts are not returned, so their jobs do nothing.
+    fn find_contract(&self, item: &ItemId, contract: &ContractId) -> Option<(Item, Contract)> {
+        let item = self.items.get(item)?;
+        item.get_history()
+            .iter()
+            .find(|c| c.get_uuid() == contract)
+            .filter(|c| *c.get_status() != Status::Canceled)
+            .map(|c| (item.clone(), c.clone()))
+    }
+
+    /// Schedules the status changes, payouts and reminders of a contract
+    /// that are still ahead. A contract that has already started is active right away and
+    /// one that has already ended is finished, canceled contracts have nothing to schedule.
+    fn schedule_contract(&mut self, item: &ItemId, contract: &Contract) {
+        if *contract.get_status() == Status::Canceled {
+            return;
+        }
+        let now = self.now();
+        let (start, end) = (*contract.get_start_date(), *contract.get_end_date());
+        if start <= now && now < end && *contract.get_status() == Status::Future {
+            self.set_contract_status(item, contract, Status::Active);
+        }
+        if end <= now && *contract.get_status() != Status::Finished {
+            self.set_contract_status(item, contract, Status::Finished);
+        }
+        let (item, contract) = (item.clone(), contract.get_uuid().clone());
+        let mut jobs: Vec<(usize, Job)> = Vec::new();
+        if start > now + 1 {
+            let (item, contract) = (item.clone(), contract.clone());
+            jobs.push((start - 1, Job::RemindStart { item, contract }));
+        }
+        if start > now {
+            let (item, contract) = (item.clone(), contract.clone());
+            jobs.push((start, Job::StartContract { item, contract }));
+        }
+        // Credits are transferred on every day strictly between the start and the end.
+        let first_payout = usize::max(start, now) + 1;
+        if first_payout < end {
+            let (item, contract) = (item.clone(), contract.clone());
+            jobs.push((first_payout, Job::Payout { item, contract }));
+        }
+        if end > now {
+            jobs.push((end, Job::FinishContract { item, contract }));
+        }
+        for (day, job) in jobs {
+            self.time_mut()
+                .schedule(day, job)
+                .expect("Only future days are scheduled.");
+        }
+    }
+
+    /// Schedules the end of the suspension of a member, if there is one.
+    fn schedule_suspension(&mut self, member: &Member) {
+        if let MemberStatus::Suspended(until) = member.get_status() {
+            let day = usize::max(*until, self.now() + 1);
+            let job = Job::ExpireSuspension {
+                member: member.get_uuid().clone(),
+            };
+            self.time_mut()
+                .schedule(day, job)
+                .expect("Only future days are scheduled.");
+        }
+    }
+
+    /// Changes the status of a contract if it still belongs to the item.
+    fn set_contract_status(&mut self, item: &ItemId, contract: &Contract, status: Status) {
+        let updated = contract.clone().status(status).build();
+        if let Some(i) = self.item_mut(item) {
+            if i.update_contract(&updated).is_ok() {
+                let name = i.get_name().clone();
+                self.contracts.sync(item, self.items.get(item));
+                let target = (Entity::Contract, contract.get_uuid(), name.as_str());
+                self.record_system(target, Some(contract), Some(&updated));
+            }
+        }
+    }
+
+    /// Executes a job that is due today.
+    ///
+    /// Jobs of contracts that have been removed, moved or canceled since they were scheduled
+    /// are skipped.
+    fn dispatch(&mut self, job: Job, charged: &mut Vec<Member>) -> SysResult<()> {
+        let day = self.now();
+        match job {
+            Job::ExpireSuspension { member } => {
+                if let Some(m) = self.member_mut(&member) {
+                    let before = m.clone();
+                    m.expire_suspension(day);
+                    let after = m.clone();
+                    let target = (Entity::Member, &member, after.get_name().as_str());
+                    self.record_system(target, Some(&before), Some(&after));
+                }
+                Ok(())
+            }
+            Job::StartContract { item, contract } => {
+                if let Some((_, con)) = self.find_contract(&item, &contract) {
+                    if *con.get_start_date() == day {
+                        self.set_contract_status(&item, &con, Status::Active);
+                    }
+                }
+                Ok(())
+            }
+            Job::Payout { item, contract } => match self.find_contract(&item, &contract) {
+                Some((i, con)) if *con.get_start_date() < day && day < *con.get_end_date() => {
+                    if day + 1 < *con.get_end_date() {
+                        self.time_mut()
+                            .schedule(day + 1, Job::Payout { item, contract })?;
+                    }
+                    self.payout(&i, &con, charged)
+                }
+                _ => Ok(()),
+            },
+            Job::FinishContract { item, contract } => {
+                if let Some((i, con)) = self.find_contract(&item, &contract) {
+                    if *con.get_end_date() == day {
+                        self.set_contract_status(&item, &con, Status::Finished);
+                        self.events.push(Event::ReturnDue {
+                            day,
+                            owner: self.fresh(con.get_owner()),
+                            lendee: self.fresh(con.get_lendee()),
+                            item: i.get_name().clone(),
+                        });
+                    }
+                }
+                Ok(())
+            }
+            Job::RemindStart { item, contract } => {
+                if let Some((i, con)) = self.find_contract(&item, &contract) {
+                    if *con.get_start_date() == day + 1 {
+                        self.events.push(Event::BookingStartsTomorrow {
+                            day,
+                            owner: self.fresh(con.get_owner()),
+                            lendee: self.fresh(con.get_lendee()),
+                            item: i.get_name().clone(),
+                        });
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Transfers the credits for one day of a contract from the lendee to the owner.
+    /// Nothing is transferred if the lendee cannot pay or if the owner has left,
+    /// since the credits would otherwise be lost.
+    fn payout(&mut self, item: &Item, con: &Contract, charged: &mut Vec<Member>) -> SysResult<()> {
+        let cost = *item.get_cost_per_day();
+        self.transaction(|sys| {
+            let lendee = sys.transfer(con.get_lendee().get_uuid(), -cost)?;
+            sys.transfer(con.get_owner().get_uuid(), cost)?;
+            sys.pay(con, cost);
+            sys.events.push(Event::Charged {
+                day: sys.now(),
+                member: lendee.clone(),
+                amount: cost,
+                item: item.get_name().clone(),
+            });
+            charged.push(lendee);
+            Ok(())
+        })
+    }
+}
+
+impl Listenable<Event, NotificationService> for System {
+    fn add_listener(&mut self, listener: NotificationService) {
+        self.listeners.push(listener);
+    }
+
+    fn pop_listener(&mut self, listener: NotificationService) -> Option<NotificationService> {
+        match self.listeners.iter().position(|l| *l == listener) {
+            Some(i) => Some(self.listeners.remove(i)),
+            None => None,
+        }
+    }
+
+    fn notify_listeners(&mut self) {
+        for event in self.events.drain(..) {
+            self.inbox.update(event.clone());
+            for listener in self.listeners.iter_mut() {
+                listener.update(event.clone());
+            }
         }
     }
 }
@@ -80,48 +661,154 @@ impl LendingSystem for System {
     }
 
     fn get_member(&self, member: &Member) -> SysResult<Member> {
-        match self.members.get(member.get_uuid()) {
-            Some(m) => Ok(m.clone()),
-            None => Err(SysError::DoesntExist),
-        }
+        self.get_member_by_id(member.get_uuid()).cloned()
+    }
+
+    fn get_member_by_id(&self, id: &MemberId) -> SysResult<&Member> {
+        self.members.get(id).ok_or(SysError::DoesntExist)
     }
 
     fn get_member_mut(&mut self, member: &Member) -> SysResult<&mut Member> {
-        match self.members.get_mut(member.get_uuid()) {
+        match self.member_mut(member.get_uuid()) {
             Some(m) => Ok(m),
             None => Err(SysError::DoesntExist),
         }
     }
 
+    fn get_member_by_email(&self, email: &str) -> SysResult<Member> {
+        let email = self
+            .contact
+            .normalize_email(email)
+            .unwrap_or_else(|_| email.to_owned());
+        self.contacts
+            .by_email(&email)
+            .and_then(|uuid| self.members.get(uuid))
+            .cloned()
+            .ok_or(SysError::DoesntExist)
+    }
+
     fn add_member(&mut self, member: Member) -> SysResult<()> {
-        if self.exists_member(&member) {
-            return Err(SysError::AlreadyExists);
-        }
+        let member = member.validate_with(&self.contact)?;
+        self.check_unique(&member)?;
+        self.schedule_suspension(&member);
+        let target = (
+            Entity::Member,
+            member.get_uuid(),
+            member.get_name().as_str(),
+        );
+        self.record(target, None, Some(&member));
+        self.mint(&member, *member.get_credits(), "Joined");
+        self.journal_member(member.get_uuid());
+        self.ids.claim(member.get_uuid());
+        self.contacts.insert(&member);
+        let step = format!("Add member {}", member.get_name());
         self.members.insert(member.get_uuid().clone(), member);
+        self.watch_invariants(&step);
         Ok(())
     }
 
     fn remove_member(&mut self, member: &Member) -> SysResult<()> {
-        if !self.exists_member(member) {
+        if !self.members.contains_key(member.get_uuid()) {
             return Err(SysError::DoesntExist);
         }
-        self.members.remove(member.get_uuid());
+        let owned = self.count_items_for_member(member);
+        if owned > 0 {
+            return Err(SysError::OwnsItems(owned));
+        }
+        let contracts = self.contracts.for_member(member.get_uuid());
+        let count = |status: Status| {
+            contracts
+                .iter()
+                .filter(|c| *c.get_status() == status)
+                .count()
+        };
+        let (active, future) = (count(Status::Active), count(Status::Future));
+        if active > 0 {
+            return Err(SysError::ActiveContracts(active));
+        }
+        if future > 0 {
+            return Err(SysError::FutureContracts(future));
+        }
+        self.journal_member(member.get_uuid());
+        if let Some(old) = self.members.remove(member.get_uuid()) {
+            self.contacts.remove(&old);
+            let target = (Entity::Member, old.get_uuid(), old.get_name().as_str());
+            self.record(target, Some(&old), None);
+            self.mint(&old, -old.get_credits(), "Left");
+        }
+        self.time_mut()
+            .cancel(|job| job.is_for_member(member.get_uuid()));
+        self.watch_invariants(&format!("Remove member {}", member.get_name()));
         Ok(())
     }
 
     fn update_member(&mut self, old_info: &Member, new_info: &Member) -> SysResult<()> {
-        if !self.exists_member(old_info) {
+        if !self.members.contains_key(old_info.get_uuid()) {
             return Err(SysError::DoesntExist);
         }
-        *self.members.get_mut(old_info.get_uuid()).unwrap() = new_info.clone();
+        let new_info = &new_info.clone().validate_with(&self.contact)?;
+        self.check_unique_contact(new_info)?;
+        let old = self.member_mut(old_info.get_uuid()).unwrap();
+        let status_changed = old.get_status() != new_info.get_status();
+        let before = std::mem::replace(old, new_info.clone());
+        let minted = new_info.get_credits() - before.get_credits();
+        self.mint(new_info, minted, "Edited");
+        self.contacts.remove(&before);
+        self.contacts.insert(new_info);
+        let target = (
+            Entity::Member,
+            new_info.get_uuid(),
+            new_info.get_name().as_str(),
+        );
+        self.record(target, Some(&before), Some(new_info));
+        if status_changed {
+            self.time_mut()
+                .cancel(|job| job.is_for_member(new_info.get_uuid()));
+            self.schedule_suspension(new_info);
+        }
+        self.watch_invariants(&format!("Update member {}", new_info.get_name()));
         Ok(())
     }
 
     fn exists_member(&self, member: &Member) -> bool {
-        self.members.iter().any(|entry| {
-            let m = entry.1.clone();
-            m == member.clone()
-        })
+        let member = &self.normalized(member);
+        self.members.contains_key(member.get_uuid())
+            || self.contacts.by_email(member.get_email()).is_some()
+            || self.contacts.by_phone_nr(member.get_phone_nr()).is_some()
+    }
+
+    fn check_unique(&self, member: &Member) -> SysResult<()> {
+        if self.members.contains_key(member.get_uuid()) || !self.ids.is_free(member.get_uuid()) {
+            return Err(MemValError::NotUnique(UniqueField::Uuid).into());
+        }
+        self.check_unique_contact(member)
+    }
+
+    fn check_unique_contact(&self, member: &Member) -> SysResult<()> {
+        match self.contacts.collision(&self.normalized(member)) {
+            Some(field) => Err(MemValError::NotUnique(field).into()),
+            None => Ok(()),
+        }
+    }
+
+    fn get_contact_rules(&self) -> &ContactConfig {
+        &self.contact
+    }
+
+    fn generate_id<K: Kind>(&mut self) -> Id<K> {
+        self.ids.generate()
+    }
+
+    fn seed_ids(&mut self, seed: u64) {
+        self.ids.reseed(seed);
+    }
+
+    fn set_id_len(&mut self, len: usize) {
+        self.ids.set_len(len);
+    }
+
+    fn get_id_len(&self) -> usize {
+        self.ids.get_len()
     }
 
     fn get_items(&self) -> Vec<&Item> {
@@ -134,83 +821,151 @@ impl LendingSystem for System {
     fn get_items_for_member(&self, member: &Member) -> Vec<&Item> {
         self.get_items()
             .into_iter()
-            .filter(|item| item.get_owner() == member)
+            .filter(|item| item.get_owner().get_uuid() == member.get_uuid())
+            .collect::<Vec<&Item>>()
+    }
+
+    fn get_bookable_items(&self) -> Vec<&Item> {
+        self.get_items()
+            .into_iter()
+            .filter(|item| match self.members.get(item.get_owner().get_uuid()) {
+                Some(owner) => owner.is_active(),
+                None => false,
+            })
             .collect::<Vec<&Item>>()
     }
 
+    fn get_contracts_for_member(&self, member: &Member) -> Vec<&Contract> {
+        self.contracts.for_member(member.get_uuid())
+    }
+
+    fn get_active_contracts(&self) -> Vec<&Contract> {
+        self.contracts.active()
+    }
+
     fn get_item_for_contract(&self, contract: &Contract) -> Option<&Item> {
-        for (_, item) in self.items.iter() {
-            for c in item.get_history().iter() {
-                if c.get_uuid() == contract.get_uuid() {
-                    return Some(item);
-                }
-            }
-        }
-        None
+        self.contracts
+            .get(contract.get_uuid())
+            .and_then(|stored| self.items.get(stored.get_item()))
     }
 
     fn get_item(&self, item: &Item) -> SysResult<Item> {
-        if !self.items.contains_key(item.get_uuid()) {
-            return Err(SysError::DoesntExist);
-        }
-        Ok(self.items[item.get_uuid()].clone())
+        self.get_item_by_id(item.get_uuid()).cloned()
+    }
+
+    fn get_item_by_id(&self, id: &ItemId) -> SysResult<&Item> {
+        self.items.get(id).ok_or(SysError::DoesntExist)
     }
 
     fn get_contract(&self, contract: &Contract) -> SysResult<Contract> {
-        for item in self.items.values() {
-            if item
-                .get_history()
-                .iter()
-                .any(|c| c.get_uuid() == contract.get_uuid())
-            {
-                return match item.get_history().get(contract) {
-                    Some(c) => Ok(c.clone()),
-                    None => Err(SysError::DoesntExist),
-                };
-            }
+        self.get_contract_by_id(contract.get_uuid()).cloned()
+    }
+
+    fn get_contract_by_id(&self, id: &ContractId) -> SysResult<&Contract> {
+        match self.contracts.get(id) {
+            Some(stored) => Ok(stored.get_contract()),
+            None => Err(SysError::DoesntExist),
         }
-        Err(SysError::DoesntExist)
     }
 
     fn add_item(&mut self, item: Item) -> SysResult<()> {
-        match self.items.insert(item.get_uuid().clone(), item.clone()) {
-            Some(_) => Err(SysError::AlreadyExists),
-            None => {
-                let temp = self.get_member(item.get_owner());
-                match temp {
-                    Ok(mut member) => match member.add_credits(100f64) {
-                        Ok(_) => match self.update_member(item.get_owner(), &member) {
-                            Ok(_) => Ok(()),
-                            Err(err) => Err(err),
-                        },
-                        Err(_) => Err(SysError::CannotUpdate),
-                    },
-                    Err(err) => Err(err),
+        // The item is only listed if its owner can be rewarded for it.
+        self.transaction(|sys| {
+            if sys.items.contains_key(item.get_uuid()) || !sys.ids.claim(item.get_uuid()) {
+                return Err(SysError::AlreadyExists);
+            }
+            sys.journal_item(item.get_uuid());
+            sys.items.insert(item.get_uuid().clone(), item.clone());
+            sys.contracts.sync(item.get_uuid(), Some(&item));
+            sys.index.insert(&item);
+            let target = (Entity::Item, item.get_uuid(), item.get_name().as_str());
+            sys.record(target, None, Some(&item));
+            for contract in item.get_history().iter() {
+                if !sys.ids.claim(contract.get_uuid()) {
+                    return Err(SysError::AlreadyExists);
                 }
+                sys.schedule_contract(item.get_uuid(), contract);
             }
-        }
+            let owner = sys.transfer(item.get_owner().get_uuid(), LISTING_REWARD)?;
+            sys.mint(&owner, LISTING_REWARD, "Listed an item");
+            Ok(())
+        })?;
+        self.watch_invariants(&format!("Add item {}", item.get_name()));
+        Ok(())
     }
 
     fn remove_item(&mut self, item: &Item) -> SysResult<()> {
+        if let Some(stored) = self.items.get(item.get_uuid()) {
+            let active = stored.count_active_contracts();
+            if active > 0 {
+                return Err(SysError::ActiveContracts(active));
+            }
+            let future = stored.count_future_contracts();
+            if future > 0 {
+                return Err(SysError::FutureContracts(future));
+            }
+        }
+        self.journal_item(item.get_uuid());
         match self.items.remove(&item.get_uuid().clone()) {
-            Some(_) => Ok(()),
+            Some(old) => {
+                let target = (Entity::Item, old.get_uuid(), old.get_name().as_str());
+                self.record(target, Some(&old), None);
+                self.index.remove(item.get_uuid());
+                self.contracts.sync(item.get_uuid(), None);
+                self.time_mut()
+                    .cancel(|job| job.is_for_item(item.get_uuid()));
+                self.watch_invariants(&format!("Remove item {}", old.get_name()));
+                Ok(())
+            }
             None => Err(SysError::CannotDelete),
         }
     }
 
     fn update_item(&mut self, info: &Item) -> SysResult<()> {
-        match self.items.get_mut(info.get_uuid()) {
-            Some(_) => {
-                *self.items.get_mut(info.get_uuid()).unwrap() = info.clone();
+        if *info.get_quantity() < info.min_quantity() {
+            return Err(SysError::Quantity(info.min_quantity()));
+        }
+        match self.item_mut(info.get_uuid()) {
+            Some(old) => {
+                let changed = info
+                    .get_history()
+                    .iter()
+                    .filter(|c| match old.get_history().get(c) {
+                        Some(before) => before.to_string() != c.to_string(),
+                        None => true,
+                    })
+                    .cloned()
+                    .collect::<Vec<Contract>>();
+                let before = std::mem::replace(old, info.clone());
+                let target = (Entity::Item, info.get_uuid(), info.get_name().as_str());
+                self.record(target, Some(&before), Some(info));
+                self.index.update(info);
+                self.contracts.sync(info.get_uuid(), Some(info));
+                if !changed.is_empty() {
+                    self.time_mut()
+                        .cancel(|job| changed.iter().any(|c| job.is_for_contract(c.get_uuid())));
+                }
+                for contract in changed.iter() {
+                    self.schedule_contract(info.get_uuid(), contract);
+                }
+                self.watch_invariants(&format!("Update item {}", info.get_name()));
                 Ok(())
             }
             None => Err(SysError::CannotUpdate),
         }
     }
 
+    fn search_items(&self, query: &str) -> Vec<&Item> {
+        self.index
+            .search(query)
+            .iter()
+            .filter_map(|(uuid, _)| self.items.get(uuid))
+            .collect::<Vec<&Item>>()
+    }
+
     fn count_items_for_member(&self, member: &Member) -> usize {
         self.get_items().iter().fold(0, |cnt, item| {
-            if item.get_owner() == member {
+            if item.get_owner().get_uuid() == member.get_uuid() {
                 cnt + 1
             } else {
                 cnt
@@ -219,48 +974,216 @@ impl LendingSystem for System {
     }
 
     fn incr_time(&mut self) -> SysResult<()> {
-        self.day += 1;
-        let cp = self.clone();
         let mut out: SysResult<()> = Ok(());
-        for item in cp.get_items() {
-            let res = match item.get_active_contract(self.day) {
-                Some(con) => {
-                    let owner = self.get_member(con.get_owner());
-                    let res1 = match owner {
-                        Ok(mut o) => match o.add_credits(*item.get_cost_per_day()) {
-                            Ok(_) => match self.update_member(con.get_owner(), &o) {
-                                Err(err) => Err(err),
-                                Ok(_) => Ok(()),
-                            },
-                            Err(_) => Err(SysError::CannotUpdate),
-                        },
-                        Err(_) => Ok(()),
-                    };
-                    let lendee = self.get_member(con.get_lendee());
-                    let res2 = match lendee {
-                        Ok(mut l) => match l.deduce_credits(*item.get_cost_per_day()) {
-                            Ok(_) => match self.update_member(con.get_lendee(), &l) {
-                                Ok(_) => Ok(()),
-                                Err(err) => Err(err),
-                            },
-                            Err(_) => Err(SysError::CannotUpdate),
-                        },
-                        Err(_) => Err(SysError::CannotUpdate),
-                    };
-                    match (res1, res2) {
-                        (Ok(_), Ok(_)) => Ok(()),
-                        _ => Err(SysError::CannotUpdate),
-                    }
-                }
-                None => Ok(()),
-            };
-            out = res
+        let mut charged: Vec<Member> = Vec::new();
+        // Every payout is a transaction of its own, so a lendee who cannot pay
+        // neither pays the owner nor keeps the day from advancing.
+        for job in self.time_mut().incr_day() {
+            if self.dispatch(job, &mut charged).is_err() {
+                out = Err(SysError::CannotUpdate);
+            }
+        }
+
+        let mut warned: Vec<&MemberId> = Vec::new();
+        for member in charged.iter() {
+            let current = self.fresh(member);
+            if *current.get_credits() < LOW_BALANCE_THRESHOLD
+                && !warned.contains(&member.get_uuid())
+            {
+                warned.push(member.get_uuid());
+                self.events.push(Event::LowBalance {
+                    day: self.now(),
+                    member: current,
+                });
+            }
         }
+        self.notify_listeners();
+        self.watch_invariants(&format!("Advance to day {}", self.now()));
         out
     }
 
     fn now(&self) -> usize {
-        self.day
+        self.time.now()
+    }
+
+    fn get_scheduled_jobs(&self) -> Vec<(usize, &Job)> {
+        self.time.pending()
+    }
+
+    fn add_contract(
+        &mut self,
+        item: &Item,
+        contract: Contract,
+        unit: Option<usize>,
+    ) -> SysResult<()> {
+        if self.contracts.get(contract.get_uuid()).is_some()
+            || !self.ids.is_free(contract.get_uuid())
+        {
+            return Err(SysError::AlreadyExists);
+        }
+        let mut updated = self.get_item(item)?;
+        let lendee = self.get_member(contract.get_lendee())?;
+        let owner = self.fresh(updated.get_owner());
+        if let Some(member) = [&lendee, &owner].into_iter().find(|m| !m.is_active()) {
+            return Err(SysError::Restricted(*member.get_status()));
+        }
+        let (start_date, end_date) = (*contract.get_start_date(), *contract.get_end_date());
+        let booked = contract.clone();
+        match unit {
+            Some(u) => updated.add_contract_for_unit(contract, u)?,
+            None => updated.add_contract(contract)?,
+        }
+        self.update_item(&updated)?;
+        self.ids.claim(booked.get_uuid());
+        let target = (
+            Entity::Contract,
+            booked.get_uuid(),
+            item.get_name().as_str(),
+        );
+        self.record(target, None, Some(&booked));
+        self.events.push(Event::BookingRequested {
+            day: self.now(),
+            owner,
+            lendee,
+            item: updated.get_name().clone(),
+            start_date,
+            end_date,
+        });
+        self.notify_listeners();
+        self.watch_invariants(&format!("Book {}", item.get_name()));
+        Ok(())
+    }
+
+    fn get_notifications(&self, member: &Member) -> Vec<Notification> {
+        self.inbox.get_inbox(member).into_iter().cloned().collect()
+    }
+
+    fn login(&mut self, email: &str, password: &str) -> SysResult<Member> {
+        let member = self
+            .get_member_by_email(email)
+            .ok()
+            .filter(|m| m.verify_password(password));
+        match member {
+            Some(m) if *m.get_status() == MemberStatus::Banned => Err(SysError::Banned),
+            Some(m) => {
+                self.session = Some(m.get_uuid().clone());
+                Ok(m.clone())
+            }
+            None => Err(SysError::InvalidCredentials),
+        }
+    }
+
+    fn logout(&mut self) {
+        self.session = None;
+        self.history.clear();
+    }
+
+    fn get_current_user(&self) -> Option<&Member> {
+        match &self.session {
+            Some(uuid) => self.members.get(uuid),
+            None => None,
+        }
+    }
+
+    fn get_audit_entries(
+        &self,
+        entity: Option<Entity>,
+        actor: Option<&MemberId>,
+    ) -> Vec<&AuditEntry> {
+        self.audit.filter(entity, actor)
+    }
+
+    fn get_ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    fn check_invariants(&self) -> Vec<Violation> {
+        check_invariants(self)
+    }
+
+    fn get_broken_invariants(&self) -> &[(String, Violation)] {
+        &self.broken
+    }
+
+    fn begin(&mut self) {
+        let transaction = Transaction::new(
+            self.audit.len(),
+            self.events.len(),
+            self.ledger.len(),
+            self.ids.count_claims(),
+        );
+        self.journal.push(transaction);
+    }
+
+    fn commit(&mut self) -> SysResult<()> {
+        self.finish().map(|_| ()).ok_or(SysError::NoTransaction)
+    }
+
+    fn rollback(&mut self) -> SysResult<()> {
+        let transaction = self.journal.pop().ok_or(SysError::NoTransaction)?;
+        let (audit_len, events_len, ledger_len, claims) = (
+            transaction.get_audit_len(),
+            transaction.get_events_len(),
+            transaction.get_ledger_len(),
+            transaction.get_claims(),
+        );
+        self.revert(transaction.into_changes());
+        self.audit.truncate(audit_len);
+        self.events.truncate(events_len);
+        self.ledger.truncate(ledger_len);
+        self.ids.release_claims(claims);
+        Ok(())
+    }
+
+    fn execute<C: Command>(&mut self, command: &mut C) -> SysResult<()> {
+        self.begin();
+        match command.execute(self) {
+            Ok(_) => {
+                let changes = self.finish().unwrap_or_default();
+                let description = command.describe();
+                self.watch_invariants(&description);
+                self.history.push(Step::new(&description, changes));
+                Ok(())
+            }
+            Err(err) => {
+                self.rollback()?;
+                Err(err)
+            }
+        }
+    }
+
+    fn undo(&mut self) -> SysResult<String> {
+        let step = self.history.pop_undo().ok_or(SysError::NothingToUndo)?;
+        let description = step.get_description().to_owned();
+        let changes = self.revert(step.into_changes());
+        self.history.push_redo(Step::new(&description, changes));
+        self.watch_invariants(&format!("Undo {}", description));
+        Ok(description)
+    }
+
+    fn redo(&mut self) -> SysResult<String> {
+        let step = self.history.pop_redo().ok_or(SysError::NothingToRedo)?;
+        let description = step.get_description().to_owned();
+        let changes = self.revert(step.into_changes());
+        self.history.push_undo(Step::new(&description, changes));
+        self.watch_invariants(&format!("Redo {}", description));
+        Ok(description)
+    }
+
+    fn get_undo_steps(&self) -> Vec<&str> {
+        self.history.undo_steps()
+    }
+
+    fn get_redo_steps(&self) -> Vec<&str> {
+        self.history.redo_steps()
+    }
+
+    fn authorize(&self, action: Action) -> SysResult<()> {
+        match self.get_current_user() {
+            Some(user) if action.is_permitted_for(user) => Ok(()),
+            Some(_) => Err(SysError::PermissionDenied),
+            None => Err(SysError::NotLoggedIn),
+        }
     }
 }
 
@@ -272,28 +1195,28 @@ impl Demo for System {
             Member::new(
                 "Allan".to_owned(),
                 "allan@enigma.com".to_owned(),
-                "0123456789".to_owned(),
+                "0701234567".to_owned(),
                 sys.now(),
             )
             .expect("Should not fail"),
             Member::new(
                 "Tina".to_owned(),
                 "tina@somethingelse.com".to_owned(),
-                "01234543210".to_owned(),
+                "0702345678".to_owned(),
                 sys.now(),
             )
             .expect("Should not fail."),
             Member::new(
                 "Turing".to_owned(),
                 "turing@enigma.com".to_owned(),
-                "9876567890".to_owned(),
+                "0703456789".to_owned(),
                 sys.now(),
             )
             .expect("Should not fail."),
             Member::new(
                 "Jeff".to_owned(),
                 "jeff@bezos.com".to_owned(),
-                "0987654321".to_owned(),
+                "0704567890".to_owned(),
                 sys.now(),
             )
             .expect("Should not fail."),
@@ -307,7 +1230,8 @@ impl Demo for System {
                 members[0].clone(),
                 30f64,
                 sys.now(),
-            ),
+            )
+            .tags(Tags::new(vec!["board", "family"])),
             Item::new(
                 "Siedler".to_owned(),
                 "Another Family Game".to_owned(),
@@ -315,7 +1239,8 @@ impl Demo for System {
                 members[0].clone(),
                 45f64,
                 sys.now(),
-            ),
+            )
+            .tags(Tags::new(vec!["board", "family", "strategy"])),
             Item::new(
                 "T-Rex".to_owned(),
                 "Dinosaur".to_owned(),
@@ -323,7 +1248,8 @@ impl Demo for System {
                 members[2].clone(),
                 10f64,
                 sys.now(),
-            ),
+            )
+            .tags(Tags::new(vec!["dinosaur", "kids"])),
             Item::new(
                 "Hammer".to_owned(),
                 "A useful tool".to_owned(),
@@ -331,50 +1257,45 @@ impl Demo for System {
                 members[1].clone(),
                 150f64,
                 sys.now(),
-            ),
-        ];
-
-        let contracts = vec![
-            Contract::new(
-                items[2].get_owner().clone(),
-                members[1].clone(),
-                sys.now() + 6,
-                6,
-                items[2].get_cost_per_day() * 6f64,
-            ),
-            Contract::new(
-                items[1].get_owner().clone(),
-                members[1].clone(),
-                sys.now() + 12,
-                9,
-                items[1].get_cost_per_day() * 9f64,
-            ),
-            Contract::new(
-                items[3].get_owner().clone(),
-                members[2].clone(),
-                sys.now(),
-                10,
-                items[3].get_cost_per_day() * 10f64,
-            ),
-            Contract::new(
-                items[1].get_owner().clone(),
-                members[1].clone(),
-                sys.now(),
-                5,
-                items[1].get_cost_per_day() * 20f64,
-            ),
+            )
+            .tags(Tags::new(vec!["hand", "carpentry"])),
         ];
 
-        for member in members.iter() {
+        for (idx, member) in members.iter().enumerate() {
             self.add_member(member.clone()).expect("");
             let mut temp = member.clone();
-            temp.add_credits(700f64).expect("");
-            self.update_member(member, &temp).expect("");
+            temp.set_password("password");
+            if idx == 0 {
+                temp = temp.role(Role::Admin);
+            }
+            self.transaction(|sys| {
+                sys.update_member(member, &temp)?;
+                sys.transfer(temp.get_uuid(), 700f64)?;
+                sys.mint(&temp, 700f64, "Demo credits");
+                Ok(())
+            })
+            .expect("");
+        }
+
+        // Lendees need their credits to book, so they are taken from the system.
+        let tina = self.get_member(&members[1]).expect("");
+        let turing = self.get_member(&members[2]).expect("");
+        let bookings = vec![
+            (0, tina.clone(), sys.now() + 6, 6),
+            (1, tina.clone(), sys.now() + 12, 9),
+            (3, turing, sys.now(), 4),
+            (2, tina, sys.now(), 5),
+        ];
+        for (idx, lendee, start_date, len) in bookings {
+            let contract = Contract::new(
+                items[idx].get_owner().clone(),
+                lendee,
+                start_date,
+                len,
+                items[idx].get_cost_per_day() * len as f64,
+            );
+            items[idx].add_contract(contract).expect("");
         }
-        items[0].add_contract(contracts[0].clone()).expect("");
-        items[1].add_contract(contracts[1].clone()).expect("");
-        items[1].add_contract(contracts[2].clone()).expect("");
-        items[2].add_contract(contracts[3].clone()).expect("");
         for item in items.iter() {
             self.add_item(item.clone()).expect("");
         }
@@ -1,9 +1,15 @@
-/// ! This is experimental.
-use crate::types::Model;
+use crate::{
+    errors::SysError,
+    models::id::{ContractId, ItemId, MemberId},
+    types::Model,
+    types::SysResult,
+};
+use std::collections::BTreeMap;
 
 pub trait Listener<T> {
     fn update(&mut self, new: T);
-    fn pull(&self) -> T;
+    /// Returns the last value the listener received.
+    fn pull(&self) -> Option<T>;
 }
 
 pub trait Listenable<V, T>
@@ -15,50 +21,147 @@ where
     fn notify_listeners(&mut self);
 }
 
+/// Work that is due on a specific day.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Time<T>
-where
-    T: Listener<usize> + Model + Clone + PartialEq + Eq,
-{
-    listeners: Vec<T>,
-    day: usize,
+pub enum Job {
+    /// Lifts the suspension of a member.
+    ExpireSuspension {
+        /// The suspended member.
+        member: MemberId,
+    },
+    /// Marks a contract as active on its first day.
+    StartContract {
+        /// The item the contract belongs to.
+        item: ItemId,
+        /// The contract.
+        contract: ContractId,
+    },
+    /// Transfers the credits for one day of lending from the lendee to the owner.
+    Payout {
+        /// The item the contract belongs to.
+        item: ItemId,
+        /// The contract.
+        contract: ContractId,
+    },
+    /// Marks a contract as finished and reminds the lendee to return the item.
+    FinishContract {
+        /// The item the contract belongs to.
+        item: ItemId,
+        /// The contract.
+        contract: ContractId,
+    },
+    /// Reminds owner and lendee that a contract starts on the next day.
+    RemindStart {
+        /// The item the contract belongs to.
+        item: ItemId,
+        /// The contract.
+        contract: ContractId,
+    },
 }
 
-impl<T> Listenable<usize, T> for Time<T>
-where
-    T: Listener<usize> + Model + Clone + PartialEq + Eq,
-{
-    fn add_listener(&mut self, listener: T) {
-        self.listeners.push(listener);
+impl Job {
+    /// Jobs due on the same day are dispatched by ascending priority.
+    pub fn priority(&self) -> usize {
+        match self {
+            Job::ExpireSuspension { .. } => 0,
+            Job::StartContract { .. } => 1,
+            Job::Payout { .. } => 2,
+            Job::FinishContract { .. } => 3,
+            Job::RemindStart { .. } => 4,
+        }
     }
 
-    fn pop_listener(&mut self, listener: T) -> Option<T> {
-        match self.index_of(&listener) {
-            Some(i) => Some(self.listeners.remove(i)),
-            None => None,
+    /// Checks if the job concerns the item.
+    pub fn is_for_item(&self, uuid: &ItemId) -> bool {
+        match self {
+            Job::ExpireSuspension { .. } => false,
+            Job::StartContract { item, .. }
+            | Job::Payout { item, .. }
+            | Job::FinishContract { item, .. }
+            | Job::RemindStart { item, .. } => item == uuid,
         }
     }
 
-    fn notify_listeners(&mut self) {
-        for listener in self.listeners.iter_mut() {
-            listener.update(self.day)
+    /// Checks if the job concerns the contract.
+    pub fn is_for_contract(&self, uuid: &ContractId) -> bool {
+        match self {
+            Job::ExpireSuspension { .. } => false,
+            Job::StartContract { contract, .. }
+            | Job::Payout { contract, .. }
+            | Job::FinishContract { contract, .. }
+            | Job::RemindStart { contract, .. } => contract == uuid,
+        }
+    }
+
+    /// Checks if the job concerns the member.
+    pub fn is_for_member(&self, uuid: &MemberId) -> bool {
+        match self {
+            Job::ExpireSuspension { member } => member == uuid,
+            _ => false,
         }
     }
 }
 
-impl<T> Time<T>
-where
-    T: Listener<usize> + Model + Clone + PartialEq + Eq,
-{
-    pub fn new(listeners: Vec<T>, day: usize) -> Self {
-        Self { listeners, day }
+/// The clock of the system.
+///
+/// Keeps track of the current day and of the jobs scheduled for the following days.
+/// Advancing the time returns the due jobs ordered by day, then by priority and finally
+/// in the order they were scheduled, so the same schedule always has the same outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Time {
+    day: usize,
+    seq: usize,
+    jobs: BTreeMap<(usize, usize, usize), Job>,
+}
+
+impl Time {
+    /// Creates a new clock starting at a day without any jobs.
+    pub fn new(day: usize) -> Self {
+        Self {
+            day,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the current day.
+    pub fn now(&self) -> usize {
+        self.day
+    }
+
+    /// Schedules a job for a future day.
+    pub fn schedule(&mut self, day: usize, job: Job) -> SysResult<()> {
+        if day <= self.day {
+            return Err(SysError::InvalidInput);
+        }
+        self.seq += 1;
+        self.jobs.insert((day, job.priority(), self.seq), job);
+        Ok(())
+    }
+
+    /// Removes all jobs matching the predicate and returns how many were removed.
+    pub fn cancel<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&Job) -> bool,
+    {
+        let before = self.jobs.len();
+        self.jobs.retain(|_, job| !predicate(job));
+        before - self.jobs.len()
     }
 
-    fn index_of(&self, val: &T) -> Option<usize> {
-        self.listeners.iter().position(|v| v == val)
+    /// Returns all scheduled jobs together with the day they are due.
+    pub fn pending(&self) -> Vec<(usize, &Job)> {
+        self.jobs
+            .iter()
+            .map(|((day, _, _), job)| (*day, job))
+            .collect::<Vec<(usize, &Job)>>()
     }
 
-    pub fn incr_day(&mut self) {
+    /// Advances the clock by one day and returns the jobs that are due.
+    pub fn incr_day(&mut self) -> Vec<Job> {
         self.day += 1;
+        let later = self.jobs.split_off(&(self.day + 1, 0, 0));
+        std::mem::replace(&mut self.jobs, later)
+            .into_values()
+            .collect::<Vec<Job>>()
     }
 }
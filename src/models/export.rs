@@ -0,0 +1,265 @@
+use crate::{
+    errors::SysError,
+    models::domain::{contract::Contract, item::Item, member::Member, report::ReportSection},
+    types::Data,
+};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The directory exported files are written to.
+pub const EXPORT_DIR: &str = "exports";
+
+/// The file formats tables can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma separated values.
+    Csv,
+    /// Markdown tables.
+    Markdown,
+    /// A standalone html page.
+    Html,
+}
+
+impl ExportFormat {
+    /// Returns the file extension of the format.
+    pub fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = SysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+/// A titled table that can be exported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet {
+    title: String,
+    head: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Sheet {
+    /// Creates a new sheet.
+    pub fn new(title: &str, head: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            title: title.to_owned(),
+            head,
+            rows,
+        }
+    }
+
+    /// Creates a sheet with the same columns the console shows for the models.
+    pub fn from_models<T: Exportable>(title: &str, models: Vec<&T>) -> Self {
+        let rows = models.iter().map(|model| model.to_cells()).collect();
+        Self::new(title, T::head(), rows)
+    }
+
+    /// Renders the sheets in the format.
+    pub fn render(sheets: &[Sheet], format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => render_csv(sheets),
+            ExportFormat::Markdown => render_markdown(sheets),
+            ExportFormat::Html => render_html(sheets),
+        }
+    }
+
+    /// Writes the sheets into a file named after the title of the first sheet.
+    pub fn export(sheets: &[Sheet], format: ExportFormat, dir: &Path) -> Result<PathBuf, SysError> {
+        let name = match sheets.first() {
+            Some(sheet) => file_name(&sheet.title),
+            None => "export".to_owned(),
+        };
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        fs::create_dir_all(dir).map_err(|_| SysError::CannotExport)?;
+        fs::write(&path, Self::render(sheets, format)).map_err(|_| SysError::CannotExport)?;
+        Ok(path)
+    }
+}
+
+/// A model that can be exported.
+///
+/// Members referenced by other models are only exported by their name and id.
+pub trait Exportable: Data {
+    /// Returns the cells of the model, by default the ones the console shows.
+    fn to_cells(&self) -> Vec<String> {
+        row_cells(self)
+    }
+}
+
+impl Exportable for Member {}
+
+impl Exportable for Item {
+    fn to_cells(&self) -> Vec<String> {
+        let mut cells = row_cells(self);
+        let contracts = self
+            .get_history()
+            .iter()
+            .map(|c| c.get_uuid().to_string())
+            .collect::<Vec<String>>();
+        set_cell::<Self>(&mut cells, "owner", member_cell(self.get_owner()));
+        set_cell::<Self>(&mut cells, "history", contracts.join(" "));
+        cells
+    }
+}
+
+impl Exportable for Contract {
+    fn to_cells(&self) -> Vec<String> {
+        let mut cells = row_cells(self);
+        set_cell::<Self>(&mut cells, "owner", member_cell(self.get_owner()));
+        set_cell::<Self>(&mut cells, "lendee", member_cell(self.get_lendee()));
+        cells
+    }
+}
+
+fn row_cells<T: Data>(model: &T) -> Vec<String> {
+    model
+        .to_row()
+        .iter()
+        .map(|cell| cell.get_content())
+        .collect::<Vec<String>>()
+}
+
+fn member_cell(member: &Member) -> String {
+    format!("{} ({})", member.get_name(), member.get_uuid())
+}
+
+fn set_cell<T: Data>(cells: &mut [String], column: &str, value: String) {
+    if let Some(idx) = T::head().iter().position(|head| head == column) {
+        cells[idx] = value;
+    }
+}
+
+impl From<ReportSection> for Sheet {
+    fn from(section: ReportSection) -> Self {
+        Self::new(
+            section.get_title(),
+            section.get_head().clone(),
+            section.get_rows().clone(),
+        )
+    }
+}
+
+fn file_name(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '-',
+        })
+        .collect()
+}
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+fn render_csv(sheets: &[Sheet]) -> String {
+    let mut out = String::new();
+    for (i, sheet) in sheets.iter().enumerate() {
+        // Several sheets are separated by an empty line and a line with the title.
+        if sheets.len() > 1 {
+            if i > 0 {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "{}", csv_field(&sheet.title));
+        }
+        for line in std::iter::once(&sheet.head).chain(sheet.rows.iter()) {
+            let fields = line.iter().map(|f| csv_field(f)).collect::<Vec<String>>();
+            let _ = writeln!(out, "{}", fields.join(","));
+        }
+    }
+    out
+}
+
+fn markdown_field(field: &str) -> String {
+    field.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(sheets: &[Sheet]) -> String {
+    let mut out = String::new();
+    for sheet in sheets {
+        let _ = writeln!(out, "## {}\n", sheet.title);
+        let head = sheet
+            .head
+            .iter()
+            .map(|h| markdown_field(h))
+            .collect::<Vec<String>>();
+        let _ = writeln!(out, "| {} |", head.join(" | "));
+        let _ = writeln!(out, "|{}", "-|".repeat(head.len()));
+        for row in sheet.rows.iter() {
+            let fields = row
+                .iter()
+                .map(|f| markdown_field(f))
+                .collect::<Vec<String>>();
+            let _ = writeln!(out, "| {} |", fields.join(" | "));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn html_field(field: &str) -> String {
+    field
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(sheets: &[Sheet]) -> String {
+    let title = match sheets.first() {
+        Some(sheet) => html_field(&sheet.title),
+        None => "Export".to_owned(),
+    };
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+    );
+    let _ = writeln!(out, "<title>{}</title>", title);
+    let _ = writeln!(
+        out,
+        "<style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #999; padding: 4px 8px; }}</style>"
+    );
+    let _ = writeln!(out, "</head>\n<body>");
+    for sheet in sheets {
+        let _ = writeln!(out, "<h2>{}</h2>\n<table>", html_field(&sheet.title));
+        let head = sheet
+            .head
+            .iter()
+            .map(|h| format!("<th>{}</th>", html_field(h)))
+            .collect::<String>();
+        let _ = writeln!(out, "<tr>{}</tr>", head);
+        for row in sheet.rows.iter() {
+            let fields = row
+                .iter()
+                .map(|f| format!("<td>{}</td>", html_field(f)))
+                .collect::<String>();
+            let _ = writeln!(out, "<tr>{}</tr>", fields);
+        }
+        let _ = writeln!(out, "</table>");
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
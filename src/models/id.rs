@@ -0,0 +1,258 @@
+use super::uuid::Uuid;
+use crate::errors::SysError;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// The length of generated ids, unless a generator is configured for longer ones.
+pub const MIN_ID_LEN: usize = 6;
+/// The longest ids a generator can be configured for.
+pub const MAX_ID_LEN: usize = 32;
+
+/// The kind of entity an id belongs to.
+pub trait Kind {
+    /// The letter ids of this kind start with.
+    const PREFIX: char;
+}
+
+/// Members are identified by a `MemberId`.
+#[derive(Debug)]
+pub struct MemberKind;
+
+/// Items are identified by an `ItemId`.
+#[derive(Debug)]
+pub struct ItemKind;
+
+/// Contracts are identified by a `ContractId`.
+#[derive(Debug)]
+pub struct ContractKind;
+
+impl Kind for MemberKind {
+    const PREFIX: char = 'M';
+}
+
+impl Kind for ItemKind {
+    const PREFIX: char = 'I';
+}
+
+impl Kind for ContractKind {
+    const PREFIX: char = 'C';
+}
+
+/// The id of a member, like `M-ab12cd`.
+pub type MemberId = Id<MemberKind>;
+/// The id of an item, like `I-ab12cd`.
+pub type ItemId = Id<ItemKind>;
+/// The id of a contract, like `C-ab12cd`.
+pub type ContractId = Id<ContractKind>;
+
+/// The id of an entity, displayed as the letter of its kind followed by its uuid.
+///
+/// Ids of different kinds are different types, so an item can never be looked up
+/// with the id of a member.
+#[derive(Debug)]
+pub struct Id<K: Kind> {
+    uuid: Uuid,
+    kind: PhantomData<K>,
+}
+
+impl<K: Kind> Id<K> {
+    /// Creates a new random id.
+    pub fn new() -> Self {
+        Self::from_uuid(Uuid::new())
+    }
+
+    /// Creates the id of the uuid.
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            kind: PhantomData,
+        }
+    }
+
+    /// Returns the uuid without the kind.
+    pub fn get_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Returns the value of the uuid.
+    pub fn get_value(&self) -> &String {
+        self.uuid.get_value()
+    }
+
+    /// Returns the length of the uuid.
+    pub fn get_len(&self) -> &usize {
+        self.uuid.get_len()
+    }
+}
+
+impl<K: Kind> Default for Id<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Kind> Clone for Id<K> {
+    fn clone(&self) -> Self {
+        Self::from_uuid(self.uuid.clone())
+    }
+}
+
+impl<K: Kind> PartialEq for Id<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+    }
+}
+
+impl<K: Kind> Eq for Id<K> {}
+
+impl<K: Kind> Hash for Id<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uuid.hash(state);
+    }
+}
+
+impl<K: Kind> Display for Id<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", K::PREFIX, self.uuid.get_value())
+    }
+}
+
+impl<K: Kind> FromStr for Id<K> {
+    type Err = SysError;
+
+    /// Parses an id of this kind, the letter of the kind is not case sensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, value) = s.trim().split_once('-').ok_or(SysError::InvalidInput)?;
+        let mut letters = prefix.chars();
+        let matches = letters
+            .next()
+            .is_some_and(|c| c.eq_ignore_ascii_case(&K::PREFIX))
+            && letters.next().is_none();
+        match matches && !value.is_empty() && value.chars().all(|c| c.is_alphanumeric()) {
+            true => Ok(Self::from_uuid(Uuid::with_value(value))),
+            false => Err(SysError::InvalidInput),
+        }
+    }
+}
+
+/// Creates the ids of a system.
+///
+/// No id is handed out twice, and neither is an id that an entity of the system has
+/// claimed. Seeded generators hand out the same ids in the same order.
+///
+/// Entities can also be created with ids of their own, so the system claims the id of
+/// every entity it inserts. An id can only be claimed once, unless the claim is released
+/// because the insert has been rolled back.
+#[derive(Debug, Clone)]
+pub struct IdGenerator {
+    len: usize,
+    rng: StdRng,
+    /// Every id that has been generated or claimed.
+    taken: HashSet<String>,
+    /// Generated ids that have not been claimed yet.
+    issued: HashSet<String>,
+    /// The claimed ids, the oldest first, together with whether they had been generated.
+    claims: Vec<(String, bool)>,
+}
+
+impl IdGenerator {
+    /// Creates a generator of random ids.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates a generator whose ids only depend on the seed.
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        Self {
+            len: MIN_ID_LEN,
+            rng,
+            taken: HashSet::new(),
+            issued: HashSet::new(),
+            claims: Vec::new(),
+        }
+    }
+
+    /// Makes the generator create ids of the length, which is kept between
+    /// `MIN_ID_LEN` and `MAX_ID_LEN`.
+    pub fn len(mut self, len: usize) -> Self {
+        self.set_len(len);
+        self
+    }
+
+    /// Makes the ids from now on this long, the length is kept between `MIN_ID_LEN`
+    /// and `MAX_ID_LEN`.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len.clamp(MIN_ID_LEN, MAX_ID_LEN);
+    }
+
+    /// Makes the ids from now on only depend on the seed, the claimed ids stay claimed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns the length of the generated ids.
+    pub fn get_len(&self) -> usize {
+        self.len
+    }
+
+    /// Creates an id that has neither been generated nor claimed before.
+    pub fn generate<K: Kind>(&mut self) -> Id<K> {
+        loop {
+            let uuid = Uuid::from_rng(&mut self.rng, self.len);
+            if self.taken.insert(uuid.get_value().clone()) {
+                self.issued.insert(uuid.get_value().clone());
+                return Id::from_uuid(uuid);
+            }
+        }
+    }
+
+    /// Checks if an entity can claim the id, which are ids the generator created and
+    /// nobody claimed yet and ids that are not taken at all.
+    pub fn is_free<K: Kind>(&self, id: &Id<K>) -> bool {
+        self.issued.contains(id.get_value()) || !self.taken.contains(id.get_value())
+    }
+
+    /// Claims the id for an entity, which fails if the id is not free.
+    pub fn claim<K: Kind>(&mut self, id: &Id<K>) -> bool {
+        if !self.is_free(id) {
+            return false;
+        }
+        let value = id.get_value().clone();
+        let generated = self.issued.remove(&value);
+        self.taken.insert(value.clone());
+        self.claims.push((value, generated));
+        true
+    }
+
+    /// Returns the number of claimed ids.
+    pub fn count_claims(&self) -> usize {
+        self.claims.len()
+    }
+
+    /// Releases the ids claimed after the first `count` claims, generated ids can be
+    /// claimed again but are never handed out again.
+    pub fn release_claims(&mut self, count: usize) {
+        for (value, generated) in self.claims.drain(count.min(self.claims.len())..) {
+            match generated {
+                true => self.issued.insert(value),
+                false => self.taken.remove(&value),
+            };
+        }
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
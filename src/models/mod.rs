@@ -2,6 +2,10 @@
 pub mod date_wrapper;
 /// Contains all domain models.
 pub mod domain;
+/// Exporting tables to files.
+pub mod export;
+/// Typed ids of members, items and contracts.
+pub mod id;
 /// Custom uuid model.
 pub mod uuid;
 /// `Vec<T>` wrapper.
@@ -30,18 +30,17 @@ pub struct Uuid {
 impl Uuid {
     /// Creates a new uuid.
     pub fn new() -> Self {
-        let len: usize = 6;
-        let value: String = thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(len)
-            .map(char::from)
-            .collect();
-        Uuid { value, len }
+        Self::with_len(6)
     }
 
     /// Creates a new uuid with a specific length.
     pub fn with_len(len: usize) -> Self {
-        let value: String = thread_rng()
+        Self::from_rng(&mut thread_rng(), len)
+    }
+
+    /// Creates a new uuid with a specific length drawn from the random number generator.
+    pub fn from_rng<R: Rng>(rng: &mut R, len: usize) -> Self {
+        let value: String = rng
             .sample_iter(&Alphanumeric)
             .take(len)
             .map(char::from)
@@ -49,6 +48,14 @@ impl Uuid {
         Uuid { value, len }
     }
 
+    /// Creates a uuid with a known value.
+    pub fn with_value(value: &str) -> Self {
+        Uuid {
+            len: value.len(),
+            value: value.to_owned(),
+        }
+    }
+
     /// Creates a new uuid with no value.
     pub fn empty() -> Self {
         Uuid {
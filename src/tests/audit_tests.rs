@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod audit_test {
+    use crate::models::domain::{
+        audit::{diff, AuditLog, Entity, Operation},
+        auth::Role,
+        contract::Contract,
+        item::{Category, Item},
+        member::Member,
+        system::{LendingSystem, System},
+    };
+    use std::collections::HashMap;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// Allan is an admin and logged in, Allan's Monopoly is listed.
+    fn setup() -> (System, Member, Member, Item) {
+        let mut allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        allan.set_password("secret");
+        let allan = allan.role(Role::Admin).build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(60f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        system.login("allan@enigma.com", "secret").expect("");
+        system.add_item(item.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = map(&[("name", "Allan"), ("credits", "100"), ("history", "[]")]);
+        let after = map(&[("name", "Allan"), ("credits", "90"), ("history", "[x]")]);
+
+        let changes = diff(Some(&before), Some(&after));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].get_field(), "credits");
+        assert_eq!(changes[0].to_string(), "credits: 100 -> 90");
+
+        let created = diff(None, Some(&after));
+        let fields = created
+            .iter()
+            .map(|c| c.get_field().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(fields, vec!["credits", "name"]);
+        assert!(created.iter().all(|c| c.get_before().is_none()));
+    }
+
+    #[test]
+    fn test_password_change_stores_no_secrets() {
+        let (mut system, _, bob, _) = setup();
+        let old_bob = system.get_member(&bob).expect("");
+        let mut new_bob = old_bob.clone().name("Robert".to_owned()).build();
+        new_bob.set_password("hunter2");
+        system.update_member(&old_bob, &new_bob).expect("");
+        let allan = system.get_members()[0].clone();
+        let secrets = [
+            new_bob.get_salt(),
+            new_bob.get_password_hash(),
+            allan.get_salt(),
+            allan.get_password_hash(),
+        ];
+
+        let entries = system.get_audit_entries(None, None);
+        let last = entries.last().unwrap();
+        let changes = last
+            .get_changes()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(changes, vec!["name: Bob -> Robert", "password changed"]);
+        for change in entries.iter().flat_map(|e| e.get_changes()) {
+            assert!(!["salt", "password_hash"].contains(&change.get_field().as_str()));
+            let shown = change.to_string();
+            assert!(secrets.iter().all(|s| !shown.contains(s.as_str())));
+        }
+    }
+
+    #[test]
+    fn test_password_change_is_recorded() {
+        let (mut system, _, bob, _) = setup();
+        let bob = system.get_member(&bob).expect("");
+        let mut new_bob = bob.clone();
+        new_bob.set_password("hunter2");
+        system.update_member(&bob, &new_bob).expect("");
+
+        let entries = system.get_audit_entries(Some(Entity::Member), None);
+        let last = entries.last().unwrap();
+        assert_eq!(*last.get_operation(), Operation::Update);
+        assert_eq!(last.get_changes().len(), 1);
+        let change = &last.get_changes()[0];
+        assert_eq!(change.get_field(), "password");
+        assert_eq!((change.get_before(), change.get_after()), (&None, &None));
+        assert_eq!(change.to_string(), "password changed");
+    }
+
+    #[test]
+    fn test_unchanged_update_is_recorded() {
+        let mut log = AuditLog::new();
+        let member = Member::default();
+        let info = map(&[("name", "Allan")]);
+        log.record(
+            None,
+            0,
+            (Entity::Member, member.get_uuid().to_string(), "Allan"),
+            Some(&info),
+            Some(&info),
+            &[],
+        );
+        assert_eq!(log.len(), 1);
+        assert!(log.filter(None, None)[0].get_changes().is_empty());
+    }
+
+    #[test]
+    fn test_system_records_mutations() {
+        let (mut system, allan, bob, item) = setup();
+        let created = system.get_audit_entries(Some(Entity::Member), None);
+        assert_eq!(created.len(), 3);
+        assert_eq!(*created[0].get_operation(), Operation::Create);
+        assert_eq!(created[0].get_actor_name(), "System");
+        // Listing an item rewards the owner, which the system does on its own.
+        assert_eq!(*created[2].get_operation(), Operation::Update);
+        assert_eq!(created[2].get_actor(), &None);
+        assert_eq!(
+            created[2].get_changes()[0].to_string(),
+            "credits: 100 -> 200"
+        );
+
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 2, 20f64);
+        system.add_contract(&item, contract, None).expect("");
+        let contracts = system.get_audit_entries(Some(Entity::Contract), None);
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].get_entity_name(), "Monopoly");
+
+        let bob = system.get_member(&bob).expect("");
+        let new_bob = bob.clone().name("Robert".to_owned()).build();
+        system.update_member(&bob, &new_bob).expect("");
+        let entries = system.get_audit_entries(Some(Entity::Member), None);
+        let last = entries.last().unwrap();
+        assert_eq!(*last.get_operation(), Operation::Update);
+        assert_eq!(last.get_changes().len(), 1);
+        assert_eq!(last.get_changes()[0].to_string(), "name: Bob -> Robert");
+
+        let chess = item.clone().uuid(system.generate_id()).build();
+        system.add_item(chess.clone()).expect("");
+        system.remove_item(&chess).expect("");
+        let items = system.get_audit_entries(Some(Entity::Item), None);
+        assert_eq!(items.len(), 4);
+        // Booking updates the item, the contract itself has its own entry.
+        assert_eq!(*items[1].get_operation(), Operation::Update);
+        assert!(items[1].get_changes().is_empty());
+        assert_eq!(*items[3].get_operation(), Operation::Delete);
+    }
+
+    #[test]
+    fn test_filter_by_actor() {
+        let (mut system, allan, bob, _) = setup();
+        system.logout();
+        let removed = system.get_member(&bob).expect("");
+        system.remove_member(&removed).expect("");
+
+        let by_allan = system.get_audit_entries(None, Some(allan.get_uuid()));
+        assert_eq!(by_allan.len(), 1);
+        assert!(by_allan.iter().all(|e| e.get_actor_name() == "Allan"));
+        let deleted = system.get_audit_entries(Some(Entity::Member), None);
+        assert_eq!(*deleted.last().unwrap().get_operation(), Operation::Delete);
+        assert_eq!(deleted.last().unwrap().get_actor_name(), "System");
+    }
+
+    #[test]
+    fn test_scheduled_changes_are_recorded_by_the_system() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 2, 20f64);
+        system.add_contract(&item, contract, None).expect("");
+        let recorded = system.get_audit_entries(None, None).len();
+        for _ in 0..3 {
+            system.incr_time().expect("");
+        }
+
+        let entries = system.get_audit_entries(None, None)[recorded..].to_vec();
+        assert!(entries.iter().all(|e| e.get_actor_name() == "System"));
+        let changes = entries
+            .iter()
+            .map(|e| (*e.get_entity(), e.get_changes()[0].to_string()))
+            .collect::<Vec<(Entity, String)>>();
+        assert_eq!(
+            changes,
+            vec![
+                (Entity::Contract, "status: Future -> Active".to_owned()),
+                (Entity::Member, "credits: 60 -> 50".to_owned()),
+                (Entity::Member, "credits: 200 -> 210".to_owned()),
+                (Entity::Contract, "status: Active -> Finished".to_owned()),
+            ]
+        );
+    }
+}
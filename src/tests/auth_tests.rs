@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod auth_test {
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            auth::{hash_password, Action, Role},
+            contract::Contract,
+            member::Member,
+            system::{LendingSystem, System},
+        },
+    };
+
+    fn setup() -> (System, Member, Member) {
+        let mut allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        allan.set_password("secret");
+        let allan = allan.role(Role::Admin);
+
+        let mut bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "46291328475".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        bob.set_password("hunter2");
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        (system, allan, bob)
+    }
+
+    #[test]
+    fn test_password_is_salted() {
+        let mut allan = Member::default();
+        let mut bob = Member::default();
+        allan.set_password("password");
+        bob.set_password("password");
+
+        assert_ne!(allan.get_password_hash(), "password");
+        assert_ne!(allan.get_password_hash(), bob.get_password_hash());
+        assert!(allan.verify_password("password"));
+        assert!(!allan.verify_password("Password"));
+    }
+
+    #[test]
+    fn test_password_hash_is_derived_key() {
+        let hash = hash_password("salt", "password");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_password("salt", "password"));
+        assert_ne!(hash, hash_password("pepper", "password"));
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!("Admin".parse::<Role>(), Ok(Role::Admin));
+        assert_eq!("member".parse::<Role>(), Ok(Role::Member));
+        assert_eq!("root".parse::<Role>(), Err(SysError::InvalidInput));
+    }
+
+    #[test]
+    fn test_no_password_cannot_login() {
+        let member = Member::default();
+        assert!(!member.verify_password(""));
+    }
+
+    #[test]
+    fn test_login() {
+        let (mut system, _, bob) = setup();
+        assert!(system.get_current_user().is_none());
+
+        let r1 = system.login("bob@gmail.com", "wrong");
+        assert_eq!(r1.err(), Some(SysError::InvalidCredentials));
+
+        let r2 = system.login("bob@gmail.com", "hunter2");
+        assert!(r2.is_ok());
+        assert_eq!(
+            system.get_current_user().unwrap().get_uuid(),
+            bob.get_uuid()
+        );
+
+        system.logout();
+        assert!(system.get_current_user().is_none());
+    }
+
+    #[test]
+    fn test_member_permissions() {
+        let (mut system, allan, bob) = setup();
+        assert_eq!(
+            system.authorize(Action::EditMember(&bob)),
+            Err(SysError::NotLoggedIn)
+        );
+
+        system.login("bob@gmail.com", "hunter2").expect("");
+        assert_eq!(system.authorize(Action::EditMember(&bob)), Ok(()));
+        assert_eq!(system.authorize(Action::ManageItems(&bob)), Ok(()));
+        assert_eq!(system.authorize(Action::CreateContract(&bob)), Ok(()));
+        assert_eq!(
+            system.authorize(Action::EditMember(&allan)),
+            Err(SysError::PermissionDenied)
+        );
+        assert_eq!(
+            system.authorize(Action::ManageItems(&allan)),
+            Err(SysError::PermissionDenied)
+        );
+        assert_eq!(
+            system.authorize(Action::CreateContract(&allan)),
+            Err(SysError::PermissionDenied)
+        );
+        assert_eq!(
+            system.authorize(Action::CreateMember),
+            Err(SysError::PermissionDenied)
+        );
+
+        let contract = Contract::default()
+            .owner(bob.clone())
+            .lendee(allan.clone())
+            .build();
+        assert_eq!(
+            system.authorize(Action::EditContract(&contract)),
+            Err(SysError::PermissionDenied)
+        );
+    }
+
+    #[test]
+    fn test_admin_permissions() {
+        let (mut system, _, bob) = setup();
+        system.login("allan@enigma.com", "secret").expect("");
+        assert_eq!(system.authorize(Action::EditMember(&bob)), Ok(()));
+        assert_eq!(system.authorize(Action::DeleteMember(&bob)), Ok(()));
+        assert_eq!(system.authorize(Action::ManageItems(&bob)), Ok(()));
+        assert_eq!(system.authorize(Action::CreateMember), Ok(()));
+        assert_eq!(system.authorize(Action::AdvanceTime), Ok(()));
+    }
+}
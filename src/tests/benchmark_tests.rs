@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod benchmark_test {
+    use crate::{
+        controllers::{
+            app::Page, member_controller::MemberController,
+            simulator_controller::SimulatorController,
+        },
+        models::{
+            domain::{
+                auth::Role,
+                community::{CommunityConfig, CommunityStats},
+                item::{Category, Item},
+                member::{Member, MemberStatus},
+                notification::Notification,
+                simulation::{DaySummary, StopCondition},
+                system::{LendingSystem, System},
+            },
+            export::ExportFormat,
+        },
+        types::{ValResult, View},
+        views::{
+            member_view::{MemberMenuOption, MemberView},
+            simulator_view::{SimulatorOption, SimulatorView},
+        },
+    };
+    use std::{
+        cell::RefCell,
+        collections::VecDeque,
+        time::{Duration, Instant},
+    };
+
+    const MEMBERS: usize = 10_000;
+    const ITEMS: usize = 100_000;
+    const ROUNDS: u32 = 10;
+
+    /// A view that picks the menu options of a script and shows nothing.
+    #[derive(Default)]
+    struct ScriptedView {
+        member_options: RefCell<VecDeque<MemberMenuOption>>,
+        simulator_options: RefCell<VecDeque<SimulatorOption>>,
+    }
+
+    impl View for ScriptedView {}
+
+    impl MemberView for ScriptedView {
+        fn member_menu(&self) -> MemberMenuOption {
+            let next = self.member_options.borrow_mut().pop_front();
+            next.unwrap_or(MemberMenuOption::Back)
+        }
+        fn display_member_verbose(&self, _: &Member, _: Vec<&Item>) {}
+        fn display_member_simple(&self, _: &Member, _: usize) {}
+        fn display_all_simple(&self, _: Vec<(&Member, usize)>) {}
+        fn display_all_verbose(&self, _: Vec<(&Member, Vec<&Item>)>) {}
+        fn get_member_info(&self, _: &dyn Fn(Member) -> ValResult<Member>) -> Option<Member> {
+            None
+        }
+        fn edit_member_info(
+            &self,
+            member: &Member,
+            validate: &dyn Fn(Member) -> ValResult<Member>,
+        ) -> Option<Member> {
+            let name = match member.get_name().strip_suffix('*') {
+                Some(name) => name.to_owned(),
+                None => format!("{}*", member.get_name()),
+            };
+            validate(member.clone().name(name).build()).ok()
+        }
+        fn display_inbox(&self, _: &Member, _: Vec<Notification>) {}
+        fn get_password(&self) -> String {
+            String::new()
+        }
+        fn get_moderation(&self, _: usize) -> Option<(MemberStatus, String)> {
+            None
+        }
+        fn get_export_format(&self) -> Option<ExportFormat> {
+            None
+        }
+        fn select_member<'a>(&'a self, members: Vec<&'a Member>) -> Option<&'a Member> {
+            members.into_iter().min_by_key(|m| m.get_uuid().to_string())
+        }
+        fn wait(&self, _: &str) {}
+    }
+
+    impl SimulatorView for ScriptedView {
+        fn simulator_menu(&self) -> SimulatorOption {
+            let next = self.simulator_options.borrow_mut().pop_front();
+            next.unwrap_or(SimulatorOption::Back)
+        }
+        fn get_days(&self) -> Option<usize> {
+            None
+        }
+        fn get_day(&self, _: usize) -> Option<usize> {
+            None
+        }
+        fn get_stop_condition(&self, _: Vec<&Member>) -> Option<StopCondition> {
+            None
+        }
+        fn display_summary(&self, _: &[DaySummary]) {}
+        fn get_community_config(&self) -> Option<CommunityConfig> {
+            None
+        }
+        fn display_community_stats(&self, _: &CommunityStats) {}
+        fn wait(&self, _: &str) {}
+    }
+
+    fn populate() -> System {
+        let mut system = System::new();
+        let mut owners: Vec<Member> = Vec::new();
+        for n in 0..MEMBERS {
+            let member = Member::default()
+                .uuid(system.generate_id())
+                .name(format!("Member {}", n))
+                .email(format!("member{}@lending.com", n))
+                .phone_nr(format!("0123{:06}", n))
+                .credits(100f64)
+                .build();
+            system.add_member(member.clone()).expect("Should not fail");
+            owners.push(member);
+        }
+        let mut admin = owners[0].clone().role(Role::Admin).build();
+        admin.set_password("secret");
+        system.update_member(&owners[0], &admin).expect("");
+        system.login("member0@lending.com", "secret").expect("");
+        for n in 0..ITEMS {
+            let item = Item::new(
+                format!("Item {}", n),
+                "Benchmark item".to_owned(),
+                Category::Other,
+                owners[n % MEMBERS].clone(),
+                10f64,
+                0,
+            )
+            .uuid(system.generate_id())
+            .build();
+            system.add_item(item).expect("Should not fail");
+        }
+        system
+    }
+
+    /// Selects a member and edits it on the members page.
+    fn visit_members(system: &mut System) {
+        let view = ScriptedView::default();
+        view.member_options.borrow_mut().extend([
+            MemberMenuOption::DisplayMemberSimple,
+            MemberMenuOption::EditMember,
+        ]);
+        MemberController::new(system, view).show();
+    }
+
+    /// Advances a day on the simulator page.
+    fn visit_simulator(system: &mut System) {
+        let view = ScriptedView::default();
+        view.simulator_options
+            .borrow_mut()
+            .push_back(SimulatorOption::IncrDay);
+        SimulatorController::new(system, view).show();
+    }
+
+    fn time<F: FnMut()>(mut f: F) -> Duration {
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            f();
+        }
+        start.elapsed() / ROUNDS
+    }
+
+    /// Reports how long page actions take on a populated system.
+    /// Run with `cargo test --release -- --ignored --nocapture benchmark`.
+    #[test]
+    #[ignore]
+    fn benchmark_page_actions() {
+        let mut system = populate();
+        assert_eq!(system.get_items().len(), ITEMS);
+
+        let members = time(|| visit_members(&mut system));
+        let simulator = time(|| visit_simulator(&mut system));
+        assert_eq!(system.now(), ROUNDS as usize);
+        assert_eq!(system.get_undo_steps().len(), 2 * ROUNDS as usize);
+
+        // Before the pages borrowed the system, every page worked on a copy of it
+        // and handed the copy back.
+        let cloned = time(|| {
+            let mut copy = system.clone();
+            visit_members(&mut copy);
+            system = copy;
+        });
+        assert_eq!(system.get_undo_steps().len(), 3 * ROUNDS as usize);
+
+        // Timings depend on the machine, so they are only reported.
+        println!("Members page:   {:?} per action", members);
+        println!("Simulator page: {:?} per action", simulator);
+        println!("Cloned members page: {:?} per action", cloned);
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod command_test {
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            command::{
+                AdvanceTime, BookItem, CreateItem, CreateMember, DeleteItem, EditContract,
+                EditMember,
+            },
+            contract::{Contract, Status},
+            item::{Category, Item},
+            member::Member,
+            simulation::Advance,
+            system::{LendingSystem, System},
+        },
+    };
+
+    /// Allan and Bob are members, Allan's Monopoly is not listed yet.
+    fn setup() -> (System, Member, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(60f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    fn credits(system: &System, member: &Member) -> f64 {
+        *system.get_member(member).expect("").get_credits()
+    }
+
+    #[test]
+    fn test_undo_redo_member() {
+        let (mut system, _, _, _) = setup();
+        let tina = Member::new(
+            "Tina".to_owned(),
+            "tina@gmail.com".to_owned(),
+            "0555555555".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .build();
+        system.execute(&mut CreateMember(tina.clone())).expect("");
+        assert_eq!(system.get_undo_steps(), vec!["Create member Tina"]);
+
+        assert_eq!(system.undo(), Ok("Create member Tina".to_owned()));
+        assert!(!system.exists_member(&tina));
+        assert!(system.get_undo_steps().is_empty());
+        assert_eq!(system.get_redo_steps(), vec!["Create member Tina"]);
+
+        assert_eq!(system.redo(), Ok("Create member Tina".to_owned()));
+        assert!(system.exists_member(&tina));
+        assert!(system.get_redo_steps().is_empty());
+    }
+
+    #[test]
+    fn test_undo_item_restores_credits_and_search() {
+        let (mut system, allan, _, item) = setup();
+        system.execute(&mut CreateItem(item.clone())).expect("");
+        assert_eq!(credits(&system, &allan), 200f64);
+
+        system.undo().expect("");
+        assert_eq!(credits(&system, &allan), 100f64);
+        assert!(system.get_item(&item).is_err());
+        assert!(system.search_items("monopoly").is_empty());
+
+        system.redo().expect("");
+        system.execute(&mut DeleteItem(item.clone())).expect("");
+        assert!(system.get_item(&item).is_err());
+        system.undo().expect("");
+        assert!(system.get_item(&item).is_ok());
+        assert_eq!(system.search_items("monopoly").len(), 1);
+        assert_eq!(
+            system.get_undo_steps(),
+            vec!["Create item Monopoly"],
+            "The deletion was undone"
+        );
+    }
+
+    #[test]
+    fn test_undo_edit_contract() {
+        let (mut system, allan, bob, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let contract = Contract::new(allan, bob, 1, 3, 30f64);
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+
+        let mut edit = EditContract {
+            item: item.clone(),
+            contract: contract.clone().contract_len(2).build(),
+        };
+        system.execute(&mut edit).expect("");
+        assert_eq!(
+            system.get_undo_steps(),
+            vec!["Edit booking of Monopoly for Bob"]
+        );
+        let len = |system: &System| *system.get_contract(&contract).expect("").get_contract_len();
+        assert_eq!(len(&system), 2);
+
+        system.undo().expect("");
+        assert_eq!(len(&system), 3);
+        system.redo().expect("");
+        assert_eq!(len(&system), 2);
+    }
+
+    #[test]
+    fn test_undo_advance_time() {
+        let (mut system, allan, bob, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        let mut booking = BookItem {
+            item: item.clone(),
+            contract: contract.clone(),
+            unit: None,
+        };
+        system.execute(&mut booking).expect("");
+        let jobs = system.get_scheduled_jobs().len();
+
+        let mut advance = AdvanceTime::new(Advance::Days(5));
+        system.execute(&mut advance).expect("");
+        assert_eq!(system.now(), 5);
+        assert_eq!(credits(&system, &bob), 40f64);
+        assert_eq!(
+            system.get_undo_steps()[0],
+            "Advance from day 0 to 5",
+            "Describes the advanced days"
+        );
+
+        system.undo().expect("");
+        assert_eq!(system.now(), 0);
+        assert_eq!(credits(&system, &bob), 60f64);
+        assert_eq!(credits(&system, &allan), 200f64);
+        assert_eq!(system.get_scheduled_jobs().len(), jobs);
+        assert_eq!(
+            *system.get_contract(&contract).expect("").get_status(),
+            Status::Future
+        );
+
+        system.redo().expect("");
+        assert_eq!(system.now(), 5);
+        assert_eq!(credits(&system, &bob), 40f64);
+        assert_eq!(
+            *system.get_contract(&contract).expect("").get_status(),
+            Status::Finished
+        );
+    }
+
+    #[test]
+    fn test_failed_command_changes_nothing() {
+        let (mut system, allan, _, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let copy = item.clone().name("Changed".to_owned()).build();
+        assert_eq!(
+            system.execute(&mut CreateItem(copy)),
+            Err(SysError::AlreadyExists)
+        );
+        assert_eq!(system.get_item(&item).expect("").get_name(), "Monopoly");
+        assert_eq!(credits(&system, &allan), 200f64);
+        assert!(system.get_undo_steps().is_empty());
+    }
+
+    #[test]
+    fn test_new_command_clears_redo() {
+        let (mut system, allan, bob, _) = setup();
+        let renamed = allan.clone().name("Al".to_owned()).build();
+        let mut edit = EditMember {
+            old: allan.clone(),
+            new: renamed,
+        };
+        system.execute(&mut edit).expect("");
+        system.undo().expect("");
+        assert_eq!(system.get_member(&allan).expect("").get_name(), "Allan");
+
+        let renamed = bob.clone().name("Robert".to_owned()).build();
+        let mut edit = EditMember {
+            old: bob.clone(),
+            new: renamed,
+        };
+        system.execute(&mut edit).expect("");
+        assert!(system.get_redo_steps().is_empty());
+        assert_eq!(system.redo(), Err(SysError::NothingToRedo));
+
+        system.logout();
+        assert_eq!(system.undo(), Err(SysError::NothingToUndo));
+        assert_eq!(system.get_member(&bob).expect("").get_name(), "Robert");
+    }
+
+    #[test]
+    fn test_undo_redo_keep_sent_notifications() {
+        let (mut system, allan, bob, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let mut booking = BookItem {
+            item,
+            contract: Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64),
+            unit: None,
+        };
+        system.execute(&mut booking).expect("");
+        let sent = system.get_notifications(&allan);
+        assert_eq!(sent.len(), 1);
+
+        system.undo().expect("");
+        assert_eq!(system.get_contracts_for_member(&bob).len(), 0);
+        assert_eq!(system.get_notifications(&allan), sent);
+
+        system.redo().expect("");
+        assert_eq!(system.get_contracts_for_member(&bob).len(), 1);
+        assert_eq!(system.get_notifications(&allan), sent);
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod community_test {
+    use crate::models::domain::{
+        community::{simulate_community, CommunityConfig},
+        system::{LendingSystem, System},
+    };
+
+    fn run(config: &CommunityConfig) -> (System, Vec<String>) {
+        let mut sys = System::new();
+        simulate_community(&mut sys, config);
+        let mut uuids = sys
+            .get_items()
+            .iter()
+            .map(|i| i.get_uuid().get_value().clone())
+            .collect::<Vec<String>>();
+        uuids.sort();
+        (sys, uuids)
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let config = CommunityConfig::default().seed(7).build();
+        let (first, first_uuids) = run(&config);
+        let (second, second_uuids) = run(&config);
+        assert_eq!(
+            simulate_community(&mut System::new(), &config),
+            simulate_community(&mut System::new(), &config)
+        );
+        assert_eq!(first_uuids, second_uuids);
+        assert_eq!(first.now(), second.now());
+    }
+
+    #[test]
+    fn test_stats() {
+        let config = CommunityConfig::default()
+            .seed(1)
+            .members(10)
+            .days(20)
+            .book_probability(0.5)
+            .build();
+        let mut sys = System::new();
+        let stats = simulate_community(&mut sys, &config);
+        assert_eq!(sys.now(), 20);
+        assert_eq!(*stats.get_members(), 10);
+        assert_eq!(*stats.get_items(), sys.get_items().len());
+        assert!(*stats.get_bookings() > 0);
+        let contracts = sys
+            .get_items()
+            .iter()
+            .map(|i| i.get_history().len())
+            .sum::<usize>();
+        assert_eq!(*stats.get_bookings(), contracts);
+        assert!(stats.get_min_credits() <= stats.get_max_credits());
+    }
+
+    #[test]
+    fn test_different_seeds() {
+        let first = CommunityConfig::default().seed(1).build();
+        let second = CommunityConfig::default().seed(2).build();
+        assert_ne!(run(&first).1, run(&second).1);
+    }
+}
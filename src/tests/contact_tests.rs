@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod contact_test {
+    use crate::{
+        errors::{MemValError, SysError},
+        models::domain::{
+            contact::{ContactConfig, ContactRules, Country, E164Rules, LocalRules},
+            member::Member,
+            system::{LendingSystem, System},
+        },
+        types::{Demo, Validate},
+    };
+
+    fn member(email: &str, phone_nr: &str) -> Member {
+        Member::default()
+            .name("Jane".to_owned())
+            .email(email.to_owned())
+            .phone_nr(phone_nr.to_owned())
+            .build()
+    }
+
+    #[test]
+    fn test_email_is_case_insensitive() {
+        assert_eq!(
+            LocalRules.normalize_email(" Jane.Doe@Example.org "),
+            Ok("jane.doe@example.org".to_owned())
+        );
+        assert_eq!(
+            LocalRules.normalize_email("Jane.Doe@"),
+            Err(MemValError::EmailPattern)
+        );
+        assert!(member("Jane.Doe@Example.org", "0123456789")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_email_pattern() {
+        for email in [
+            "jane-doe@x.org",
+            "a+b@x.org",
+            "jane_doe@mail.example-host.com",
+        ] {
+            assert_eq!(LocalRules.normalize_email(email), Ok(email.to_owned()));
+        }
+        for email in [
+            "jane@example.org junk",
+            "jane@example.org;drop",
+            "jane@example.organization",
+            ".jane@example.org",
+            "jane.@example.org",
+            "ja ne@example.org",
+        ] {
+            assert_eq!(
+                LocalRules.normalize_email(email),
+                Err(MemValError::EmailPattern),
+                "{} is not valid",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn test_local_phone_nrs() {
+        assert_eq!(
+            LocalRules.normalize_phone_nr("012 345 6789"),
+            Ok("0123456789".to_owned())
+        );
+        assert_eq!(
+            LocalRules.normalize_phone_nr("+46701234567"),
+            Err(MemValError::PhoneNumberContainsNonNumeric)
+        );
+        assert_eq!(
+            LocalRules.normalize_phone_nr("0123456"),
+            Err(MemValError::PhoneNumber)
+        );
+    }
+
+    #[test]
+    fn test_e164_phone_nrs() {
+        let sweden = E164Rules::new(Country::Sweden);
+        let expected = Ok("+46701234567".to_owned());
+        for input in [
+            "070-123 45 67",
+            "+46 70 123 45 67",
+            "+46 (0)70 123 45 67",
+            "0046701234567",
+        ] {
+            assert_eq!(sweden.normalize_phone_nr(input), expected);
+        }
+        assert_eq!(
+            sweden.normalize_phone_nr("+49 30 1234567"),
+            Ok("+49301234567".to_owned())
+        );
+        assert_eq!(
+            sweden.normalize_phone_nr("070 123"),
+            Err(MemValError::PhoneNumber)
+        );
+        assert_eq!(
+            sweden.normalize_phone_nr("070 ABC 45 67"),
+            Err(MemValError::PhoneNumberContainsNonNumeric)
+        );
+
+        let us = E164Rules::new(Country::UnitedStates);
+        assert_eq!(
+            us.normalize_phone_nr("(212) 555-1234"),
+            Ok("+12125551234".to_owned())
+        );
+        assert_eq!(
+            us.normalize_phone_nr("1 212 555 1234"),
+            Ok("+12125551234".to_owned())
+        );
+        let uk = E164Rules::new(Country::UnitedKingdom);
+        assert_eq!(
+            uk.normalize_phone_nr("020 7946 0958"),
+            Ok("+442079460958".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_member_stores_normalized_contact() {
+        let rules = ContactConfig::E164(Country::Sweden);
+        let jane = member("Jane.Doe@Example.org", "070-123 45 67")
+            .validate_with(&rules)
+            .expect("Should not fail");
+        assert_eq!(jane.get_email(), "jane.doe@example.org");
+        assert_eq!(jane.get_phone_nr(), "+46701234567");
+
+        let local = member("Jane.Doe@Example.org", "070-123 45 67")
+            .validate_with(&ContactConfig::Local)
+            .map(|_| ());
+        assert!(local.is_err());
+    }
+
+    #[test]
+    fn test_system_selects_rules() {
+        assert_eq!(System::new().get_contact_rules(), &ContactConfig::Local);
+        let mut system = System::new()
+            .contact(ContactConfig::E164(Country::Germany))
+            .build();
+        let jane = member("Jane@Example.org", "030 1234567");
+        system.add_member(jane).expect("Should not fail");
+        assert_eq!(
+            system
+                .get_member_by_email("JANE@example.org")
+                .map(|m| m.get_phone_nr().clone()),
+            Ok("+49301234567".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_rules_are_read_from_config() {
+        assert_eq!("local".parse(), Ok(ContactConfig::Local));
+        assert_eq!("E164:se".parse(), Ok(ContactConfig::E164(Country::Sweden)));
+        assert_eq!(
+            "e164:united-kingdom".parse(),
+            Ok(ContactConfig::E164(Country::UnitedKingdom))
+        );
+        assert_eq!(
+            "e164:mars".parse::<ContactConfig>(),
+            Err(SysError::InvalidInput)
+        );
+        assert_eq!("".parse::<ContactConfig>(), Err(SysError::InvalidInput));
+
+        for country in ["se", "de", "fr", "uk", "us"] {
+            let rules = format!("e164:{}", country).parse().expect("");
+            let mut system = System::new().contact(rules).build();
+            system.init_demo();
+            assert_eq!(system.get_members().len(), 4);
+            assert!(system
+                .get_members()
+                .iter()
+                .all(|m| m.get_phone_nr().starts_with('+')));
+        }
+    }
+}
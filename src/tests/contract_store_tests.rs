@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod contract_store_test {
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            command::{BookItem, DeleteItem},
+            contract::{Contract, Status},
+            item::{Category, Item},
+            member::Member,
+            system::{LendingSystem, System},
+        },
+    };
+
+    /// Allan lists Monopoly, Bob has enough credits to book it for ten days.
+    fn setup() -> (System, Member, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        system.add_item(item.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    fn uuids(contracts: Vec<&Contract>) -> Vec<String> {
+        contracts
+            .into_iter()
+            .map(|c| c.get_uuid().get_value().clone())
+            .collect::<Vec<String>>()
+    }
+
+    #[test]
+    fn test_lookup_by_uuid() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+
+        assert_eq!(system.get_contract(&contract), Ok(contract.clone()));
+        assert_eq!(
+            system
+                .get_item_for_contract(&contract)
+                .map(|i| i.get_uuid()),
+            Some(item.get_uuid())
+        );
+
+        // Items with running or future contracts cannot be removed.
+        system.incr_time().expect("");
+        assert_eq!(system.remove_item(&item), Err(SysError::ActiveContracts(1)));
+        for _ in 0..3 {
+            system.incr_time().expect("");
+        }
+        system.remove_item(&item).expect("");
+        assert_eq!(system.get_contract(&contract), Err(SysError::DoesntExist));
+        assert!(system.get_item_for_contract(&contract).is_none());
+        assert!(system.get_contracts_for_member(&bob).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_uuid_is_rejected() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 3, 30f64);
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+        let chess = Item::new(
+            "Chess".to_owned(),
+            "Strategy Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            5f64,
+            0,
+        );
+        system.add_item(chess.clone()).expect("");
+
+        let later = contract.clone().from_date(10, 2).build();
+        assert_eq!(
+            system.add_contract(&item, later, None),
+            Err(SysError::AlreadyExists)
+        );
+        assert_eq!(
+            system.add_contract(&chess, contract.clone(), None),
+            Err(SysError::AlreadyExists)
+        );
+        assert_eq!(system.get_contracts_for_member(&bob).len(), 1);
+        assert_eq!(
+            system
+                .get_item_for_contract(&contract)
+                .map(|i| i.get_uuid()),
+            Some(item.get_uuid())
+        );
+        assert!(system.get_item(&chess).expect("").get_history().is_empty());
+    }
+
+    #[test]
+    fn test_contracts_for_member() {
+        let (mut system, allan, bob, item) = setup();
+        let late = Contract::new(allan.clone(), bob.clone(), 6, 2, 20f64);
+        let early = Contract::new(allan.clone(), allan.clone(), 1, 2, 20f64);
+        system.add_contract(&item, late.clone(), None).expect("");
+        system.add_contract(&item, early.clone(), None).expect("");
+
+        assert_eq!(
+            uuids(system.get_contracts_for_member(&allan)),
+            uuids(vec![&early, &late])
+        );
+        assert_eq!(
+            uuids(system.get_contracts_for_member(&bob)),
+            uuids(vec![&late])
+        );
+    }
+
+    #[test]
+    fn test_active_contracts() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 2, 20f64);
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+        assert!(system.get_active_contracts().is_empty());
+
+        system.incr_time().expect("");
+        assert_eq!(uuids(system.get_active_contracts()), uuids(vec![&contract]));
+
+        system.incr_time().expect("");
+        system.incr_time().expect("");
+        assert!(system.get_active_contracts().is_empty());
+        assert_eq!(
+            system.get_contract(&contract).expect("").get_status(),
+            &Status::Finished
+        );
+    }
+
+    #[test]
+    fn test_booking_starting_today_is_active() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 0, 2, 20f64);
+        let mut booking = BookItem {
+            item,
+            contract: contract.clone(),
+            unit: None,
+        };
+        system.execute(&mut booking).expect("");
+        assert_eq!(uuids(system.get_active_contracts()), uuids(vec![&contract]));
+
+        system.undo().expect("");
+        assert!(system.get_active_contracts().is_empty());
+        system.redo().expect("");
+        assert_eq!(uuids(system.get_active_contracts()), uuids(vec![&contract]));
+    }
+
+    #[test]
+    fn test_undo_restores_contracts() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+        for _ in 0..4 {
+            system.incr_time().expect("");
+        }
+        let returned = system.get_contract(&contract).expect("");
+        let listed = system.get_item(&item).expect("");
+        system.execute(&mut DeleteItem(listed)).expect("");
+        assert!(system.get_contract(&contract).is_err());
+
+        system.undo().expect("");
+        assert_eq!(system.get_contract(&contract), Ok(returned));
+        assert_eq!(system.check_invariants(), vec![]);
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod error_test {
+    use crate::{
+        errors::{Check, Context, FieldError, LendingError, MemValError, SysError},
+        models::domain::{
+            audit::Entity,
+            contract::Contract,
+            item::{Category, Item},
+            member::Member,
+            member_index::UniqueField,
+            system::{LendingSystem, System},
+        },
+        types::Validate,
+    };
+    use std::error::Error;
+
+    fn setup() -> (System, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let hammer = Item::new(
+            "Hammer".to_owned(),
+            "Heavy".to_owned(),
+            Category::Tool,
+            allan.clone(),
+            5f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        system.add_item(hammer.clone()).expect("");
+        for start in [2, 6] {
+            let contract = Contract::new(allan.clone(), bob.clone(), start, 2, 10f64);
+            system.add_contract(&hammer, contract, None).expect("");
+        }
+        (system, allan, hammer)
+    }
+
+    #[test]
+    fn test_error_names_the_entity() {
+        let (system, allan, hammer) = setup();
+        let hammer = system.get_item(&hammer).expect("");
+        assert_eq!(hammer.count_future_contracts(), 2);
+
+        let err = LendingError::new(&hammer, SysError::FutureContracts(2));
+        assert_eq!(err.to_string(), "Item 'Hammer' has 2 future contracts.");
+        assert_eq!(err.get_entity(), &Entity::Item);
+        assert_eq!(err.get_id(), &hammer.get_uuid().to_string());
+        assert_eq!(err.get_cause(), &SysError::FutureContracts(2));
+        assert_eq!(
+            err.source().map(|e| e.to_string()),
+            Some(err.get_cause().to_string())
+        );
+
+        let err = LendingError::new(&hammer, SysError::ActiveContracts(1));
+        assert_eq!(
+            err.to_string(),
+            "Item 'Hammer' is lent out in 1 active contract."
+        );
+        let err = LendingError::new(&hammer, SysError::ActiveContracts(2));
+        assert_eq!(
+            err.to_string(),
+            "Item 'Hammer' is lent out in 2 active contracts."
+        );
+
+        let err = LendingError::new(&allan, MemValError::NotUnique(UniqueField::Email));
+        assert_eq!(
+            err.to_string(),
+            "Member 'Allan' has the same email as another member."
+        );
+
+        let contract = hammer.get_history().iter().next().expect("").clone();
+        let err = LendingError::new(&contract, SysError::CannotUpdate);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Contract {}: There was a problem updating this object.",
+                contract.get_uuid()
+            )
+        );
+    }
+
+    #[test]
+    fn test_conversions_from_validation_errors() {
+        assert_eq!(
+            SysError::from(MemValError::PhoneNumber),
+            SysError::Validation(MemValError::PhoneNumber)
+        );
+        let check = Member::default()
+            .name("Allan".to_owned())
+            .email("abc".to_owned())
+            .phone_nr("0123456789".to_owned())
+            .validate()
+            .expect_err("Should fail");
+        let err = LendingError::new(&Member::default().name("Allan".to_owned()).build(), check);
+        assert_eq!(
+            err.get_cause(),
+            &SysError::InvalidFields(vec![FieldError::new("email", MemValError::EmailPattern)])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Member 'Allan' is invalid: email: Email doesnt match any valid patterns."
+        );
+        assert_eq!(SysError::from(Check::Ok), SysError::InvalidFields(vec![]));
+    }
+
+    #[test]
+    fn test_context_keeps_the_cause() {
+        let (mut system, allan, hammer) = setup();
+        let err = system
+            .remove_item(&hammer)
+            .context(&hammer)
+            .expect_err("Should fail");
+        assert_eq!(err.get_cause(), &SysError::FutureContracts(2));
+        assert_eq!(err.to_string(), "Item 'Hammer' has 2 future contracts.");
+        assert!(system.get_item(&hammer).is_ok());
+
+        let saw = Item::default().name("Saw".to_owned()).build();
+        let err = system
+            .remove_item(&saw)
+            .context(&saw)
+            .expect_err("Should fail");
+        assert_eq!(err.get_cause(), &SysError::CannotDelete);
+        assert!(err.to_string().starts_with("Item 'Saw': "));
+
+        let stranger = Member::default().name("Stranger".to_owned()).build();
+        let err = system
+            .get_member(&stranger)
+            .context(&stranger)
+            .expect_err("Should fail");
+        assert_eq!(err.to_string(), "Member 'Stranger' does not exist.");
+        assert!(system.get_member(&allan).context(&allan).is_ok());
+    }
+
+    #[test]
+    fn test_item_lent_out_today_is_not_removed() {
+        let (mut system, allan, hammer) = setup();
+        let bob = system.get_member_by_email("bob@gmail.com").expect("");
+        let contract = Contract::new(allan, bob, 0, 2, 10f64);
+        system.add_contract(&hammer, contract, None).expect("");
+
+        let err = system
+            .remove_item(&hammer)
+            .context(&hammer)
+            .expect_err("Should fail");
+        assert_eq!(err.get_cause(), &SysError::ActiveContracts(1));
+        assert_eq!(
+            err.to_string(),
+            "Item 'Hammer' is lent out in 1 active contract."
+        );
+        assert!(system.get_item(&hammer).is_ok());
+    }
+}
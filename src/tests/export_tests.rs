@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod export_test {
+    use crate::{
+        models::{
+            domain::{
+                contract::Contract,
+                item::{Category, Item},
+                member::Member,
+            },
+            export::{ExportFormat, Sheet},
+        },
+        types::Data,
+    };
+    use std::{fs, str::FromStr};
+
+    fn sheet() -> Sheet {
+        Sheet::new(
+            "Top Borrowers",
+            vec!["Member".to_owned(), "Credits".to_owned()],
+            vec![
+                vec!["Bob, Jr.".to_owned(), "60".to_owned()],
+                vec!["<Tina> & \"Co\"|".to_owned(), "20".to_owned()],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            Sheet::render(&[sheet()], ExportFormat::Csv),
+            "Member,Credits\n\"Bob, Jr.\",60\n\"<Tina> & \"\"Co\"\"|\",20\n"
+        );
+        let two = Sheet::render(&[sheet(), sheet()], ExportFormat::Csv);
+        assert!(two.starts_with("Top Borrowers\nMember,Credits\n"));
+        assert!(two.contains("20\n\nTop Borrowers\n"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let md = Sheet::render(&[sheet()], ExportFormat::Markdown);
+        assert!(md.starts_with("## Top Borrowers\n\n| Member | Credits |\n|-|-|\n"));
+        assert!(md.contains("| <Tina> & \"Co\"\\| | 20 |"));
+    }
+
+    #[test]
+    fn test_html() {
+        let html = Sheet::render(&[sheet()], ExportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Top Borrowers</title>"));
+        assert!(html.contains("<tr><th>Member</th><th>Credits</th></tr>"));
+        assert!(html.contains("<td>&lt;Tina&gt; &amp; &quot;Co&quot;|</td>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_from_models() {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        let csv = Sheet::render(
+            &[Sheet::from_models("Members", vec![&allan])],
+            ExportFormat::Csv,
+        );
+        let lines = csv.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], Member::head().join(","));
+        assert!(lines[1].starts_with("Allan,allan@enigma.com,0123456789,"));
+        assert!(lines[1].contains(allan.get_uuid().get_value()));
+        assert!(!lines[1].contains("[len,"));
+    }
+
+    #[test]
+    fn test_members_are_exported_by_name_and_id() {
+        let mut allan = Member::default().name("Allan".to_owned()).build();
+        allan.set_password("secret");
+        let mut bob = Member::default()
+            .name("Bob".to_owned())
+            .credits(100f64)
+            .build();
+        bob.set_password("hunter2");
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 3, 30f64);
+        let mut item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+        item.add_contract(contract.clone())
+            .expect("Should not fail");
+
+        let items = Sheet::from_models("Items", vec![&item]);
+        let contracts = Sheet::from_models("Contracts", vec![&contract]);
+        for format in [
+            ExportFormat::Csv,
+            ExportFormat::Markdown,
+            ExportFormat::Html,
+        ] {
+            let out = Sheet::render(&[items.clone(), contracts.clone()], format);
+            for member in [&allan, &bob] {
+                assert!(!out.contains(member.get_salt().as_str()));
+                assert!(!out.contains(member.get_password_hash().as_str()));
+            }
+            assert!(out.contains(&format!("Allan ({})", allan.get_uuid())));
+            assert!(out.contains(&format!("Bob ({})", bob.get_uuid())));
+            assert!(out.contains(&contract.get_uuid().to_string()));
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = std::env::temp_dir().join(format!("exports-{}", std::process::id()));
+        let path = Sheet::export(&[sheet()], ExportFormat::from_str("md").unwrap(), &dir)
+            .expect("Should not fail");
+        assert_eq!(path, dir.join("top-borrowers.md"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            Sheet::render(&[sheet()], ExportFormat::Markdown)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
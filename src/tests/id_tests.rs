@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod id_test {
+    use crate::{
+        errors::{MemValError, Subject, SysError},
+        models::{
+            domain::{
+                contract::Contract,
+                item::{Category, Item},
+                member::{Member, MemberValidation},
+                member_index::UniqueField,
+                system::{LendingSystem, System},
+            },
+            id::{
+                ContractId, ContractKind, Id, IdGenerator, ItemId, MemberId, MemberKind,
+                MAX_ID_LEN, MIN_ID_LEN,
+            },
+            uuid::Uuid,
+        },
+        types::{FromMap, ToMap},
+    };
+    use std::{collections::HashMap, str::FromStr};
+
+    fn setup() -> (System, Member, Item, Contract) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 3, 30f64);
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob).expect("");
+        system.add_item(item.clone()).expect("");
+        system
+            .add_contract(&item, contract.clone(), None)
+            .expect("");
+        (system, allan, item, contract)
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        let id = MemberId::new();
+        let shown = id.to_string();
+        assert_eq!(shown, format!("M-{}", id.get_value()));
+        assert_eq!(MemberId::from_str(&shown), Ok(id.clone()));
+        let lowercase = format!("m-{}", id.get_value());
+        assert_eq!(MemberId::from_str(&lowercase), Ok(id.clone()));
+        assert_eq!(MemberId::from_str(&format!(" {} ", shown)), Ok(id));
+
+        assert!(ItemId::new().to_string().starts_with("I-"));
+        assert!(ContractId::new().to_string().starts_with("C-"));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_kinds() {
+        let item = ItemId::new().to_string();
+        assert_eq!(MemberId::from_str(&item), Err(SysError::InvalidInput));
+        assert_eq!(ContractId::from_str(&item), Err(SysError::InvalidInput));
+        for invalid in ["", "ab12cd", "M-", "M-ab 12", "MM-ab12cd"] {
+            assert_eq!(MemberId::from_str(invalid), Err(SysError::InvalidInput));
+        }
+    }
+
+    #[test]
+    fn test_entities_keep_their_id() {
+        let (_, allan, item, _) = setup();
+        let parsed = Member::from_complete_map(allan.to_map());
+        assert_eq!(parsed.get_uuid(), allan.get_uuid());
+        let edited = item.copy_with_map(HashMap::from([("name".to_owned(), "Risk".to_owned())]));
+        assert_eq!(edited.get_uuid(), item.get_uuid());
+    }
+
+    #[test]
+    fn test_lookup_by_id() {
+        let (system, allan, item, contract) = setup();
+        assert_eq!(system.get_member_by_id(allan.get_uuid()), Ok(&allan));
+        assert_eq!(
+            system
+                .get_item_by_id(item.get_uuid())
+                .map(|i| i.get_name().as_str()),
+            Ok("Monopoly")
+        );
+        assert_eq!(
+            system.get_contract_by_id(contract.get_uuid()),
+            Ok(&contract)
+        );
+
+        let stranger = MemberId::from_str("M-zzzzzz").expect("");
+        assert_eq!(
+            system.get_member_by_id(&stranger),
+            Err(SysError::DoesntExist)
+        );
+    }
+
+    #[test]
+    fn test_seeded_generator() {
+        let mut first = IdGenerator::seeded(42);
+        let mut second = IdGenerator::seeded(42);
+        let ids = (0..100)
+            .map(|_| first.generate())
+            .collect::<Vec<MemberId>>();
+        assert_eq!(
+            ids,
+            (0..100)
+                .map(|_| second.generate())
+                .collect::<Vec<MemberId>>()
+        );
+
+        let mut system = System::new();
+        let mut other = System::new();
+        system.seed_ids(7);
+        other.seed_ids(7);
+        let id: ItemId = system.generate_id();
+        assert_eq!(id, other.generate_id());
+    }
+
+    #[test]
+    fn test_generator_never_repeats() {
+        let mut generator = IdGenerator::seeded(1);
+        let first: MemberId = generator.generate();
+        // The same seed would start with the same id, unless it is claimed.
+        let mut claimed = IdGenerator::seeded(1);
+        assert!(claimed.claim(&first));
+        assert_ne!(claimed.generate::<MemberKind>(), first);
+
+        let (mut system, allan, _, _) = setup();
+        system.seed_ids(1);
+        let taken = system
+            .get_member_by_id(allan.get_uuid())
+            .cloned()
+            .expect("");
+        for _ in 0..1000 {
+            let id: MemberId = system.generate_id();
+            assert_ne!(&id, taken.get_uuid());
+        }
+    }
+
+    #[test]
+    fn test_generator_len() {
+        assert_eq!(IdGenerator::new().get_len(), MIN_ID_LEN);
+        let mut generator = IdGenerator::new().len(12);
+        assert_eq!(generator.generate::<ContractKind>().get_len(), &12);
+        assert_eq!(IdGenerator::new().len(2).get_len(), MIN_ID_LEN);
+        assert_eq!(IdGenerator::new().len(100).get_len(), MAX_ID_LEN);
+
+        let mut system = System::new().ids(IdGenerator::seeded(3).len(20)).build();
+        let id: MemberId = system.generate_id();
+        assert_eq!(id.get_len(), &20);
+        system.set_id_len(14);
+        assert_eq!(system.get_id_len(), 14);
+        let id: ItemId = system.generate_id();
+        assert_eq!(id.get_len(), &14);
+    }
+
+    #[test]
+    fn test_inserted_ids_are_claimed() {
+        let (mut system, allan, item, contract) = setup();
+        let twin = Member::default()
+            .uuid(allan.get_uuid().clone())
+            .email("twin@enigma.com".to_owned())
+            .phone_nr("0123456780".to_owned())
+            .build();
+        assert_eq!(
+            system.add_member(twin),
+            Err(MemValError::NotUnique(UniqueField::Uuid).into())
+        );
+
+        // Ids stay claimed after the entity is removed.
+        let carol = Member::default()
+            .email("carol@gmail.com".to_owned())
+            .phone_nr("0111111111".to_owned())
+            .build();
+        system.add_member(carol.clone()).expect("");
+        system.remove_member(&carol).expect("");
+        assert!(system.add_member(carol).is_err());
+
+        let chess = Item::default()
+            .uuid(system.generate_id())
+            .owner(allan.clone());
+        system.add_item(chess.clone()).expect("");
+        system.remove_item(&chess).expect("");
+        assert_eq!(system.add_item(chess.clone()), Err(SysError::AlreadyExists));
+        let copy = item.clone().name("Chess".to_owned()).build();
+        assert_eq!(system.add_item(copy), Err(SysError::AlreadyExists));
+
+        let chess = Item::default()
+            .uuid(system.generate_id())
+            .owner(allan.clone());
+        system.add_item(chess.clone()).expect("");
+        let rebooked = contract.clone().start_date(10).end_date(12).build();
+        assert_eq!(
+            system.add_contract(&chess, rebooked, None),
+            Err(SysError::AlreadyExists)
+        );
+    }
+
+    #[test]
+    fn test_rolled_back_ids_are_released() {
+        let (mut system, allan, _, _) = setup();
+        let generated = Item::default()
+            .uuid(system.generate_id())
+            .owner(allan.clone());
+        let own = Item::default()
+            .uuid(IdGenerator::new().generate())
+            .owner(allan.clone());
+
+        system.begin();
+        system.add_item(generated.clone()).expect("");
+        system.add_item(own.clone()).expect("");
+        system.rollback().expect("");
+
+        system.add_item(generated).expect("");
+        system.add_item(own).expect("");
+    }
+
+    #[test]
+    fn test_validate_long_ids() {
+        let member = Member::default()
+            .uuid(IdGenerator::new().len(16).generate())
+            .build();
+        assert_eq!(member.validate_id(), Ok(()));
+        let short = Member::default()
+            .uuid(MemberId::from_uuid(Uuid::with_len(4)))
+            .build();
+        assert!(short.validate_id().is_err());
+        let long = MemberId::from_uuid(Uuid::with_len(MAX_ID_LEN + 1));
+        assert!(Member::default().uuid(long).build().validate_id().is_err());
+    }
+
+    #[test]
+    fn test_typed_ids_of_subjects() {
+        let member = Member::default().uuid(MemberId::new()).build();
+        let typed = format!("m-{}", member.get_uuid().get_value());
+        let parsed = MemberId::from_str(typed.as_str()).expect("Should parse");
+        assert_eq!(member.get_typed_id(), &parsed);
+        assert_eq!(member.get_id(), member.get_uuid().to_string());
+
+        let item_id = ItemId::new().to_string();
+        assert_eq!(
+            Id::<<Member as Subject>::Kind>::from_str(item_id.as_str()),
+            Err(SysError::InvalidInput)
+        );
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod interval_test {
+    use crate::models::domain::{
+        contract::{Contract, Status},
+        interval::{ContractHistory, IntervalIndex},
+        item::{Availability, Conflict, Item},
+        member::Member,
+        period::Period,
+    };
+
+    /// Indexes a single period and checks if it overlaps with days 5 to 10.
+    fn overlaps(start: usize, end: usize) -> bool {
+        let mut index = IntervalIndex::new();
+        index.insert(0, Period::new(start, end), 0);
+        !index.overlapping(&Period::new(5, 10)).is_empty()
+    }
+
+    fn contract(start: usize, days: usize) -> Contract {
+        let lendee = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        Contract::default()
+            .lendee(lendee)
+            .start_date(start)
+            .from_date(0, days)
+            .build()
+    }
+
+    #[test]
+    fn test_disjoint_periods() {
+        assert!(!overlaps(1, 3));
+        assert!(!overlaps(12, 15));
+    }
+
+    #[test]
+    fn test_adjacent_periods() {
+        assert!(!overlaps(3, 5));
+        assert!(!overlaps(10, 12));
+    }
+
+    #[test]
+    fn test_partially_overlapping_periods() {
+        assert!(overlaps(3, 6));
+        assert!(overlaps(9, 12));
+    }
+
+    #[test]
+    fn test_periods_sharing_a_bound() {
+        assert!(overlaps(5, 7));
+        assert!(overlaps(7, 10));
+    }
+
+    #[test]
+    fn test_identical_period() {
+        assert!(overlaps(5, 10));
+    }
+
+    #[test]
+    fn test_contained_and_containing_periods() {
+        assert!(overlaps(6, 8));
+        assert!(overlaps(2, 12));
+    }
+
+    #[test]
+    fn test_empty_periods() {
+        assert!(!overlaps(6, 6));
+        let mut index = IntervalIndex::new();
+        index.insert(0, Period::new(5, 10), 0);
+        assert!(index.overlapping(&Period::new(7, 7)).is_empty());
+        assert!(IntervalIndex::new().is_empty());
+    }
+
+    #[test]
+    fn test_long_period_far_back() {
+        let mut index = IntervalIndex::new();
+        index.insert(0, Period::new(0, 100), 0);
+        index.insert(1, Period::new(20, 22), 1);
+        index.insert(2, Period::new(50, 51), 1);
+        assert_eq!(
+            index.overlapping(&Period::new(50, 60)),
+            vec![(0, 0), (2, 1)]
+        );
+        assert_eq!(index.covering(21), vec![(0, 0), (1, 1)]);
+        assert_eq!(index.covering(100), vec![]);
+
+        assert!(index.remove(0, &Period::new(0, 100)));
+        assert!(!index.remove(0, &Period::new(0, 100)));
+        assert_eq!(index.overlapping(&Period::new(50, 60)), vec![(2, 1)]);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_canceled_contracts_book_nothing() {
+        let booked = contract(5, 5);
+        let mut history = ContractHistory::from(vec![booked.clone()]);
+        assert_eq!(history.covering(7).len(), 1);
+
+        history.set(0, &booked.clone().status(Status::Canceled).build());
+        assert!(history.covering(7).is_empty());
+        assert_eq!(history.len(), 1);
+
+        history.set(0, &booked.clone().start_date(20).from_date(0, 2));
+        assert!(history.covering(7).is_empty());
+        assert_eq!(history.covering(21).len(), 1);
+        assert_eq!(history.booked_units(21), 1);
+    }
+
+    #[test]
+    fn test_conflicts_of_an_item() {
+        let mut monopoly = Item::default().name("Monopoly".to_owned());
+        let booked = contract(5, 5);
+        assert!(monopoly.add_contract(booked.clone()).is_ok());
+
+        let conflict = Some(Conflict::Booked(Box::new(booked.clone())));
+        assert_eq!(monopoly.get_contract_in_period(&5, &7), conflict);
+        assert_eq!(monopoly.get_contract_in_period(&2, &12), conflict);
+        assert_eq!(monopoly.get_contract_in_period(&5, &10), conflict);
+        assert_eq!(monopoly.get_contract_in_period(&10, &12), None);
+        assert!(monopoly.add_contract(contract(5, 5)).is_err());
+        assert!(monopoly.add_contract(contract(4, 7)).is_err());
+        assert!(monopoly.add_contract(contract(10, 2)).is_ok());
+    }
+
+    #[test]
+    fn test_active_contracts_and_availability() {
+        let mut tables = Item::default()
+            .name("Folding Table".to_owned())
+            .quantity(2)
+            .build();
+        assert!(tables.add_contract(contract(0, 3)).is_ok());
+        assert!(tables.add_contract(contract(2, 3)).is_ok());
+
+        assert_eq!(tables.get_active_contracts(0).len(), 1);
+        assert_eq!(tables.get_active_contracts(2).len(), 2);
+        assert_eq!(tables.get_active_contracts(5).len(), 0);
+
+        let availability = tables.get_availability(0);
+        assert_eq!(availability[1].1, Availability::Free(1));
+        assert_eq!(availability[2].1, Availability::Free(0));
+        assert_eq!(availability[3].1, Availability::Free(1));
+        assert_eq!(availability[5].1, Availability::Free(2));
+    }
+}
@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod invariants_test {
+    use crate::{
+        models::domain::{
+            command::{CreateItem, EditMember},
+            contract::Contract,
+            interval::ContractHistory,
+            invariants::Violation,
+            item::{Category, Item},
+            member::Member,
+            system::{LendingSystem, System},
+        },
+        types::Demo,
+    };
+
+    /// Allan lists Monopoly, Bob has enough credits to book it for six days.
+    fn setup() -> (System, Member, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(60f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        system.add_item(item.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    fn with_history(item: &Item, contracts: Vec<Contract>) -> Item {
+        item.clone()
+            .history(ContractHistory::from(contracts))
+            .build()
+    }
+
+    #[test]
+    fn test_valid_systems() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        system.add_contract(&item, contract, None).expect("");
+        for _ in 0..5 {
+            let _ = system.incr_time();
+        }
+        assert_eq!(system.check_invariants(), vec![]);
+        assert_eq!(system.get_ledger().total(), 260f64);
+
+        let mut demo = System::new();
+        demo.init_demo();
+        assert_eq!(demo.check_invariants(), vec![]);
+    }
+
+    #[test]
+    fn test_overlapping_contracts() {
+        let (mut system, allan, bob, item) = setup();
+        let first = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        let second = Contract::new(allan.clone(), bob.clone(), 3, 3, 30f64);
+        let adjacent = Contract::new(allan.clone(), bob.clone(), 6, 2, 20f64);
+        let item = with_history(&item, vec![first, second, adjacent]);
+        system.update_item(&item).expect("");
+
+        let violations = system.check_invariants();
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0],
+            Violation::OverlappingContracts { unit: 0, .. }
+        ));
+    }
+
+    /// A member who never joined the system.
+    fn stranger() -> Member {
+        Member::default().name("Tina".to_owned()).build()
+    }
+
+    #[test]
+    fn test_contract_violations() {
+        let (mut system, allan, bob, item) = setup();
+        let wrong_owner = Contract::new(bob.clone(), allan.clone(), 1, 3, 30f64);
+        let missing_lendee = Contract::new(allan.clone(), stranger(), 5, 2, 20f64);
+        let wrong_end = Contract::new(allan.clone(), bob.clone(), 10, 3, 30f64)
+            .end_date(20)
+            .build();
+        let item = with_history(&item, vec![wrong_owner, missing_lendee, wrong_end]);
+        system.update_item(&item).expect("");
+
+        let violations = system.check_invariants();
+        assert_eq!(violations.len(), 3);
+        assert!(matches!(
+            violations[0],
+            Violation::WrongContractOwner { .. }
+        ));
+        assert!(matches!(violations[1], Violation::MissingLendee { .. }));
+        assert!(matches!(violations[2], Violation::WrongEndDate { .. }));
+    }
+
+    #[test]
+    fn test_missing_owner() {
+        let (mut system, _, _, item) = setup();
+        let item = item.owner(stranger()).build();
+        system.update_item(&item).expect("");
+        let violations = system.check_invariants();
+        assert_eq!(
+            violations,
+            vec![Violation::MissingOwner {
+                item: "Monopoly".to_owned(),
+                owner: "Tina".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_former_lendees_leave_no_violations() {
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 0, 1, 10f64);
+        system.add_contract(&item, contract, None).expect("");
+        system.incr_time().expect("");
+        system.remove_member(&bob).expect("");
+        assert_eq!(system.check_invariants(), vec![]);
+    }
+
+    #[test]
+    fn test_credit_violations() {
+        let (mut system, _, bob, _) = setup();
+        let broke = bob.clone().credits(-5f64).build();
+        system.update_member(&bob, &broke).expect("");
+        assert_eq!(
+            system.check_invariants(),
+            vec![Violation::NegativeBalance {
+                member: "Bob".to_owned(),
+                credits: -5f64
+            }]
+        );
+
+        // Only changes that bypass the system create credits the ledger does not know of.
+        system
+            .get_member_mut(&bob)
+            .expect("")
+            .add_credits(65f64)
+            .expect("");
+        assert_eq!(
+            system.check_invariants(),
+            vec![Violation::CreditsNotConserved {
+                minted: 195f64,
+                total: 260f64
+            }]
+        );
+    }
+
+    #[test]
+    fn test_edited_credits_are_minted() {
+        let (mut system, _, bob, _) = setup();
+        let richer = bob.clone().credits(80f64).build();
+        system.update_member(&bob, &richer).expect("");
+        assert_eq!(system.check_invariants(), vec![]);
+        assert_eq!(system.get_ledger().total(), 280f64);
+        let mint = system.get_ledger().get_mints().last().expect("").clone();
+        assert_eq!(mint.get_reason(), "Edited");
+        assert_eq!(*mint.get_amount(), 20f64);
+
+        system.update_member(&richer, &bob).expect("");
+        assert_eq!(system.check_invariants(), vec![]);
+        assert_eq!(system.get_ledger().total(), 260f64);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_steps_that_break_invariants_are_kept() {
+        let (mut system, _, bob, _) = setup();
+        let broke = bob.clone().credits(-5f64).build();
+        system
+            .execute(&mut EditMember {
+                old: bob.clone(),
+                new: broke,
+            })
+            .expect("");
+        let broken = system.get_broken_invariants().to_vec();
+        assert_eq!(
+            broken,
+            vec![(
+                "Edit member Bob".to_owned(),
+                Violation::NegativeBalance {
+                    member: "Bob".to_owned(),
+                    credits: -5f64
+                }
+            )]
+        );
+
+        system.undo().expect("");
+        assert_eq!(system.check_invariants(), vec![]);
+        system.redo().expect("");
+        assert_eq!(system.get_broken_invariants(), broken.as_slice());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_direct_changes_are_watched() {
+        let mut demo = System::new();
+        demo.init_demo();
+        assert!(demo.get_broken_invariants().is_empty());
+
+        let (mut system, allan, bob, item) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 3, 30f64);
+        system.add_contract(&item, contract, None).expect("");
+        system.incr_time().expect("");
+        assert!(system.get_broken_invariants().is_empty());
+
+        let moved = system.get_item(&item).expect("").owner(stranger()).build();
+        system.update_item(&moved).expect("");
+        let broken = system.get_broken_invariants().to_vec();
+        assert!(!broken.is_empty());
+        assert!(broken
+            .iter()
+            .all(|(step, _)| step == "Update item Monopoly"));
+        assert!(broken
+            .iter()
+            .any(|(_, violation)| matches!(violation, Violation::MissingOwner { .. })));
+        // The known violations are not reported again by the following steps.
+        system.incr_time().expect("");
+        assert_eq!(system.get_broken_invariants(), broken.as_slice());
+    }
+
+    #[test]
+    fn test_ledger_follows_undo() {
+        let (mut system, allan, _, _) = setup();
+        let item = Item::new(
+            "Siedler".to_owned(),
+            "Another Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            45f64,
+            0,
+        );
+        system.execute(&mut CreateItem(item)).expect("");
+        assert_eq!(system.get_ledger().total(), 360f64);
+        system.undo().expect("");
+        assert_eq!(system.get_ledger().total(), 260f64);
+        assert_eq!(system.check_invariants(), vec![]);
+    }
+}
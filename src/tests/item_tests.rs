@@ -1,10 +1,14 @@
 #[cfg(test)]
 mod item_tests {
 
-    use crate::models::domain::{
-        contract::Contract,
-        item::{Category, Item},
-        member::Member,
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            contract::Contract,
+            item::{Availability, Category, Conflict, Item},
+            member::Member,
+            period::Period,
+        },
     };
 
     #[test]
@@ -181,4 +185,197 @@ mod item_tests {
         assert_eq!(monopoly.add_contract(c1).is_ok(), true);
         assert_eq!(monopoly.add_contract(c2).is_ok(), false);
     }
+
+    #[test]
+    fn test_multiple_units() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        let mut tables = Item::default()
+            .name("Folding Table".to_owned())
+            .cost_per_day(10f64)
+            .quantity(2)
+            .owner(allan.clone());
+
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(50f64)
+            .from_date(0, 5)
+            .build();
+
+        assert!(tables.add_contract(contract.clone()).is_ok());
+        assert!(tables
+            .add_contract(contract.clone().start_date(1).from_date(0, 5))
+            .is_ok());
+        assert!(tables
+            .add_contract(contract.clone().start_date(2).from_date(0, 5))
+            .is_err());
+
+        let units = tables
+            .get_history()
+            .iter()
+            .map(|c| *c.get_unit())
+            .collect::<Vec<usize>>();
+        assert_eq!(units, vec![0, 1]);
+        assert_eq!(tables.get_active_contracts(2).len(), 2);
+    }
+
+    #[test]
+    fn test_book_specific_unit() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        let mut tables = Item::default()
+            .name("Folding Table".to_owned())
+            .cost_per_day(10f64)
+            .quantity(3)
+            .owner(allan.clone());
+
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(50f64)
+            .from_date(0, 5)
+            .build();
+
+        assert!(tables.add_contract_for_unit(contract.clone(), 2).is_ok());
+        assert!(tables
+            .add_contract_for_unit(contract.clone().start_date(1).from_date(0, 5), 2)
+            .is_err());
+        assert!(tables.add_contract_for_unit(contract.clone(), 3).is_err());
+        assert!(tables.add_contract(contract).is_ok());
+        assert_eq!(tables.get_history().to_vec()[1].get_unit(), &0);
+
+        let availability = tables.get_availability(0);
+        assert_eq!(availability[0], ("0".to_owned(), Availability::Free(1)));
+        assert_eq!(availability[5], ("5".to_owned(), Availability::Free(3)));
+    }
+
+    #[test]
+    fn test_blackout_blocks_booking() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        let mut monopoly = Item::default()
+            .name("Monopoly".to_owned())
+            .cost_per_day(20f64)
+            .owner(allan.clone());
+
+        assert!(monopoly.add_blackout(Period::from_len(5, 3)).is_ok());
+
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(100f64)
+            .start_date(3)
+            .from_date(0, 5)
+            .build();
+        assert_eq!(monopoly.add_contract(contract), Err(SysError::Unavailable));
+        assert_eq!(
+            monopoly.get_contract_in_period(&6, &7),
+            Some(Conflict::Blackout(Period::new(5, 8)))
+        );
+
+        let availability = monopoly.get_availability(0);
+        assert_eq!(availability[4].1, Availability::Free(1));
+        assert_eq!(availability[5].1, Availability::Blackout);
+        assert_eq!(availability[7].1, Availability::Blackout);
+        assert_eq!(availability[8].1, Availability::Free(1));
+
+        assert!(monopoly.remove_blackout(&Period::new(5, 8)).is_ok());
+        assert_eq!(monopoly.get_contract_in_period(&6, &7), None);
+    }
+
+    #[test]
+    fn test_blackout_on_booked_days() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        let mut monopoly = Item::default()
+            .name("Monopoly".to_owned())
+            .cost_per_day(20f64)
+            .owner(allan.clone());
+
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(100f64)
+            .from_date(0, 5)
+            .build();
+        monopoly.add_contract(contract).expect("");
+
+        assert!(monopoly.add_blackout(Period::from_len(4, 3)).is_err());
+        assert!(monopoly.add_blackout(Period::from_len(5, 3)).is_ok());
+        assert!(monopoly.add_blackout(Period::from_len(9, 0)).is_err());
+    }
+
+    #[test]
+    fn test_unit_conflict_with_same_or_containing_period() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(500f64)
+            .build();
+        let mut item = Item::default()
+            .name("Monopoly".to_owned())
+            .cost_per_day(10f64)
+            .owner(allan.clone());
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 2, 20f64);
+        assert!(item.add_contract_for_unit(contract, 0).is_ok());
+
+        // Only periods that started or ended strictly inside the booking used to conflict.
+        for (start, end) in [(2, 4), (1, 5), (2, 5), (1, 4)] {
+            assert!(
+                matches!(
+                    item.get_unit_contract_in_period(0, &start, &end),
+                    Some(Conflict::Booked(_))
+                ),
+                "{}..{} overlaps the booking",
+                start,
+                end
+            );
+        }
+        let containing = Contract::new(allan, bob, 1, 4, 40f64);
+        assert!(item.add_contract_for_unit(containing, 0).is_err());
+        assert!(item.get_unit_contract_in_period(0, &4, &6).is_none());
+    }
 }
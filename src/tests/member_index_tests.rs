@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod member_index_test {
+    use crate::{
+        errors::{MemValError, SysError},
+        models::domain::{
+            contact::{ContactConfig, Country},
+            member::Member,
+            member_index::{MemberIndex, UniqueField},
+            system::{LendingSystem, System},
+        },
+    };
+
+    fn setup() -> (System, Member, Member) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        (system, allan, bob)
+    }
+
+    fn stranger(email: &str, phone_nr: &str) -> Member {
+        Member::default()
+            .name("Stranger".to_owned())
+            .email(email.to_owned())
+            .phone_nr(phone_nr.to_owned())
+            .build()
+    }
+
+    #[test]
+    fn test_index_compares_stored_values() {
+        let allan = stranger("allan@enigma.com", "0123456789");
+        let mut index = MemberIndex::new();
+        index.insert(&allan);
+        assert_eq!(index.by_email("allan@enigma.com"), Some(allan.get_uuid()));
+        assert_eq!(index.by_phone_nr("0123456789"), Some(allan.get_uuid()));
+        assert_eq!(index.collision(&allan), None);
+
+        let (system, allan, _) = setup();
+        let spaced = stranger(" Allan@Enigma.COM ", "012 345 6789");
+        assert_eq!(
+            system.check_unique(&spaced),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::Email
+            )))
+        );
+        assert!(system.exists_member(&spaced));
+        assert_eq!(system.get_member_by_email("ALLAN@enigma.com"), Ok(allan));
+    }
+
+    #[test]
+    fn test_e164_numbers_collide() {
+        let mut system = System::new()
+            .contact(ContactConfig::E164(Country::Sweden))
+            .build();
+        let jane = stranger("jane@gmail.com", "070-123 45 67");
+        system.add_member(jane.clone()).expect("");
+        let stored = system.get_member(&jane).expect("");
+        assert_eq!(stored.get_phone_nr(), "+46701234567");
+
+        let john = stranger("john@gmail.com", "+46 70 123 45 67");
+        assert_eq!(
+            system.add_member(john.clone()),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::PhoneNr
+            )))
+        );
+        let moved = stored.clone().phone_nr("0701234568".to_owned()).build();
+        system.update_member(&stored, &moved).expect("");
+        assert_eq!(
+            system.get_member(&jane).map(|m| m.get_phone_nr().clone()),
+            Ok("+46701234568".to_owned())
+        );
+        assert_eq!(system.add_member(john), Ok(()));
+    }
+
+    #[test]
+    fn test_collisions_name_the_field() {
+        let (mut system, allan, _) = setup();
+        let same_email = stranger("Allan@Enigma.com", "1111111111");
+        let same_phone_nr = stranger("someone@gmail.com", "0123 456 789");
+        assert_eq!(
+            system.add_member(same_email),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::Email
+            )))
+        );
+        assert_eq!(
+            system.add_member(same_phone_nr),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::PhoneNr
+            )))
+        );
+        assert_eq!(
+            system.add_member(allan.clone()),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::Uuid
+            )))
+        );
+        assert_eq!(
+            SysError::from(MemValError::NotUnique(UniqueField::PhoneNr)).to_string(),
+            "A member with this phone number already exists."
+        );
+    }
+
+    #[test]
+    fn test_update_keeps_index_in_sync() {
+        let (mut system, allan, bob) = setup();
+        let taken = allan.clone().email("bob@gmail.com".to_owned()).build();
+        assert_eq!(
+            system.update_member(&allan, &taken),
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::Email
+            )))
+        );
+
+        let moved = allan.clone().email("allan@turing.com".to_owned()).build();
+        system.update_member(&allan, &moved).expect("");
+        assert!(system.get_member_by_email("allan@enigma.com").is_err());
+        assert_eq!(
+            system.get_member_by_email("Allan@Turing.com"),
+            Ok(moved.clone())
+        );
+
+        system.remove_member(&bob).expect("");
+        let newcomer = stranger("bob@gmail.com", "0987654321");
+        assert_eq!(system.add_member(newcomer), Ok(()));
+    }
+
+    #[test]
+    fn test_rollback_keeps_index_in_sync() {
+        let (mut system, allan, _) = setup();
+        let newcomer = stranger("newcomer@gmail.com", "5555555555");
+        system.begin();
+        system.add_member(newcomer.clone()).expect("");
+        system.remove_member(&allan).expect("");
+        system.rollback().expect("");
+
+        assert!(system.get_member_by_email("newcomer@gmail.com").is_err());
+        assert_eq!(system.get_member_by_email("allan@enigma.com"), Ok(allan));
+        assert_eq!(system.add_member(newcomer), Ok(()));
+    }
+
+    #[test]
+    fn test_login_ignores_case() {
+        let (mut system, allan, _) = setup();
+        let mut with_password = allan.clone();
+        with_password.set_password("secret");
+        system.update_member(&allan, &with_password).expect("");
+        assert!(system.login("ALLAN@enigma.com", "secret").is_ok());
+        assert_eq!(
+            system.login("allan@enigma.com", "wrong"),
+            Err(SysError::InvalidCredentials)
+        );
+    }
+}
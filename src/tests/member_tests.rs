@@ -1,7 +1,87 @@
 #[cfg(test)]
 mod member_test {
 
-    use crate::{models::domain::member::Member, types::Validate};
+    use crate::{
+        controllers::{app::Page, member_controller::MemberController},
+        errors::{Check, FieldError, MemValError},
+        models::{
+            domain::{
+                auth::Role,
+                item::Item,
+                member::{Member, MemberStatus},
+                member_index::UniqueField,
+                notification::Notification,
+                system::{LendingSystem, System},
+            },
+            export::ExportFormat,
+        },
+        types::{ToMap, ValResult, Validate, View},
+        views::member_view::{MemberMenuOption, MemberView},
+    };
+    use std::{cell::RefCell, collections::VecDeque, rc::Rc, str::FromStr};
+
+    /// A member form that enters the scripted members one after another, like a user
+    /// who fixes the invalid fields, and keeps the failed checks.
+    #[derive(Default)]
+    struct FormView {
+        options: RefCell<VecDeque<MemberMenuOption>>,
+        inputs: RefCell<VecDeque<Member>>,
+        checks: Rc<RefCell<Vec<Check>>>,
+        selected: Option<String>,
+    }
+
+    impl FormView {
+        fn fill(&self, validate: &dyn Fn(Member) -> ValResult<Member>) -> Option<Member> {
+            loop {
+                let input = self.inputs.borrow_mut().pop_front()?;
+                match validate(input) {
+                    Ok(member) => return Some(member),
+                    Err(check) => self.checks.borrow_mut().push(check),
+                }
+            }
+        }
+    }
+
+    impl View for FormView {}
+
+    impl MemberView for FormView {
+        fn member_menu(&self) -> MemberMenuOption {
+            let next = self.options.borrow_mut().pop_front();
+            next.unwrap_or(MemberMenuOption::Back)
+        }
+        fn display_member_verbose(&self, _: &Member, _: Vec<&Item>) {}
+        fn display_member_simple(&self, _: &Member, _: usize) {}
+        fn display_all_simple(&self, _: Vec<(&Member, usize)>) {}
+        fn display_all_verbose(&self, _: Vec<(&Member, Vec<&Item>)>) {}
+        fn get_member_info(
+            &self,
+            validate: &dyn Fn(Member) -> ValResult<Member>,
+        ) -> Option<Member> {
+            self.fill(validate)
+        }
+        fn edit_member_info(
+            &self,
+            _: &Member,
+            validate: &dyn Fn(Member) -> ValResult<Member>,
+        ) -> Option<Member> {
+            self.fill(validate)
+        }
+        fn display_inbox(&self, _: &Member, _: Vec<Notification>) {}
+        fn get_password(&self) -> String {
+            "secret".to_owned()
+        }
+        fn get_moderation(&self, _: usize) -> Option<(MemberStatus, String)> {
+            None
+        }
+        fn get_export_format(&self) -> Option<ExportFormat> {
+            None
+        }
+        fn select_member<'a>(&'a self, members: Vec<&'a Member>) -> Option<&'a Member> {
+            let email = self.selected.as_ref()?;
+            members.into_iter().find(|m| m.get_email() == email)
+        }
+        fn wait(&self, _: &str) {}
+    }
 
     #[test]
     fn test_new_creation() {
@@ -32,6 +112,28 @@ mod member_test {
         assert_eq!(bob.get_credits(), &0f64);
     }
 
+    #[test]
+    fn test_members_are_compared_by_id() {
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("40123456789".to_owned())
+            .build();
+        let moved = bob
+            .clone()
+            .email("robert@gmail.com".to_owned())
+            .phone_nr("40987654321".to_owned())
+            .build();
+        let twin = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("40123456789".to_owned())
+            .build();
+
+        assert_eq!(bob, moved);
+        assert_ne!(bob, twin);
+    }
+
     #[test]
     fn test_partial_builder_creation() {
         let name = "Bob".to_owned();
@@ -158,4 +260,171 @@ mod member_test {
         assert_eq!(allan.deduce_credits(-100f64).is_err(), true);
         assert_eq!(allan.deduce_credits(100f64).is_err(), true);
     }
+
+    #[test]
+    fn test_member_status() {
+        let mut allan = Member::default().name("Allan".to_owned()).build();
+        assert!(allan.is_active());
+
+        allan.suspend(5, "Late returns, twice");
+        assert_eq!(allan.get_status(), &MemberStatus::Suspended(5));
+        assert_eq!(allan.get_status_reason(), "Late returns  twice");
+        assert!(!allan.is_active());
+
+        assert!(!allan.expire_suspension(4));
+        assert!(allan.expire_suspension(5));
+        assert!(allan.is_active());
+        assert_eq!(allan.get_status_reason(), "");
+
+        allan.ban("Fraud");
+        assert!(!allan.expire_suspension(100));
+        assert_eq!(allan.get_status(), &MemberStatus::Banned);
+    }
+
+    #[test]
+    fn test_member_status_from_str() {
+        for status in [
+            MemberStatus::Active,
+            MemberStatus::Suspended(12),
+            MemberStatus::Banned,
+        ] {
+            assert_eq!(MemberStatus::from_str(&status.to_string()), Ok(status));
+        }
+        assert!(MemberStatus::from_str("sleeping").is_err());
+    }
+
+    #[test]
+    fn test_all_invalid_fields_are_reported() {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("abc".to_owned())
+            .phone_nr("abc1298374".to_owned())
+            .validate_and_build();
+        let expected = vec![
+            FieldError::new("email", MemValError::EmailPattern),
+            FieldError::new("phone_nr", MemValError::PhoneNumberContainsNonNumeric),
+        ];
+        assert_eq!(allan, Err(Check::Invalid(expected)));
+
+        let check = Member::default()
+            .email("allan@turing.com".to_owned())
+            .phone_nr("123".to_owned())
+            .validate()
+            .expect_err("Should fail");
+        assert!(!check.is_invalid("email"));
+        assert!(check.is_invalid("phone_nr"));
+        let error = &check.get_errors()[0];
+        assert_eq!(error.get_kind(), &MemValError::PhoneNumber);
+        assert_eq!(error.get_message(), "Invalid Phone number");
+        assert_eq!(check.to_string(), "phone_nr: Invalid Phone number");
+    }
+
+    #[test]
+    fn test_credentials_are_never_shown() {
+        let mut allan = Member::default().name("Allan".to_owned()).build();
+        allan.set_password("secret");
+        let (salt, hash) = (allan.get_salt().clone(), allan.get_password_hash().clone());
+        assert!(!salt.is_empty() && !hash.is_empty());
+
+        let shown = allan.to_string();
+        assert!(!shown.contains(&salt) && !shown.contains(&hash));
+        assert!(!shown.contains("salt") && !shown.contains("password_hash"));
+        let map = allan.to_map();
+        assert!(!map.contains_key("salt") && !map.contains_key("password_hash"));
+        assert!(map
+            .values()
+            .all(|v| !v.contains(&salt) && !v.contains(&hash)));
+        assert!(allan.verify_password("secret"));
+    }
+
+    #[test]
+    fn test_taken_email_is_asked_for_again() {
+        let mut system = System::new();
+        let mut admin = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("0123456789".to_owned())
+            .role(Role::Admin)
+            .uuid(system.generate_id())
+            .build();
+        admin.set_password("secret");
+        system.add_member(admin).expect("Should not fail");
+        system.login("allan@turing.com", "secret").expect("");
+
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .phone_nr("0987654321".to_owned());
+        let view = FormView::default();
+        view.options
+            .borrow_mut()
+            .push_back(MemberMenuOption::CreateMember);
+        view.inputs.borrow_mut().extend([
+            bob.clone().email("Allan@Turing.com".to_owned()).build(),
+            bob.email("bob@turing.com".to_owned()).build(),
+        ]);
+        let checks = Rc::clone(&view.checks);
+        MemberController::new(&mut system, view).show();
+
+        let checks = checks.borrow();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].is_invalid("email"));
+        assert!(!checks[0].is_invalid("phone_nr"));
+        assert_eq!(
+            checks[0].get_errors()[0].get_kind(),
+            &MemValError::NotUnique(UniqueField::Email)
+        );
+        assert_eq!(
+            checks[0].to_string(),
+            "email: A member with this email already exists."
+        );
+        assert_eq!(system.get_members().len(), 2);
+        assert!(system.get_member_by_email("bob@turing.com").is_ok());
+    }
+
+    #[test]
+    fn test_taken_email_is_asked_for_again_when_editing() {
+        let mut system = System::new();
+        let mut allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("0123456789".to_owned())
+            .uuid(system.generate_id())
+            .build();
+        allan.set_password("secret");
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@turing.com".to_owned())
+            .phone_nr("0987654321".to_owned())
+            .uuid(system.generate_id())
+            .build();
+        system.add_member(allan.clone()).expect("Should not fail");
+        system.add_member(bob).expect("Should not fail");
+        system.login("allan@turing.com", "secret").expect("");
+
+        let view = FormView {
+            selected: Some("allan@turing.com".to_owned()),
+            ..FormView::default()
+        };
+        view.options
+            .borrow_mut()
+            .push_back(MemberMenuOption::EditMember);
+        view.inputs.borrow_mut().extend([
+            allan.clone().email("Bob@Turing.com".to_owned()).build(),
+            allan.clone().email("allan@enigma.com".to_owned()).build(),
+        ]);
+        let checks = Rc::clone(&view.checks);
+        MemberController::new(&mut system, view).show();
+
+        let checks = checks.borrow();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].is_invalid("email"));
+        assert!(!checks[0].is_invalid("phone_nr"));
+        assert_eq!(
+            checks[0].to_string(),
+            "email: A member with this email already exists."
+        );
+        let edited = system.get_member(&allan).expect("");
+        assert_eq!(edited.get_email(), "allan@enigma.com");
+        assert_eq!(edited.get_phone_nr(), "0123456789");
+    }
 }
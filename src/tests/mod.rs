@@ -1,10 +1,50 @@
+/// Audit tests
+mod audit_tests;
+/// Auth tests
+mod auth_tests;
+/// Benchmark tests
+mod benchmark_tests;
+/// Command tests
+mod command_tests;
+/// Community tests
+mod community_tests;
+/// Contact tests
+mod contact_tests;
+/// Contract store tests
+mod contract_store_tests;
 /// CDate Tests
 mod date_wrapper_tests;
+/// Error tests
+mod error_tests;
+/// Export tests
+mod export_tests;
+/// Id tests
+mod id_tests;
+/// Interval tests
+mod interval_tests;
+/// Invariants tests
+mod invariants_tests;
 /// Item tests
 mod item_tests;
+/// Member index tests
+mod member_index_tests;
 /// Member tests
 mod member_tests;
+/// Notification tests
+mod notification_tests;
+/// Property tests
+mod property_tests;
+/// Report tests
+mod report_tests;
+/// Search tests
+mod search_tests;
+/// Simulation tests
+mod simulation_tests;
 /// System tests
 mod system_tests;
+/// Time tests
+mod time_tests;
+/// Transaction tests
+mod transaction_tests;
 /// Uuid tests
 mod uuid_tests;
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod notification_test {
+    use crate::models::domain::{
+        contract::Contract,
+        item::{Category, Item},
+        member::Member,
+        notification::{Event, NotificationService},
+        system::{LendingSystem, System},
+        time::{Listenable, Listener},
+    };
+    use std::{fs, path::PathBuf};
+
+    fn setup(service: NotificationService) -> (System, Member, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(60f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_listener(service);
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        system.add_item(item.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    fn subjects(system: &System, member: &Member) -> Vec<String> {
+        system
+            .get_notifications(member)
+            .iter()
+            .map(|n| n.get_subject().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_booking_requested() {
+        let (mut system, allan, bob, item) = setup(NotificationService::new());
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 4, 40f64);
+        system.add_contract(&item, contract, None).expect("");
+        assert_eq!(subjects(&system, &allan), vec!["Booking requested"]);
+        assert_eq!(subjects(&system, &bob), vec!["Booking confirmed"]);
+        assert_eq!(system.get_item(&item).unwrap().get_history().len(), 1);
+    }
+
+    #[test]
+    fn test_inboxes_do_not_depend_on_listeners() {
+        let (mut system, allan, bob, item) = setup(NotificationService::new());
+        system.add_listener(NotificationService::new());
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 4, 40f64);
+        system.add_contract(&item, contract, None).expect("");
+        assert_eq!(subjects(&system, &bob), vec!["Booking confirmed"]);
+
+        let mut quiet = System::new();
+        quiet.add_member(allan.clone()).expect("");
+        quiet.add_member(bob.clone()).expect("");
+        quiet.add_item(item.clone()).expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 4, 40f64);
+        quiet.add_contract(&item, contract, None).expect("");
+        assert_eq!(subjects(&quiet, &allan), vec!["Booking requested"]);
+    }
+
+    #[test]
+    fn test_reminders_while_advancing_time() {
+        let (mut system, allan, bob, item) = setup(NotificationService::new());
+        let contract = Contract::new(allan.clone(), bob.clone(), 2, 4, 40f64);
+        system.add_contract(&item, contract, None).expect("");
+        for _ in 0..6 {
+            system.incr_time().expect("");
+        }
+
+        let bob_subjects = subjects(&system, &bob);
+        assert_eq!(
+            bob_subjects,
+            vec![
+                "Booking confirmed",
+                "Booking starts tomorrow",
+                "Credits charged",
+                "Credits charged",
+                "Low balance",
+                "Credits charged",
+                "Low balance",
+                "Return due",
+            ]
+        );
+        assert_eq!(
+            subjects(&system, &allan),
+            vec!["Booking requested", "Booking starts tomorrow"]
+        );
+        let last = system.get_notifications(&bob).pop().unwrap();
+        assert_eq!(last.get_day(), 6);
+    }
+
+    #[test]
+    fn test_pull_last_event() {
+        let (allan, bob) = {
+            let (_, a, b, _) = setup(NotificationService::new());
+            (a, b)
+        };
+        let mut service = NotificationService::new();
+        assert_eq!(service.pull(), None);
+        let event = Event::LowBalance {
+            day: 3,
+            member: bob.clone(),
+        };
+        service.update(event.clone());
+        assert_eq!(service.pull(), Some(event));
+        assert_eq!(service.get_inbox(&bob).len(), 1);
+        assert!(service.get_inbox(&allan).is_empty());
+    }
+
+    #[test]
+    fn test_outbox() {
+        let dir = std::env::temp_dir().join(format!("outbox-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let (mut system, allan, bob, item) =
+            setup(NotificationService::with_outbox(PathBuf::from(&dir)));
+        let contract = Contract::new(allan, bob, 2, 4, 40f64);
+        system.add_contract(&item, contract, None).expect("");
+
+        let files = fs::read_dir(&dir).unwrap().count();
+        assert_eq!(files, 2);
+        let rendered = fs::read_dir(&dir)
+            .unwrap()
+            .map(|f| fs::read_to_string(f.unwrap().path()).unwrap())
+            .collect::<Vec<String>>();
+        assert!(rendered
+            .iter()
+            .any(|r| r.contains("To: Bob <bob@gmail.com>") && r.contains("Booking confirmed")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,369 @@
+#[cfg(test)]
+mod property_test {
+    use crate::models::{
+        domain::{
+            contract::{Contract, Status},
+            item::{Category, Item},
+            member::Member,
+            system::{LendingSystem, System},
+        },
+        id::ContractId,
+    };
+    use proptest::{prelude::*, test_runner::TestCaseError};
+    use std::collections::HashMap;
+
+    /// An operation on the lending system.
+    ///
+    /// Members and items are picked by their index among all members and items ever
+    /// created, so operations on removed ones are generated as well.
+    #[derive(Debug, Clone)]
+    enum Op {
+        AddMember {
+            credits: u8,
+        },
+        RemoveMember(usize),
+        AddItem {
+            owner: usize,
+            cost: u8,
+        },
+        RemoveItem(usize),
+        Book {
+            item: usize,
+            lendee: usize,
+            start: usize,
+            len: usize,
+        },
+        AdvanceTime(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            3 => any::<u8>().prop_map(|credits| Op::AddMember { credits }),
+            1 => (0..16usize).prop_map(Op::RemoveMember),
+            3 => (0..16usize, 1..30u8).prop_map(|(owner, cost)| Op::AddItem { owner, cost }),
+            1 => (0..16usize).prop_map(Op::RemoveItem),
+            4 => (0..16usize, 0..16usize, 0..5usize, 1..6usize).prop_map(
+                |(item, lendee, start, len)| Op::Book { item, lendee, start, len }
+            ),
+            3 => (1..4usize).prop_map(Op::AdvanceTime),
+        ]
+    }
+
+    struct RefMember {
+        member: Member,
+        /// `None` once the member has been removed.
+        credits: Option<f64>,
+    }
+
+    struct RefItem {
+        item: Item,
+        owner: usize,
+        listed: bool,
+    }
+
+    struct Booking {
+        contract: ContractId,
+        item: usize,
+        lendee: usize,
+        start: usize,
+        end: usize,
+    }
+
+    impl Booking {
+        /// A booking is active from its start until its end, also when it starts on
+        /// the day it is booked.
+        fn status(&self, day: usize) -> Status {
+            match day {
+                day if day < self.start => Status::Future,
+                day if day < self.end => Status::Active,
+                _ => Status::Finished,
+            }
+        }
+    }
+
+    /// A simplified lending system the real one is compared against.
+    #[derive(Default)]
+    struct Reference {
+        members: Vec<RefMember>,
+        items: Vec<RefItem>,
+        bookings: Vec<Booking>,
+        /// Pending payouts as (day, order of scheduling, booking).
+        payouts: Vec<(usize, usize, usize)>,
+        seq: usize,
+        day: usize,
+    }
+
+    impl Reference {
+        /// Applies an operation to the system and to the reference and compares the outcome.
+        fn apply(&mut self, sys: &mut System, op: &Op) -> Result<(), TestCaseError> {
+            match *op {
+                Op::AddMember { credits } => {
+                    let n = self.members.len();
+                    let member = Member::new(
+                        format!("Member {}", n),
+                        format!("member{}@lending.com", n),
+                        format!("0123{:06}", n),
+                        self.day,
+                    )
+                    .expect("Generated members are valid")
+                    .credits(credits as f64)
+                    .build();
+                    prop_assert!(sys.add_member(member.clone()).is_ok());
+                    self.members.push(RefMember {
+                        member,
+                        credits: Some(credits as f64),
+                    });
+                }
+                Op::RemoveMember(idx) => {
+                    if self.members.is_empty() {
+                        return Ok(());
+                    }
+                    let idx = idx % self.members.len();
+                    // Owners of listed items and lendees of bookings that have not
+                    // finished yet have to stay.
+                    let owns = self.items.iter().any(|i| i.listed && i.owner == idx);
+                    let borrows = self
+                        .bookings
+                        .iter()
+                        .any(|b| b.lendee == idx && b.status(self.day) != Status::Finished);
+                    let expected = self.members[idx].credits.is_some() && !owns && !borrows;
+                    let result = sys.remove_member(&self.members[idx].member);
+                    prop_assert_eq!(result.is_ok(), expected);
+                    if expected {
+                        self.members[idx].credits = None;
+                    }
+                }
+                Op::AddItem { owner, cost } => {
+                    if self.members.is_empty() {
+                        return Ok(());
+                    }
+                    let owner = owner % self.members.len();
+                    let item = Item::new(
+                        format!("Item {}", self.items.len()),
+                        "Generated".to_owned(),
+                        Category::Game,
+                        self.members[owner].member.clone(),
+                        cost as f64,
+                        self.day,
+                    );
+                    let listed = self.members[owner].credits.is_some();
+                    prop_assert_eq!(sys.add_item(item.clone()).is_ok(), listed);
+                    if let Some(credits) = self.members[owner].credits.as_mut() {
+                        *credits += 100f64;
+                    }
+                    self.items.push(RefItem {
+                        item,
+                        owner,
+                        listed,
+                    });
+                }
+                Op::RemoveItem(idx) => {
+                    if self.items.is_empty() {
+                        return Ok(());
+                    }
+                    let idx = idx % self.items.len();
+                    let future = self
+                        .bookings
+                        .iter()
+                        .any(|b| b.item == idx && b.status(self.day) != Status::Finished);
+                    let result = sys.remove_item(&self.items[idx].item);
+                    prop_assert_eq!(result.is_ok(), self.items[idx].listed && !future);
+                    if future {
+                        return Ok(());
+                    }
+                    self.items[idx].listed = false;
+                    let bookings = &self.bookings;
+                    self.payouts.retain(|(_, _, b)| bookings[*b].item != idx);
+                }
+                Op::Book {
+                    item,
+                    lendee,
+                    start,
+                    len,
+                } => {
+                    if self.items.is_empty() || self.members.is_empty() {
+                        return Ok(());
+                    }
+                    let (item, lendee) = (item % self.items.len(), lendee % self.members.len());
+                    let (start, end) = (self.day + start, self.day + start + len);
+                    let cost = *self.items[item].item.get_cost_per_day() * len as f64;
+                    let free = !self
+                        .bookings
+                        .iter()
+                        .any(|b| b.item == item && start < b.end && b.start < end);
+                    let expected = self.items[item].listed
+                        && free
+                        && self.members[lendee]
+                            .credits
+                            .is_some_and(|credits| credits >= cost);
+
+                    let member = &self.members[lendee].member;
+                    let lendee_info = sys.get_member(member).unwrap_or(member.clone());
+                    let owner = self.members[self.items[item].owner].member.clone();
+                    let contract = Contract::new(owner, lendee_info, start, len, cost);
+                    let uuid = contract.get_uuid().clone();
+                    let result = sys.add_contract(&self.items[item].item, contract, None);
+                    prop_assert_eq!(result.is_ok(), expected);
+                    if expected {
+                        self.bookings.push(Booking {
+                            contract: uuid,
+                            item,
+                            lendee,
+                            start,
+                            end,
+                        });
+                        if start + 1 < end {
+                            self.schedule(start + 1, self.bookings.len() - 1);
+                        }
+                    }
+                }
+                Op::AdvanceTime(days) => {
+                    for _ in 0..days {
+                        let result = sys.incr_time();
+                        prop_assert_eq!(result.is_ok(), self.incr_time());
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        fn schedule(&mut self, day: usize, booking: usize) {
+            self.seq += 1;
+            self.payouts.push((day, self.seq, booking));
+        }
+
+        /// Advances by one day and transfers the credits of the due payouts.
+        /// Returns whether every payout succeeded.
+        fn incr_time(&mut self) -> bool {
+            self.day += 1;
+            let day = self.day;
+            let (mut due, later): (Vec<_>, Vec<_>) =
+                self.payouts.drain(..).partition(|(d, _, _)| *d == day);
+            self.payouts = later;
+            due.sort_by_key(|(_, seq, _)| *seq);
+
+            let mut ok = true;
+            for (_, _, b) in due {
+                let (lendee, end) = (self.bookings[b].lendee, self.bookings[b].end);
+                let item = &self.items[self.bookings[b].item];
+                let (owner, cost) = (item.owner, *item.item.get_cost_per_day());
+                if day + 1 < end {
+                    self.schedule(day + 1, b);
+                }
+                let paid = match (self.members[lendee].credits, self.members[owner].credits) {
+                    (Some(credits), Some(_)) => credits >= cost,
+                    _ => false,
+                };
+                if paid {
+                    *self.members[lendee].credits.as_mut().unwrap() -= cost;
+                    *self.members[owner].credits.as_mut().unwrap() += cost;
+                }
+                ok &= paid;
+            }
+            ok
+        }
+
+        /// Compares the state of the system with the reference and checks that it breaks
+        /// none of its invariants.
+        fn check(&self, sys: &System) -> Result<(), TestCaseError> {
+            prop_assert_eq!(sys.now(), self.day);
+
+            let credits = sys
+                .get_members()
+                .into_iter()
+                .map(|m| (m.get_uuid().get_value().clone(), *m.get_credits()))
+                .collect::<HashMap<String, f64>>();
+            let expected = self
+                .members
+                .iter()
+                .filter_map(|m| Some((m.member.get_uuid().get_value().clone(), m.credits?)))
+                .collect::<HashMap<String, f64>>();
+            prop_assert_eq!(credits, expected);
+
+            let contracts = sys
+                .get_items()
+                .into_iter()
+                .map(|i| (i.get_uuid().get_value().clone(), i.get_history().len()))
+                .collect::<HashMap<String, usize>>();
+            let expected = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, i)| i.listed)
+                .map(|(idx, i)| {
+                    let booked = self.bookings.iter().filter(|b| b.item == idx).count();
+                    (i.item.get_uuid().get_value().clone(), booked)
+                })
+                .collect::<HashMap<String, usize>>();
+            prop_assert_eq!(contracts, expected);
+
+            for booking in self.bookings.iter() {
+                if self.items[booking.item].listed {
+                    let contract = sys.get_contract_by_id(&booking.contract);
+                    prop_assert_eq!(
+                        contract.map(|c| *c.get_status()),
+                        Ok(booking.status(self.day))
+                    );
+                }
+            }
+
+            prop_assert_eq!(sys.check_invariants(), vec![]);
+            Ok(())
+        }
+    }
+
+    fn run(ops: &[Op]) -> Result<(), TestCaseError> {
+        let mut system = System::new();
+        let mut reference = Reference::default();
+        for op in ops {
+            reference.apply(&mut system, op)?;
+            reference.check(&system)?;
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(200))]
+
+        #[test]
+        fn test_system_matches_reference(ops in prop::collection::vec(op(), 1..60)) {
+            run(&ops)?;
+        }
+    }
+
+    #[test]
+    fn test_owner_stays_while_lending() {
+        let ops = vec![
+            Op::AddMember { credits: 0 },
+            Op::AddMember { credits: 50 },
+            Op::AddItem { owner: 0, cost: 10 },
+            Op::Book {
+                item: 0,
+                lendee: 1,
+                start: 0,
+                len: 3,
+            },
+            Op::RemoveMember(0),
+            Op::AdvanceTime(3),
+        ];
+        run(&ops).expect("The owner of a listed item cannot leave");
+    }
+
+    #[test]
+    fn test_booking_starting_today_is_active() {
+        let ops = vec![
+            Op::AddMember { credits: 50 },
+            Op::AddItem { owner: 0, cost: 10 },
+            Op::Book {
+                item: 0,
+                lendee: 0,
+                start: 0,
+                len: 2,
+            },
+            Op::RemoveItem(0),
+            Op::AdvanceTime(2),
+            Op::RemoveItem(0),
+        ];
+        run(&ops).expect("The booking is active from the day it is booked");
+    }
+}
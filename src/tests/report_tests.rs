@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod report_test {
+    use crate::models::domain::{
+        contract::{Contract, Status},
+        item::{Category, Item},
+        member::Member,
+        period::Period,
+        report::{ReportKind, Reports},
+        system::{LendingSystem, System},
+    };
+
+    /// Allan lists Monopoly for 20 credits a day, which Bob books from day 1 to day 5.
+    fn booked(bob_credits: f64) -> (System, Item) {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(bob_credits)
+            .build();
+        let monopoly = Item::default()
+            .name("Monopoly".to_owned())
+            .category(Category::Game)
+            .cost_per_day(20f64)
+            .owner(allan.clone());
+        let mut sys = System::new();
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(monopoly.clone()).expect("");
+        let contract = Contract::new(allan, bob, 1, 4, 20f64);
+        sys.add_contract(&monopoly, contract, None).expect("");
+        (sys, monopoly)
+    }
+
+    fn setup() -> System {
+        let (mut sys, monopoly) = booked(300f64);
+        let drill = Item::default()
+            .name("Drill".to_owned())
+            .category(Category::Tool)
+            .cost_per_day(10f64)
+            .owner(monopoly.get_owner().clone());
+        sys.add_item(drill).expect("");
+        for _ in 0..6 {
+            sys.incr_time().expect("");
+        }
+        sys
+    }
+
+    fn rows(sys: &System, period: Period, kind: ReportKind) -> Vec<Vec<String>> {
+        Reports::new(sys, period).section(kind).get_rows().clone()
+    }
+
+    fn row(values: Vec<&str>) -> Vec<String> {
+        values.into_iter().map(|v| v.to_owned()).collect()
+    }
+
+    #[test]
+    fn test_utilization() {
+        let sys = setup();
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::Utilization),
+            vec![
+                row(vec!["Drill", "Allan", "0", "10", "0.0%"]),
+                row(vec!["Monopoly", "Allan", "4", "10", "40.0%"]),
+            ]
+        );
+        assert_eq!(
+            rows(&sys, Period::new(3, 5), ReportKind::Utilization)[1],
+            row(vec!["Monopoly", "Allan", "2", "2", "100.0%"])
+        );
+    }
+
+    #[test]
+    fn test_revenue() {
+        let sys = setup();
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::ItemRevenue)[0],
+            row(vec!["Monopoly", "Allan", "60"])
+        );
+        assert_eq!(
+            rows(&sys, Period::new(3, 4), ReportKind::ItemRevenue)[0],
+            row(vec!["Monopoly", "Allan", "20"])
+        );
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::OwnerRevenue),
+            vec![row(vec!["Allan", "2", "60"])]
+        );
+    }
+
+    #[test]
+    fn test_top_borrowers() {
+        let sys = setup();
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::TopBorrowers),
+            vec![row(vec!["Bob", "1", "4", "60"])]
+        );
+        assert!(rows(&sys, Period::new(5, 10), ReportKind::TopBorrowers).is_empty());
+    }
+
+    #[test]
+    fn test_credit_distribution() {
+        let sys = setup();
+        let distribution = rows(&sys, Period::new(0, 10), ReportKind::CreditDistribution);
+        assert_eq!(distribution.len(), 5);
+        assert_eq!(distribution[3], row(vec!["200 to 500", "2", "100.0%"]));
+    }
+
+    #[test]
+    fn test_category_popularity() {
+        let sys = setup();
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::CategoryPopularity),
+            vec![
+                row(vec!["Game", "1", "1", "4"]),
+                row(vec!["Tool", "1", "0", "0"])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canceled_contracts_are_left_out() {
+        let mut sys = setup();
+        let mut drill = sys
+            .get_items()
+            .into_iter()
+            .find(|i| i.get_name() == "Drill")
+            .cloned()
+            .unwrap();
+        let bob = sys.get_member_by_email("bob@gmail.com").unwrap();
+        let canceled = Contract::new(drill.get_owner().clone(), bob, 1, 4, 40f64)
+            .status(Status::Canceled)
+            .build();
+        drill.add_contract(canceled).expect("");
+        sys.update_item(&drill).expect("");
+
+        let period = Period::new(0, 10);
+        assert_eq!(
+            rows(&sys, period, ReportKind::Utilization)[0],
+            row(vec!["Drill", "Allan", "0", "10", "0.0%"])
+        );
+        assert_eq!(
+            rows(&sys, period, ReportKind::ItemRevenue)[1],
+            row(vec!["Drill", "Allan", "0"])
+        );
+        assert_eq!(
+            rows(&sys, period, ReportKind::OwnerRevenue),
+            vec![row(vec!["Allan", "2", "60"])]
+        );
+        assert_eq!(
+            rows(&sys, period, ReportKind::TopBorrowers),
+            vec![row(vec!["Bob", "1", "4", "60"])]
+        );
+        assert_eq!(
+            rows(&sys, period, ReportKind::CategoryPopularity)[1],
+            row(vec!["Tool", "1", "0", "0"])
+        );
+    }
+
+    #[test]
+    fn test_failed_payouts_earn_nothing() {
+        // Bob can pay for the first day only.
+        let (mut sys, _) = booked(30f64);
+        for _ in 0..6 {
+            let _ = sys.incr_time();
+        }
+        let period = Period::new(0, 10);
+        assert_eq!(
+            rows(&sys, period, ReportKind::ItemRevenue),
+            vec![row(vec!["Monopoly", "Allan", "20"])]
+        );
+        assert_eq!(
+            rows(&sys, period, ReportKind::TopBorrowers),
+            vec![row(vec!["Bob", "1", "4", "20"])]
+        );
+    }
+
+    #[test]
+    fn test_price_changes_keep_past_revenue() {
+        let (mut sys, monopoly) = booked(300f64);
+        sys.incr_time().expect("");
+        sys.incr_time().expect("");
+        let pricier = sys
+            .get_item(&monopoly)
+            .expect("")
+            .cost_per_day(50f64)
+            .build();
+        sys.update_item(&pricier).expect("");
+        for _ in 0..4 {
+            sys.incr_time().expect("");
+        }
+
+        let bob = sys.get_member_by_email("bob@gmail.com").unwrap();
+        assert_eq!(*bob.get_credits(), 180f64);
+        assert_eq!(
+            rows(&sys, Period::new(0, 10), ReportKind::ItemRevenue),
+            vec![row(vec!["Monopoly", "Allan", "120"])]
+        );
+        assert_eq!(
+            rows(&sys, Period::new(0, 3), ReportKind::ItemRevenue),
+            vec![row(vec!["Monopoly", "Allan", "20"])]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod search_test {
+    use crate::models::domain::{
+        item::{Category, Item, Tags},
+        member::Member,
+        search::tokenize,
+        system::{LendingSystem, System},
+    };
+    use std::str::FromStr;
+
+    fn setup() -> (System, Vec<Item>) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        let items = vec![
+            Item::new(
+                "Drill".to_owned(),
+                "Drill with cable".to_owned(),
+                Category::Tool,
+                allan.clone(),
+                10f64,
+                0,
+            ),
+            Item::new(
+                "Cordless Drill".to_owned(),
+                "Battery powered drill".to_owned(),
+                Category::Tool,
+                allan.clone(),
+                20f64,
+                0,
+            )
+            .tags(Tags::new(vec!["cordless", "power"])),
+            Item::new(
+                "Screwdriver".to_owned(),
+                "Cordless screwdriver".to_owned(),
+                Category::Tool,
+                allan.clone(),
+                5f64,
+                0,
+            ),
+            Item::new(
+                "Monopoly".to_owned(),
+                "Family Game".to_owned(),
+                Category::Game,
+                allan.clone(),
+                5f64,
+                0,
+            ),
+        ];
+
+        let mut system = System::new();
+        system.add_member(allan).expect("");
+        for item in items.iter() {
+            system.add_item(item.clone()).expect("");
+        }
+        (system, items)
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Cordless-Drill, 18V!"),
+            vec!["cordless".to_owned(), "drill".to_owned(), "18v".to_owned()]
+        );
+        assert!(tokenize("  ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let tags = Tags::from_str("Power, tool  power").unwrap();
+        assert_eq!(tags, Tags::new(vec!["power", "tool"]));
+        assert_eq!(tags.to_string(), "power tool");
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let (system, items) = setup();
+        let results = system.search_items("cordless drill");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].get_uuid(), items[1].get_uuid());
+        assert!(results.iter().all(|i| i.get_uuid() != items[3].get_uuid()));
+        assert!(system.search_items("bicycle").is_empty());
+    }
+
+    #[test]
+    fn test_search_after_update_and_remove() {
+        let (mut system, items) = setup();
+        let renamed = items[3].clone().name("Bicycle".to_owned()).build();
+        system.update_item(&renamed).expect("");
+        assert!(system.search_items("monopoly").is_empty());
+        assert_eq!(system.search_items("bicycle").len(), 1);
+
+        system.remove_item(&renamed).expect("");
+        assert!(system.search_items("bicycle").is_empty());
+    }
+}
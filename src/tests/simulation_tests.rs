@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod simulation_test {
+    use crate::models::domain::{
+        contract::{Contract, Status},
+        item::Item,
+        member::Member,
+        simulation::{simulate, Advance, StopCondition, MAX_SIMULATED_DAYS},
+        system::{LendingSystem, System},
+    };
+
+    fn setup() -> (System, Member, Member) {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(100f64)
+            .build();
+        let monopoly = Item::default()
+            .name("Monopoly".to_owned())
+            .cost_per_day(20f64)
+            .owner(allan.clone());
+        let mut sys = System::new();
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(monopoly.clone()).expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 5, 100f64);
+        sys.add_contract(&monopoly, contract, None).expect("");
+        (sys, allan, bob)
+    }
+
+    #[test]
+    fn test_advance_days() {
+        let (mut sys, allan, _) = setup();
+        let result = simulate(&mut sys, &Advance::Days(3));
+        assert_eq!(sys.now(), 3);
+        assert_eq!(result.get_days().len(), 3);
+        assert!(!result.get_condition_met());
+
+        let day3 = &result.get_days()[2];
+        assert_eq!(*day3.get_day(), 3);
+        assert_eq!(*day3.get_active_contracts(), 1);
+        assert_eq!(*day3.get_credits_transferred(), 20f64);
+        assert_eq!(day3.get_lowest_balance(), &Some(("Bob".to_owned(), 60f64)));
+        assert_eq!(sys.get_member(&allan).unwrap().get_credits(), &140f64);
+    }
+
+    #[test]
+    fn test_advance_to_day() {
+        let (mut sys, _, _) = setup();
+        simulate(&mut sys, &Advance::ToDay(4));
+        assert_eq!(sys.now(), 4);
+        // Days in the past do not move the time.
+        assert!(simulate(&mut sys, &Advance::ToDay(2)).get_days().is_empty());
+        assert_eq!(sys.now(), 4);
+    }
+
+    #[test]
+    fn test_until_contract_ends() {
+        let (mut sys, _, _) = setup();
+        let result = simulate(&mut sys, &Advance::Until(StopCondition::ContractEnds));
+        assert!(result.get_condition_met());
+        assert_eq!(sys.now(), 6);
+        assert_eq!(*result.get_days()[5].get_ended_contracts(), 1);
+    }
+
+    #[test]
+    fn test_until_balance_below() {
+        let (mut sys, _, bob) = setup();
+        let condition = StopCondition::BalanceBelow(bob.get_uuid().clone(), 50f64);
+        let result = simulate(&mut sys, &Advance::Until(condition));
+        assert!(result.get_condition_met());
+        assert_eq!(sys.now(), 4);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &40f64);
+    }
+
+    #[test]
+    fn test_until_settlement_failure() {
+        let (mut sys, _, bob) = setup();
+        let poor = sys.get_member(&bob).unwrap().credits(30f64).build();
+        sys.update_member(&bob, &poor).expect("");
+        let result = simulate(&mut sys, &Advance::Until(StopCondition::SettlementFailure));
+        assert!(result.get_condition_met());
+        assert_eq!(sys.now(), 3);
+        assert!(!result.get_days()[2].get_settled());
+    }
+
+    #[test]
+    fn test_condition_never_met() {
+        let (mut sys, _, _) = setup();
+        let result = simulate(&mut sys, &Advance::Until(StopCondition::SettlementFailure));
+        assert!(!result.get_condition_met());
+        assert_eq!(sys.now(), MAX_SIMULATED_DAYS);
+    }
+
+    #[test]
+    fn test_canceled_contracts_are_not_counted() {
+        let (mut sys, _, _) = setup();
+        let mut item = sys.get_items()[0].clone();
+        let contract = item.get_history().iter().next().cloned().unwrap();
+        item.update_contract(&contract.status(Status::Canceled).build())
+            .expect("");
+        sys.update_item(&item).expect("");
+
+        let result = simulate(&mut sys, &Advance::Days(6));
+        assert!(result
+            .get_days()
+            .iter()
+            .all(|d| *d.get_active_contracts() == 0 && *d.get_ended_contracts() == 0));
+    }
+}
@@ -1,13 +1,15 @@
 #[cfg(test)]
 mod system_tests {
     use crate::{
-        errors::SysError,
+        errors::{MemValError, SysError},
         models::domain::{
-            contract::Contract,
+            contract::{Contract, Status},
             item::{Category, Item},
-            member::Member,
+            member::{Member, MemberStatus},
+            member_index::UniqueField,
             system::{LendingSystem, System},
         },
+        models::id::ContractId,
     };
 
     #[test]
@@ -46,7 +48,12 @@ mod system_tests {
         let r0 = system.add_member(allan);
         assert_eq!(r0, Ok(()));
         let r1 = system.add_member(turing1);
-        assert_eq!(r1, Err(SysError::AlreadyExists));
+        assert_eq!(
+            r1,
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::Email
+            )))
+        );
     }
 
     #[test]
@@ -71,7 +78,12 @@ mod system_tests {
         let r0 = system.add_member(allan);
         assert_eq!(r0, Ok(()));
         let r1 = system.add_member(turing2);
-        assert_eq!(r1, Err(SysError::AlreadyExists));
+        assert_eq!(
+            r1,
+            Err(SysError::Validation(MemValError::NotUnique(
+                UniqueField::PhoneNr
+            )))
+        );
     }
 
     #[test]
@@ -317,4 +329,207 @@ mod system_tests {
         assert_eq!(sys.get_member(&allan).unwrap().get_credits(), &200f64);
         assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &200f64);
     }
+
+    #[test]
+    fn test_advance_time_bills_per_unit() {
+        let mut sys = System::new();
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(300f64)
+            .build();
+        let mut tables = Item::default()
+            .name("Folding Table".to_owned())
+            .cost_per_day(10f64)
+            .quantity(3)
+            .owner(allan.clone());
+
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(50f64)
+            .from_date(0, 6)
+            .build();
+
+        tables.add_contract_for_unit(contract.clone(), 0).expect("");
+        tables
+            .add_contract_for_unit(contract.uuid(ContractId::new()).build(), 2)
+            .expect("");
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(tables).expect("");
+
+        sys.incr_time().expect("");
+
+        assert_eq!(sys.get_member(&allan).unwrap().get_credits(), &120f64);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &280f64);
+    }
+
+    #[test]
+    fn test_update_item_keeps_booked_units() {
+        let mut sys = System::new();
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(300f64)
+            .build();
+        let mut tables = Item::default()
+            .name("Folding Table".to_owned())
+            .cost_per_day(10f64)
+            .quantity(4)
+            .owner(allan.clone());
+        let contract = Contract::default()
+            .owner(allan.clone())
+            .lendee(bob.clone())
+            .credits(50f64)
+            .from_date(0, 6)
+            .build();
+        tables.add_contract_for_unit(contract.clone(), 1).expect("");
+        let canceled = contract
+            .uuid(ContractId::new())
+            .status(Status::Canceled)
+            .build();
+        tables.add_contract_for_unit(canceled, 3).expect("");
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(tables.clone()).expect("");
+
+        let resize = |sys: &mut System, quantity: usize| {
+            let item = sys.get_item(&tables).unwrap();
+            sys.update_item(&item.quantity(quantity).build())
+        };
+        assert_eq!(resize(&mut sys, 0), Err(SysError::Quantity(2)));
+        assert_eq!(resize(&mut sys, 1), Err(SysError::Quantity(2)));
+        assert_eq!(*sys.get_item(&tables).unwrap().get_quantity(), 4);
+        assert_eq!(resize(&mut sys, 2), Ok(()));
+        assert_eq!(*sys.get_item(&tables).unwrap().get_quantity(), 2);
+    }
+
+    #[test]
+    fn test_suspension() {
+        let mut sys = System::new();
+        let mut allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        allan.set_password("secret");
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "A beautiful Family Game.".to_owned(),
+            Category::Game,
+            allan.clone(),
+            20f64,
+            0,
+        );
+        sys.add_member(allan.clone()).expect("");
+        sys.add_item(item).expect("");
+        assert_eq!(sys.get_bookable_items().len(), 1);
+
+        let mut suspended = sys.get_member(&allan).unwrap();
+        suspended.suspend(2, "Damaged an item");
+        sys.update_member(&allan, &suspended).expect("");
+        assert!(sys.get_bookable_items().is_empty());
+
+        sys.incr_time().expect("");
+        assert!(!sys.get_member(&allan).unwrap().is_active());
+        sys.incr_time().expect("");
+        assert!(sys.get_member(&allan).unwrap().is_active());
+        assert_eq!(sys.get_bookable_items().len(), 1);
+    }
+
+    #[test]
+    fn test_restricted_members_cannot_book() {
+        let mut sys = System::new();
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "A beautiful Family Game.".to_owned(),
+            Category::Game,
+            allan.clone(),
+            20f64,
+            0,
+        );
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(item.clone()).expect("");
+        let book = |sys: &mut System| {
+            let contract = Contract::new(allan.clone(), bob.clone(), 1, 1, 20f64);
+            sys.add_contract(&item, contract, None)
+        };
+
+        let mut banned = sys.get_member(&bob).unwrap();
+        banned.ban("Fraud");
+        sys.update_member(&bob, &banned).expect("");
+        assert_eq!(
+            book(&mut sys),
+            Err(SysError::Restricted(MemberStatus::Banned))
+        );
+
+        let mut active = sys.get_member(&bob).unwrap();
+        active.reinstate();
+        sys.update_member(&bob, &active).expect("");
+        let mut suspended = sys.get_member(&allan).unwrap();
+        suspended.suspend(3, "Damaged an item");
+        sys.update_member(&allan, &suspended).expect("");
+        assert_eq!(
+            book(&mut sys),
+            Err(SysError::Restricted(MemberStatus::Suspended(3)))
+        );
+        assert!(sys.get_item(&item).unwrap().get_history().is_empty());
+
+        let mut active = sys.get_member(&allan).unwrap();
+        active.reinstate();
+        sys.update_member(&allan, &active).expect("");
+        assert_eq!(book(&mut sys), Ok(()));
+    }
+
+    #[test]
+    fn test_banned_member_cannot_login() {
+        let mut sys = System::new();
+        let mut allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail");
+        allan.set_password("secret");
+        allan.ban("Fraud");
+        sys.add_member(allan).expect("");
+
+        assert_eq!(
+            sys.login("allan@enigma.com", "secret").err(),
+            Some(SysError::Banned)
+        );
+    }
 }
@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod time_test {
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            command::EditContract,
+            contract::{Contract, Status},
+            item::Item,
+            member::Member,
+            system::{LendingSystem, System},
+            time::{Job, Time},
+        },
+        models::id::{ContractId, ItemId, MemberId},
+        types::Demo,
+    };
+
+    fn setup() -> (System, Member, Member, Item) {
+        let allan = Member::default()
+            .name("Allan".to_owned())
+            .email("allan@turing.com".to_owned())
+            .phone_nr("4602134567".to_owned())
+            .build();
+        let bob = Member::default()
+            .name("Bob".to_owned())
+            .email("bob@gmail.com".to_owned())
+            .phone_nr("46291328475".to_owned())
+            .credits(300f64)
+            .build();
+        let monopoly = Item::default()
+            .name("Monopoly".to_owned())
+            .cost_per_day(20f64)
+            .owner(allan.clone());
+        let mut sys = System::new();
+        sys.add_member(allan.clone()).expect("");
+        sys.add_member(bob.clone()).expect("");
+        sys.add_item(monopoly.clone()).expect("");
+        (sys, allan, bob, monopoly)
+    }
+
+    fn status(sys: &System, contract: &Contract) -> Status {
+        *sys.get_contract(contract).unwrap().get_status()
+    }
+
+    #[test]
+    fn test_dispatch_order() {
+        let mut time = Time::new(0);
+        let (item, member) = (ItemId::new(), MemberId::new());
+        let payout = Job::Payout {
+            item: item.clone(),
+            contract: ContractId::new(),
+        };
+        let start = Job::StartContract {
+            item: item.clone(),
+            contract: ContractId::new(),
+        };
+        let expire = Job::ExpireSuspension {
+            member: member.clone(),
+        };
+        time.schedule(2, payout.clone()).expect("");
+        time.schedule(1, payout.clone()).expect("");
+        time.schedule(1, start.clone()).expect("");
+        time.schedule(1, expire.clone()).expect("");
+
+        assert_eq!(time.incr_day(), vec![expire, start, payout.clone()]);
+        assert_eq!(time.now(), 1);
+        assert_eq!(time.pending(), vec![(2, &payout)]);
+        assert_eq!(time.incr_day(), vec![payout]);
+        assert!(time.incr_day().is_empty());
+    }
+
+    #[test]
+    fn test_schedule_in_the_past() {
+        let mut time = Time::new(3);
+        let job = Job::ExpireSuspension {
+            member: MemberId::new(),
+        };
+        assert_eq!(time.schedule(3, job.clone()), Err(SysError::InvalidInput));
+        assert_eq!(time.schedule(4, job), Ok(()));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut time = Time::new(0);
+        let (item, member) = (ItemId::new(), MemberId::new());
+        time.schedule(
+            1,
+            Job::Payout {
+                item: item.clone(),
+                contract: ContractId::new(),
+            },
+        )
+        .expect("");
+        time.schedule(1, Job::ExpireSuspension { member })
+            .expect("");
+        assert_eq!(time.cancel(|job| job.is_for_item(&item)), 1);
+        assert_eq!(time.pending().len(), 1);
+    }
+
+    #[test]
+    fn test_contract_status_changes() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        let contract = Contract::new(allan, bob.clone(), 2, 3, 60f64);
+        sys.add_contract(&monopoly, contract.clone(), None)
+            .expect("");
+        assert_eq!(sys.get_scheduled_jobs().len(), 4);
+
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Future);
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Active);
+        sys.incr_time().expect("");
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Active);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &260f64);
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Finished);
+        assert!(sys.get_scheduled_jobs().is_empty());
+    }
+
+    #[test]
+    fn test_contract_starting_today_is_active() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        sys.incr_time().expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 2, 40f64);
+        sys.add_contract(&monopoly, contract.clone(), None)
+            .expect("");
+        assert_eq!(status(&sys, &contract), Status::Active);
+        assert_eq!(sys.get_active_contracts().len(), 1);
+
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Active);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &280f64);
+        sys.incr_time().expect("");
+        assert_eq!(status(&sys, &contract), Status::Finished);
+        assert!(sys.get_active_contracts().is_empty());
+
+        // The Hammer and the T-Rex of the demo are lent out from the first day.
+        let mut demo = System::new();
+        demo.init_demo();
+        assert_eq!(demo.get_active_contracts().len(), 2);
+        demo.incr_time().expect("");
+        assert_eq!(demo.get_active_contracts().len(), 2);
+    }
+
+    #[test]
+    fn test_lent_item_is_removed_after_it_is_returned() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 5, 100f64);
+        sys.add_contract(&monopoly, contract, None).expect("");
+        assert_eq!(
+            sys.remove_item(&monopoly),
+            Err(SysError::FutureContracts(1))
+        );
+        sys.incr_time().expect("");
+        assert_eq!(
+            sys.remove_item(&monopoly),
+            Err(SysError::ActiveContracts(1))
+        );
+
+        for _ in 0..5 {
+            sys.incr_time().expect("");
+        }
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &220f64);
+        sys.remove_item(&monopoly).expect("");
+        assert!(sys.get_scheduled_jobs().is_empty());
+        sys.incr_time().expect("");
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &220f64);
+    }
+
+    #[test]
+    fn test_canceled_contract_is_not_run() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 0, 3, 60f64);
+        sys.add_contract(&monopoly, contract.clone(), None)
+            .expect("");
+        assert_eq!(status(&sys, &contract), Status::Active);
+
+        let mut item = sys.get_item(&monopoly).unwrap();
+        let canceled = sys
+            .get_contract(&contract)
+            .unwrap()
+            .status(Status::Canceled)
+            .build();
+        item.update_contract(&canceled).expect("");
+        sys.update_item(&item).expect("");
+        assert!(sys.get_scheduled_jobs().is_empty());
+
+        for _ in 0..5 {
+            sys.incr_time().expect("");
+        }
+        assert_eq!(status(&sys, &contract), Status::Canceled);
+        assert!(sys.get_active_contracts().is_empty());
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &300f64);
+        assert_eq!(sys.get_member(&allan).unwrap().get_credits(), &100f64);
+    }
+
+    #[test]
+    fn test_failed_payout() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        let contract = Contract::new(allan, bob.clone(), 0, 5, 80f64);
+        sys.add_contract(&monopoly, contract, None).expect("");
+        let poor = sys.get_member(&bob).unwrap().credits(10f64).build();
+        sys.update_member(&bob, &poor).expect("");
+
+        assert_eq!(sys.incr_time(), Err(SysError::CannotUpdate));
+        // The payouts of the following days are still scheduled.
+        assert_eq!(sys.get_scheduled_jobs().len(), 2);
+    }
+
+    #[test]
+    fn test_edited_contract_is_rescheduled() {
+        let (mut sys, allan, bob, monopoly) = setup();
+        let contract = Contract::new(allan.clone(), bob.clone(), 0, 3, 60f64);
+        sys.add_contract(&monopoly, contract.clone(), None)
+            .expect("");
+        let edit = |sys: &mut System, len: usize| {
+            let item = sys.get_item(&monopoly).unwrap();
+            let booked = sys.get_contract(&contract).unwrap();
+            sys.execute(&mut EditContract {
+                item,
+                contract: booked.contract_len(len).build(),
+            })
+            .expect("");
+        };
+
+        edit(&mut sys, 5);
+        assert_eq!(*sys.get_contract(&contract).unwrap().get_end_date(), 5);
+        for _ in 0..4 {
+            sys.incr_time().expect("");
+        }
+        assert_eq!(status(&sys, &contract), Status::Active);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &220f64);
+
+        edit(&mut sys, 4);
+        sys.incr_time().expect("");
+        assert_eq!(sys.now(), 5);
+        assert_eq!(status(&sys, &contract), Status::Finished);
+        assert_eq!(sys.get_member(&bob).unwrap().get_credits(), &220f64);
+        assert!(sys.get_scheduled_jobs().is_empty());
+        assert_eq!(sys.check_invariants(), vec![]);
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod transaction_test {
+    use crate::{
+        errors::SysError,
+        models::domain::{
+            audit::Entity,
+            contract::Contract,
+            item::{Category, Item},
+            member::Member,
+            system::{LendingSystem, System},
+        },
+    };
+
+    /// Allan and Bob are members, Bob can only pay for Monopoly for one day.
+    fn setup() -> (System, Member, Member, Item) {
+        let allan = Member::new(
+            "Allan".to_owned(),
+            "allan@enigma.com".to_owned(),
+            "0123456789".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(100f64)
+        .build();
+        let bob = Member::new(
+            "Bob".to_owned(),
+            "bob@gmail.com".to_owned(),
+            "0987654321".to_owned(),
+            0,
+        )
+        .expect("Should not fail")
+        .credits(15f64)
+        .build();
+        let item = Item::new(
+            "Monopoly".to_owned(),
+            "Family Game".to_owned(),
+            Category::Game,
+            allan.clone(),
+            10f64,
+            0,
+        );
+
+        let mut system = System::new();
+        system.add_member(allan.clone()).expect("");
+        system.add_member(bob.clone()).expect("");
+        (system, allan, bob, item)
+    }
+
+    fn credits(system: &System, member: &Member) -> f64 {
+        *system.get_member(member).expect("").get_credits()
+    }
+
+    #[test]
+    fn test_rollback() {
+        let (mut system, allan, bob, item) = setup();
+        system.begin();
+        system.add_item(item.clone()).expect("");
+        system.remove_member(&bob).expect("");
+        system.rollback().expect("");
+
+        assert!(system.get_item(&item).is_err());
+        assert!(system.search_items("monopoly").is_empty());
+        assert!(system.exists_member(&bob));
+        assert_eq!(credits(&system, &allan), 100f64);
+        assert!(system
+            .get_audit_entries(Some(Entity::Item), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_nested_transactions() {
+        let (mut system, _, bob, item) = setup();
+        system.begin();
+        system.add_item(item.clone()).expect("");
+        system.begin();
+        system.remove_member(&bob).expect("");
+        system.commit().expect("");
+        system.rollback().expect("");
+        assert!(system.get_item(&item).is_err());
+        assert!(system.exists_member(&bob));
+
+        system.begin();
+        system.add_item(item.clone()).expect("");
+        system.begin();
+        system.remove_member(&bob).expect("");
+        system.rollback().expect("");
+        system.commit().expect("");
+        assert!(system.get_item(&item).is_ok());
+        assert!(system.exists_member(&bob));
+    }
+
+    #[test]
+    fn test_no_transaction() {
+        let (mut system, _, _, _) = setup();
+        assert_eq!(system.commit(), Err(SysError::NoTransaction));
+        assert_eq!(system.rollback(), Err(SysError::NoTransaction));
+    }
+
+    #[test]
+    fn test_transaction_closure() {
+        let (mut system, allan, bob, item) = setup();
+        let result = system.transaction(|sys| {
+            sys.add_item(item.clone())?;
+            sys.remove_member(&bob)?;
+            sys.remove_member(&bob)
+        });
+        assert_eq!(result, Err(SysError::DoesntExist));
+        assert!(system.get_item(&item).is_err());
+        assert!(system.exists_member(&bob));
+
+        let count = system.transaction(|sys| {
+            sys.add_item(item.clone())?;
+            Ok(sys.count_items_for_member(&allan))
+        });
+        assert_eq!(count, Ok(1));
+        assert_eq!(credits(&system, &allan), 200f64);
+    }
+
+    #[test]
+    fn test_add_item_without_owner() {
+        let (mut system, allan, _, item) = setup();
+        system.remove_member(&allan).expect("");
+        assert_eq!(system.add_item(item.clone()), Err(SysError::DoesntExist));
+        assert!(system.get_item(&item).is_err());
+        assert!(system.get_scheduled_jobs().is_empty());
+        assert!(system
+            .get_audit_entries(Some(Entity::Item), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_add_item_twice_keeps_first() {
+        let (mut system, allan, _, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let copy = item.clone().name("Copy".to_owned()).build();
+        assert_eq!(system.add_item(copy), Err(SysError::AlreadyExists));
+        assert_eq!(system.get_item(&item).expect("").get_name(), "Monopoly");
+        assert_eq!(credits(&system, &allan), 200f64);
+    }
+
+    #[test]
+    fn test_payout_is_all_or_nothing() {
+        let (mut system, allan, bob, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 4, 10f64);
+        system.add_contract(&item, contract, None).expect("");
+
+        // Bob can pay for the first day only.
+        assert!(system.incr_time().is_ok());
+        assert!(system.incr_time().is_ok());
+        assert_eq!(credits(&system, &bob), 5f64);
+        assert_eq!(credits(&system, &allan), 210f64);
+
+        assert_eq!(system.incr_time(), Err(SysError::CannotUpdate));
+        assert_eq!(credits(&system, &bob), 5f64);
+        assert_eq!(credits(&system, &allan), 210f64);
+        assert_eq!(system.now(), 3);
+    }
+
+    #[test]
+    fn test_members_of_running_contracts_cannot_leave() {
+        let (mut system, allan, bob, item) = setup();
+        system.add_item(item.clone()).expect("");
+        let contract = Contract::new(allan.clone(), bob.clone(), 1, 2, 10f64);
+        system.add_contract(&item, contract, None).expect("");
+        assert_eq!(system.remove_member(&allan), Err(SysError::OwnsItems(1)));
+        assert_eq!(
+            system.remove_member(&bob),
+            Err(SysError::FutureContracts(1))
+        );
+
+        system.incr_time().expect("");
+        assert_eq!(
+            system.remove_member(&bob),
+            Err(SysError::ActiveContracts(1))
+        );
+        system.incr_time().expect("");
+        assert_eq!(credits(&system, &bob), 5f64);
+        assert_eq!(credits(&system, &allan), 210f64);
+
+        system.incr_time().expect("");
+        system.remove_member(&bob).expect("");
+        assert_eq!(system.check_invariants(), vec![]);
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    console::{Console, Ui},
+    Options,
+};
+use crate::models::domain::{
+    audit::{AuditEntry, Entity},
+    member::Member,
+};
+use prettytable::{Row, Table};
+use shared::{DeriveOptions, View};
+use std::str::FromStr;
+
+/// Longest change shown in the table.
+const MAX_CHANGE_LEN: usize = 60;
+
+/// All the options for the audit log page.
+#[derive(Debug, DeriveOptions)]
+pub enum AuditOption {
+    /// Shows every entry.
+    ShowAll,
+    /// Shows the entries concerning members, items or contracts.
+    FilterByEntity,
+    /// Shows the entries done by a member.
+    FilterByActor,
+    /// Shows the entries concerning an entity done by a member.
+    FilterByEntityAndActor,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
+    /// Goes Back.
+    Back,
+    /// Quits The Application.
+    Quit,
+    /// Any Other other choice.
+    #[other]
+    Other,
+}
+
+/// Defines all methods for the audit view.
+pub trait AuditView {
+    /// Shows all the options for the audit log page.
+    fn audit_menu(&self) -> AuditOption;
+    /// Getting the kind of entity to filter by.
+    fn get_entity(&self) -> Option<Entity>;
+    /// Selecting the member to filter by.
+    fn select_actor<'a>(&'a self, members: Vec<&'a Member>) -> Option<&'a Member>;
+    /// Displays the entries of the audit log.
+    fn display_entries(&self, entries: Vec<&AuditEntry>);
+    /// waits for user.
+    fn wait(&self, display: &str);
+}
+
+/// Implementation for audit view trait.
+#[derive(View)]
+pub struct CliAuditView {
+    console: Console,
+}
+
+impl AuditView for CliAuditView {
+    fn audit_menu(&self) -> AuditOption {
+        self.console.title();
+        let choice = self.console.show_menu(AuditOption::options());
+        match choice {
+            AuditOption::Other => self.audit_menu(),
+            _ => choice,
+        }
+    }
+
+    fn get_entity(&self) -> Option<Entity> {
+        let inp = self
+            .console
+            .get_str_input("Entity (member, item, contract) or (e) to go back");
+        match inp.as_str() {
+            "e" => None,
+            _ => match Entity::from_str(&inp) {
+                Ok(entity) => Some(entity),
+                Err(_) => {
+                    self.wait("Invalid input: Enter member, item or contract.");
+                    self.get_entity()
+                }
+            },
+        }
+    }
+
+    fn select_actor<'a>(&'a self, members: Vec<&'a Member>) -> Option<&'a Member> {
+        self.console.select_model(members)
+    }
+
+    fn display_entries(&self, entries: Vec<&AuditEntry>) {
+        self.console.clear();
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec![
+            "#",
+            "Day",
+            "Time",
+            "Actor",
+            "Operation",
+            "Entity",
+            "Name",
+            "Changes",
+        ]));
+        for entry in entries {
+            let changes = entry
+                .get_changes()
+                .iter()
+                .map(|change| shorten(&change.to_string()))
+                .collect::<Vec<String>>();
+            table.add_row(Row::from(vec![
+                entry.get_seq().to_string(),
+                entry.get_day().to_string(),
+                entry.get_time().format("%H:%M:%S").to_string(),
+                entry.get_actor_name().clone(),
+                entry.get_operation().to_string(),
+                entry.get_entity().to_string(),
+                entry.get_entity_name().clone(),
+                changes.join("\n"),
+            ]));
+        }
+        self.console.display_table(table);
+    }
+
+    fn wait(&self, display: &str) {
+        self.console.wait(display)
+    }
+}
+
+/// Cuts long changes, so the table stays readable.
+fn shorten(change: &str) -> String {
+    match change.chars().count() > MAX_CHANGE_LEN {
+        true => format!(
+            "{}...",
+            change.chars().take(MAX_CHANGE_LEN).collect::<String>()
+        ),
+        false => change.to_owned(),
+    }
+}
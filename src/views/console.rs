@@ -1,7 +1,8 @@
-use super::Options;
+use super::{ExportOption, Options};
 use crate::{
-    models::domain::item::Item,
-    types::{Data, FromMap, Model, ToMap},
+    errors::Subject,
+    models::{domain::item::Item, export::ExportFormat, id::Id},
+    types::{Data, FromMap, Model, ToMap, ValResult},
 };
 use prettytable::{Cell, Row, Table};
 use std::{
@@ -88,19 +89,27 @@ pub trait Ui {
         current_page: usize,
     ) -> Either<&M, usize>
     where
-        M: Data + FromMap + ToMap + Model;
+        M: Data + FromMap + ToMap + Model + Subject;
 
     /// Shows a list of models in pages of 10 and then lets the user
     /// select one of those.
     fn select_model<'a, M>(&'a self, vec_model: Vec<&'a M>) -> Option<&M>
     where
-        M: Data + FromMap + ToMap + Model;
+        M: Data + FromMap + ToMap + Model + Subject;
 
     /// Lets the user edit the information for a model.
     fn edit_model_info<T>(&self, model: &T) -> Option<T>
     where
         T: Data + FromMap + ToMap + Model;
 
+    /// Lets the user edit the information for a model until it is valid.
+    ///
+    /// Only the invalid fields are asked for again, returns None if the user cancels.
+    fn edit_validated_model_info<T, F>(&self, model: &T, validate: F) -> Option<T>
+    where
+        T: Data + FromMap + ToMap + Model,
+        F: Fn(T) -> ValResult<T>;
+
     /// Displays a message to the user and waits for him to acknowledge the message
     ///  before continuing with the flow.
     fn wait(&self, display: &str);
@@ -164,6 +173,69 @@ impl Console {
             .collect::<HashMap<String, String>>();
         obj.copy_with_map(data)
     }
+
+    /// Collects information for a model until it is valid.
+    ///
+    /// Only the invalid fields are asked for again, returns None if the user cancels.
+    pub fn get_validated_model_info<T, F>(&self, obj: T, validate: F) -> Option<T>
+    where
+        T: Data + FromMap + ToMap + Model,
+        F: Fn(T) -> ValResult<T>,
+    {
+        self.title();
+        let input = self.get_consecutive_str_input(T::head_allowed_mutable());
+        self.fix_invalid_fields(&obj, input, &validate)
+            .map(|(model, _)| model)
+    }
+
+    /// Validates the model with the input and asks again for the invalid fields,
+    /// while the valid input is kept.
+    ///
+    /// Returns the valid model with the input it was built from, or None if the user
+    /// cancels by not entering anything.
+    fn fix_invalid_fields<T, F>(
+        &self,
+        obj: &T,
+        mut input: HashMap<String, String>,
+        validate: &F,
+    ) -> Option<(T, HashMap<String, String>)>
+    where
+        T: Data + FromMap + ToMap + Model,
+        F: Fn(T) -> ValResult<T>,
+    {
+        let check = match validate(obj.copy_with_map(input.clone())) {
+            Ok(model) => return Some((model, input)),
+            Err(check) => check,
+        };
+        let fields = T::head_allowed_mutable()
+            .into_iter()
+            .filter(|field| check.is_invalid(field))
+            .collect::<Vec<String>>();
+        self.title();
+        self.write(check.to_string().as_str());
+        if fields.is_empty() {
+            self.wait("");
+            return None;
+        }
+        self.write("Enter the invalid fields again, or nothing to cancel.");
+        let fixed = self.get_consecutive_str_input(fields);
+        if fixed.is_empty() {
+            return None;
+        }
+        input.extend(fixed);
+        self.fix_invalid_fields(obj, input, validate)
+    }
+
+    /// Asks for the format to export a table to.
+    pub fn get_export_format(&self) -> Option<ExportFormat> {
+        self.title();
+        self.write("Export as");
+        let choice: ExportOption = self.show_menu(ExportOption::options());
+        match choice {
+            ExportOption::Other => self.get_export_format(),
+            _ => choice.format(),
+        }
+    }
 }
 
 impl Ui for Console {
@@ -275,7 +347,7 @@ impl Ui for Console {
         curr_page: usize,
     ) -> Either<&M, usize>
     where
-        M: Data + FromMap + ToMap + Model + Data,
+        M: Data + FromMap + ToMap + Model + Subject,
     {
         self.clear();
 
@@ -299,18 +371,6 @@ impl Ui for Console {
                 let data = item.to_map();
                 let cell_data = data.get(key).unwrap();
                 match key.to_lowercase().as_str() {
-                    "uuid" => {
-                        let uuid_value = cell_data
-                            .split(';')
-                            .collect::<Vec<&str>>()
-                            .last()
-                            .unwrap()
-                            .split(',')
-                            .last()
-                            .unwrap()
-                            .replace(']', "");
-                        row.add_cell(Cell::new(uuid_value.as_str()));
-                    }
                     "owner" => {
                         let owner_name = cell_data
                             .split(';')
@@ -356,7 +416,7 @@ impl Ui for Console {
         let page_count = chunks.len();
         let page_display = format!("Page: {} / {}", curr_page + 1, page_count);
         self.write(page_display.as_str());
-        let msg = "Press \n\tn\t(next)\n\tp\t(previous)\n\tq\t(quit)\n\te\t(go back to menu)\n\t0..9\t(select)\n\t";
+        let msg = "Press \n\tn\t(next)\n\tp\t(previous)\n\tq\t(quit)\n\te\t(go back to menu)\n\t0..9\t(select)\n\ti\t(select by id)\n\t";
         let inp = self.get_char_input(msg);
 
         if let Ok(res) = inp.to_string().parse::<usize>() {
@@ -380,6 +440,13 @@ impl Ui for Console {
                         self.display_page(vec_model.clone(), chunks.clone(), curr_page)
                     }
                 }
+                'i' => match find_by_id(&vec_model, self.get_str_input("Id").as_str()) {
+                    Some(model) => Either::Left(model),
+                    None => {
+                        self.wait("Nothing with this id to select.");
+                        self.display_page(vec_model, chunks, curr_page)
+                    }
+                },
                 'q' => std::process::exit(0),
                 'e' => Either::None,
                 ' ' => self.display_page(vec_model, chunks, curr_page),
@@ -390,7 +457,7 @@ impl Ui for Console {
 
     fn select_model<'a, M>(&'a self, vec_model: Vec<&'a M>) -> Option<&M>
     where
-        M: Data + FromMap + ToMap + Model + Data,
+        M: Data + FromMap + ToMap + Model + Subject,
     {
         if vec_model.is_empty() {
             self.wait("Nothing to select.");
@@ -412,10 +479,19 @@ impl Ui for Console {
     fn edit_model_info<T>(&self, obj: &T) -> Option<T>
     where
         T: Data + FromMap + ToMap + Model,
+    {
+        self.edit_validated_model_info(obj, Ok)
+    }
+
+    fn edit_validated_model_info<T, F>(&self, obj: &T, validate: F) -> Option<T>
+    where
+        T: Data + FromMap + ToMap + Model,
+        F: Fn(T) -> ValResult<T>,
     {
         self.title();
         self.write("I you do not want to edit a certain parameter, simply hit enter without typing anything.");
         let new_model_info = self.get_consecutive_str_input(T::head_allowed_mutable());
+        let (model, new_model_info) = self.fix_invalid_fields(obj, new_model_info, &validate)?;
         let obj_map = obj.to_map_allowed_mutable();
         let values_tuples = new_model_info
             .iter()
@@ -429,7 +505,7 @@ impl Ui for Console {
                     tpl
                 });
         if self.confirm(keys, vals) {
-            return Some(obj.copy_with_map(new_model_info));
+            return Some(model);
         }
         None
     }
@@ -442,3 +518,13 @@ impl Ui for Console {
         }
     }
 }
+
+/// Finds the model with the id, which is typed like it is displayed, e.g. `M-ab12cd`.
+/// Only the letter of the kind may be typed in lowercase.
+fn find_by_id<'a, M: Subject>(models: &[&'a M], id: &str) -> Option<&'a M> {
+    let id = Id::<M::Kind>::from_str(id).ok()?;
+    models
+        .iter()
+        .find(|model| model.get_typed_id() == &id)
+        .copied()
+}
@@ -2,7 +2,7 @@ use super::{
     console::{Console, Ui},
     Options,
 };
-use crate::models::domain::contract::Contract;
+use crate::models::{domain::contract::Contract, export::ExportFormat};
 use shared::{DeriveOptions, View};
 use std::str::FromStr;
 
@@ -18,6 +18,12 @@ pub enum ContractOption {
     /// Asks user for input and returns a new instance of the previous contract
     /// with (possibly) updated attributes.
     EditContract,
+    /// Exports all contracts to a file.
+    ExportContracts,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
     /// Returns to the previous page.
     Back,
     /// Quits the entire application.
@@ -39,6 +45,8 @@ pub trait ContractView {
     fn get_contract_info(&self) -> Contract;
     /// Displays a contract in a simple format.
     fn display_contract_simple(&self, contract: &Contract);
+    /// Getting the format to export to.
+    fn get_export_format(&self) -> Option<ExportFormat>;
     /// Displays a message to the user and waits till the user acknowledges the message
     /// before continuing with the regular flow.
     fn wait(&self, display: &str);
@@ -75,13 +83,14 @@ impl ContractView for CliContractView {
 
     fn display_contract_simple(&self, contract: &Contract) {
         let out = format!(
-            "Owner:\t{}\nLendee:\t{}\nCredits:\t{}\nStatus:\t{}\nStart Date:\t{}\nEnd Date:\t{}",
+            "Owner:\t{}\nLendee:\t{}\nCredits:\t{}\nStatus:\t{}\nStart Date:\t{}\nEnd Date:\t{}\nUnit:\t{}",
             contract.get_owner().get_name(),
             contract.get_lendee().get_name(),
             contract.get_credits(),
             contract.get_status(),
             contract.get_start_date(),
             contract.get_end_date(),
+            contract.get_unit(),
         );
 
         self.console.clear();
@@ -89,6 +98,10 @@ impl ContractView for CliContractView {
         self.console.write(out.as_str());
     }
 
+    fn get_export_format(&self) -> Option<ExportFormat> {
+        self.console.get_export_format()
+    }
+
     fn wait(&self, display: &str) {
         self.console.wait(display)
     }
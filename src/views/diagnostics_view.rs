@@ -0,0 +1,95 @@
+use super::{
+    console::{Console, Ui},
+    Options,
+};
+use crate::models::domain::{invariants::Violation, ledger::Mint};
+use prettytable::{Row, Table};
+use shared::{DeriveOptions, View};
+use std::str::FromStr;
+
+/// All the options for the diagnostics page.
+#[derive(Debug, DeriveOptions)]
+pub enum DiagnosticsOption {
+    /// Verifies the whole state of the system.
+    CheckInvariants,
+    /// Shows all credits created and destroyed outside of payouts.
+    ShowLedger,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
+    /// Goes Back.
+    Back,
+    /// Quits The Application.
+    Quit,
+    /// Any Other other choice.
+    #[other]
+    Other,
+}
+
+/// Defines all methods for the diagnostics view.
+pub trait DiagnosticsView {
+    /// Shows all the options for the diagnostics page.
+    fn diagnostics_menu(&self) -> DiagnosticsOption;
+    /// Displays all violations, together with the step that introduced them if it is known.
+    fn display_violations(&self, violations: Vec<(Violation, Option<String>)>);
+    /// Displays the mints of the ledger and their total.
+    fn display_ledger(&self, mints: &[Mint], total: f64);
+    /// waits for user.
+    fn wait(&self, display: &str);
+}
+
+/// Implementation for diagnostics view trait.
+#[derive(View)]
+pub struct CliDiagnosticsView {
+    console: Console,
+}
+
+impl DiagnosticsView for CliDiagnosticsView {
+    fn diagnostics_menu(&self) -> DiagnosticsOption {
+        self.console.title();
+        let choice = self.console.show_menu(DiagnosticsOption::options());
+        match choice {
+            DiagnosticsOption::Other => self.diagnostics_menu(),
+            _ => choice,
+        }
+    }
+
+    fn display_violations(&self, violations: Vec<(Violation, Option<String>)>) {
+        self.console.clear();
+        if violations.is_empty() {
+            self.console.write("All invariants hold.");
+            return;
+        }
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["#", "Violation", "Introduced by"]));
+        for (i, (violation, step)) in violations.iter().enumerate() {
+            table.add_row(Row::from(vec![
+                (i + 1).to_string(),
+                violation.to_string(),
+                step.clone().unwrap_or_else(|| "-".to_owned()),
+            ]));
+        }
+        self.console.display_table(table);
+    }
+
+    fn display_ledger(&self, mints: &[Mint], total: f64) {
+        self.console.clear();
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["Day", "Member", "Amount", "Reason"]));
+        for mint in mints {
+            table.add_row(Row::from(vec![
+                mint.get_day().to_string(),
+                mint.get_member_name().clone(),
+                mint.get_amount().to_string(),
+                mint.get_reason().clone(),
+            ]));
+        }
+        self.console.display_table(table);
+        self.console.write(format!("Total: {}", total).as_str());
+    }
+
+    fn wait(&self, display: &str) {
+        self.console.wait(display)
+    }
+}
@@ -0,0 +1,35 @@
+use super::console::{Console, Ui};
+use prettytable::{Row, Table};
+use shared::View;
+
+/// Defines all methods for the history view.
+pub trait HistoryView {
+    /// Displays the steps that can be undone and redone, the next one first.
+    fn display_steps(&self, undo: Vec<&str>, redo: Vec<&str>);
+    /// waits for user.
+    fn wait(&self, display: &str);
+}
+
+/// Implementation for history view trait.
+#[derive(View)]
+pub struct CliHistoryView {
+    console: Console,
+}
+
+impl HistoryView for CliHistoryView {
+    fn display_steps(&self, undo: Vec<&str>, redo: Vec<&str>) {
+        self.console.clear();
+        for (title, steps) in [("Undo", undo), ("Redo", redo)] {
+            let mut table = Table::new();
+            table.set_titles(Row::from(vec!["#", title]));
+            for (i, step) in steps.into_iter().enumerate() {
+                table.add_row(Row::from(vec![(i + 1).to_string(), step.to_owned()]));
+            }
+            self.console.display_table(table);
+        }
+    }
+
+    fn wait(&self, display: &str) {
+        self.console.wait(display)
+    }
+}
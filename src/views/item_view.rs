@@ -1,7 +1,11 @@
 use super::console::{Console, Ui};
 use super::Options;
+use crate::models::domain::item::Availability;
 use crate::models::domain::item::Category;
 use crate::models::domain::item::Item;
+use crate::models::domain::item::Tags;
+use crate::models::domain::period::Period;
+use crate::models::export::ExportFormat;
 use crate::types::Data;
 use shared::{DeriveOptions, View};
 use std::str::FromStr;
@@ -20,6 +24,18 @@ pub enum ItemMenuOption {
     DeleteItem,
     /// Shows availability
     ShowAvailability,
+    /// Searches items by keywords.
+    SearchItems,
+    /// Blocks an item for a period.
+    AddBlackout,
+    /// Unblocks an item again.
+    RemoveBlackout,
+    /// Exports all items to a file.
+    ExportItems,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
     /// Go back to the previous page.
     Back,
     /// Quits the entire application.
@@ -45,6 +61,16 @@ pub trait ItemView {
     fn display_availability(&self, now: usize, item: &Item);
     /// Selecting a date
     fn select_date(&self, now: usize, item: &Item) -> Option<usize>;
+    /// Selecting a specific unit of an item. Returns None if any free unit can be booked.
+    fn select_unit(&self, item: &Item) -> Option<usize>;
+    /// Getting the keywords to search for.
+    fn get_search_query(&self) -> String;
+    /// Getting a period of days starting from today or later.
+    fn get_period(&self, now: usize) -> Option<Period>;
+    /// Selecting one of the blackout periods of an item.
+    fn select_blackout(&self, item: &Item) -> Option<Period>;
+    /// Getting the format to export to.
+    fn get_export_format(&self) -> Option<ExportFormat>;
     /// Displays a message to the user and waits for him to respond.
     fn wait(&self, display: &str);
 }
@@ -74,11 +100,12 @@ impl ItemView for CliItemView {
                     .iter()
                     .map(|cons| {
                         format!(
-                            "\n[\n\tOwner:\t{}\n\tLendee:\t{}\n\tCredits:\t{}\n\tLength:\t{}\n]",
+                            "\n[\n\tOwner:\t{}\n\tLendee:\t{}\n\tCredits:\t{}\n\tLength:\t{}\n\tUnit:\t{}\n]",
                             cons.get_owner().get_name(),
                             cons.get_lendee().get_name(),
                             cons.get_credits(),
                             cons.get_contract_len(),
+                            cons.get_unit(),
                         )
                     })
                     .collect::<Vec<String>>();
@@ -92,12 +119,19 @@ impl ItemView for CliItemView {
             .collect::<Vec<String>>()
             .join("\n");
         let out = format!(
-            "Name:\t\t{}\nDescriptioin:\t{}\nCategory:\t{}\nOwner:\t\t{}\nCost/Day:\t{}\nHistory:\n{}",
+            "Name:\t\t{}\nDescriptioin:\t{}\nCategory:\t{}\nTags:\t\t{}\nOwner:\t\t{}\nCost/Day:\t{}\nQuantity:\t{}\nBlackouts:\t{}\nHistory:\n{}",
             item.get_name(),
             item.get_description(),
             item.get_category(),
+            item.get_tags(),
             item.get_owner().get_name(),
             item.get_cost_per_day(),
+            item.get_quantity(),
+            item.get_blackouts()
+                .iter()
+                .map(|period| period.to_string())
+                .collect::<Vec<String>>()
+                .join(" "),
             out,
         );
 
@@ -129,6 +163,15 @@ impl ItemView for CliItemView {
             None => &buf,
         })
         .unwrap();
+        let tags = Tags::from_str(match data.get("tags") {
+            Some(val) => val,
+            None => &buf,
+        })
+        .unwrap();
+        let quantity = match data.get("quantity") {
+            Some(val) => val.parse::<usize>().unwrap_or(1).max(1),
+            None => 1,
+        };
         let cost_per_day = match data.get("cost_per_day") {
             Some(val) => val,
             None => &buf,
@@ -145,7 +188,9 @@ impl ItemView for CliItemView {
             .name(name.clone())
             .description(description.clone())
             .category(category)
+            .tags(tags)
             .cost_per_day(cost_per_day)
+            .quantity(quantity)
             .build()
     }
 
@@ -156,13 +201,23 @@ impl ItemView for CliItemView {
     fn display_availability(&self, now: usize, item: &Item) {
         let check = '✓';
         let cross = '✕';
+        let blocked = '■';
         let am = item.get_availability(now);
         for chunk in am.chunks(10) {
             for tpl in chunk.iter() {
-                print!("|  {}\t:{}  ", tpl.0, if tpl.1 { cross } else { check });
+                match (tpl.1, item.get_quantity()) {
+                    (Availability::Blackout, _) => print!("|  {}\t:{}  ", tpl.0, blocked),
+                    (Availability::Free(0), _) => print!("|  {}\t:{}  ", tpl.0, cross),
+                    (Availability::Free(_), 1) => print!("|  {}\t:{}  ", tpl.0, check),
+                    (Availability::Free(free), _) => print!("|  {}\t:{}  ", tpl.0, free),
+                }
             }
             println!("|")
         }
+        println!(
+            "{} free\t{} booked\t{} blocked by owner",
+            check, cross, blocked
+        );
     }
 
     fn select_date(&self, now: usize, item: &Item) -> Option<usize> {
@@ -180,6 +235,74 @@ impl ItemView for CliItemView {
         }
     }
 
+    fn select_unit(&self, item: &Item) -> Option<usize> {
+        let inp = self.console.get_str_input(
+            format!(
+                "Press (0..{}) to book a specific unit or enter to book any free unit",
+                item.get_quantity() - 1
+            )
+            .as_str(),
+        );
+        if inp.is_empty() {
+            return None;
+        }
+        match inp.parse::<usize>() {
+            Ok(unit) if unit < *item.get_quantity() => Some(unit),
+            _ => self.select_unit(item),
+        }
+    }
+
+    fn get_period(&self, now: usize) -> Option<Period> {
+        let start = self.console.get_str_input("First day (or (e) to go back)");
+        if start.as_str() == "e" {
+            return None;
+        }
+        let days = self.console.get_str_input("Number of days");
+        match (start.parse::<usize>(), days.parse::<usize>()) {
+            (Ok(start), Ok(days)) if start >= now && days > 0 => {
+                Some(Period::from_len(start, days))
+            }
+            _ => {
+                self.wait("Invalid input: The period has to start today or later and last at least one day.");
+                self.get_period(now)
+            }
+        }
+    }
+
+    fn select_blackout(&self, item: &Item) -> Option<Period> {
+        let blackouts = item.get_blackouts().to_vec();
+        if blackouts.is_empty() {
+            self.wait("This item has no blackout periods.");
+            return None;
+        }
+        let out = blackouts
+            .iter()
+            .enumerate()
+            .map(|(cnt, period)| format!("\t{}\t:\tDays {}", cnt, period))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.console.write(out.as_str());
+        let inp = self
+            .console
+            .get_str_input("Press (0..9) to select or (e) to go back");
+        match inp.parse::<usize>() {
+            Ok(idx) if idx < blackouts.len() => Some(blackouts[idx]),
+            _ => match inp.as_str() {
+                "e" => None,
+                _ => self.select_blackout(item),
+            },
+        }
+    }
+
+    fn get_search_query(&self) -> String {
+        self.console.title();
+        self.console.get_str_input("Search")
+    }
+
+    fn get_export_format(&self) -> Option<ExportFormat> {
+        self.console.get_export_format()
+    }
+
     fn wait(&self, display: &str) {
         self.console.wait(display)
     }
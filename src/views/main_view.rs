@@ -15,6 +15,18 @@ pub enum MainMenuOption {
     ContractsPage,
     /// goes to the simulator
     Simulator,
+    /// goes to the reports.
+    Reports,
+    /// goes to the audit log.
+    AuditLog,
+    /// goes to the diagnostics.
+    Diagnostics,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
+    /// logs out the current member.
+    Logout,
     /// quites the application.
     Quit,
     #[other]
@@ -26,6 +38,10 @@ pub enum MainMenuOption {
 pub trait MainView {
     /// Shows all the options for the main menu.
     fn main_menu(&self) -> MainMenuOption;
+    /// Shows the login screen and returns the email and password.
+    fn login(&self) -> (String, String);
+    /// Displays a message to the user and waits for him to respond.
+    fn wait(&self, display: &str);
 }
 
 /// A concrete implementation of the main view.
@@ -43,4 +59,16 @@ impl MainView for CliMainView {
             _ => choice,
         }
     }
+
+    fn login(&self) -> (String, String) {
+        self.console.title();
+        self.console.write("Please log in.");
+        let email = self.console.get_str_input("email");
+        let password = self.console.get_str_input("password");
+        (email, password)
+    }
+
+    fn wait(&self, display: &str) {
+        self.console.wait(display)
+    }
 }
@@ -1,7 +1,9 @@
 use super::console::{Console, Ui};
 use crate::models::domain::item::Item;
-use crate::models::domain::member::Member;
-use crate::types::Data;
+use crate::models::domain::member::{Member, MemberStatus};
+use crate::models::domain::notification::Notification;
+use crate::models::export::ExportFormat;
+use crate::types::{Data, ValResult};
 use crate::views::Options;
 use prettytable::{Cell, Row, Table};
 use shared::{DeriveOptions, View};
@@ -24,6 +26,18 @@ pub enum MemberMenuOption {
     DeleteMember,
     /// Edits member information for a specific member.
     EditMember,
+    /// Changes the password of a specific member.
+    ChangePassword,
+    /// Suspends, bans or reinstates a member.
+    ModerateMember,
+    /// Shows the notifications of the logged in member.
+    ShowInbox,
+    /// Exports all members to a file.
+    ExportMembers,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
     /// Goes back to previous page.
     Back,
     /// Quits the entire application.
@@ -33,6 +47,22 @@ pub enum MemberMenuOption {
     Other,
 }
 
+/// All the options for moderating a member.
+#[derive(Debug, DeriveOptions)]
+pub enum ModerationOption {
+    /// Suspends the member for a number of days.
+    Suspend,
+    /// Bans the member permanently.
+    Ban,
+    /// Lifts a suspension or ban.
+    Reinstate,
+    /// Goes back to previous page.
+    Back,
+    #[other]
+    /// Any other choice made by the user.
+    Other,
+}
+
 /// Defines all methods required by a concrete implementaion of the member view.
 pub trait MemberView {
     /// Displays all options for the member menu.
@@ -45,10 +75,23 @@ pub trait MemberView {
     fn display_all_simple(&self, members: Vec<(&Member, usize)>);
     /// Displays all members in a verbose format.
     fn display_all_verbose(&self, members: Vec<(&Member, Vec<&Item>)>);
-    /// Getting information for a new member.
-    fn get_member_info(&self) -> Member;
-    /// Edits a single member.
-    fn edit_member_info(&self, member: &Member) -> Option<Member>;
+    /// Getting information for a new member until it is valid.
+    /// Returns None if the user cancels.
+    fn get_member_info(&self, validate: &dyn Fn(Member) -> ValResult<Member>) -> Option<Member>;
+    /// Edits a single member until it is valid.
+    fn edit_member_info(
+        &self,
+        member: &Member,
+        validate: &dyn Fn(Member) -> ValResult<Member>,
+    ) -> Option<Member>;
+    /// Displays the notifications of a member.
+    fn display_inbox(&self, member: &Member, notifications: Vec<Notification>);
+    /// Getting a new password for a member.
+    fn get_password(&self) -> String;
+    /// Getting the new status and the reason for it.
+    fn get_moderation(&self, now: usize) -> Option<(MemberStatus, String)>;
+    /// Getting the format to export to.
+    fn get_export_format(&self) -> Option<ExportFormat>;
     /// Selecting a member from a list of options.
     fn select_member<'a>(&'a self, members: Vec<&'a Member>) -> Option<&Member>;
     /// Displays a message to the user and waits for him to respond.
@@ -87,11 +130,13 @@ impl MemberView for CliMemberView {
             items_str.push_str(&formatted);
         }
         let out = format!(
-            "Name:\t\t{}\nEmail:\t\t{}\nPhone number:\t{}\nCredits:\t{}\nItems: [\n{}\n]\n",
+            "Name:\t\t{}\nEmail:\t\t{}\nPhone number:\t{}\nCredits:\t{}\nStatus:\t\t{} {}\nItems: [\n{}\n]\n",
             member.get_name(),
             member.get_email(),
             member.get_phone_nr(),
             member.get_credits(),
+            member.get_status(),
+            member.get_status_reason(),
             items_str,
         );
         self.console.clear();
@@ -101,10 +146,11 @@ impl MemberView for CliMemberView {
 
     fn display_member_simple(&self, member: &Member, number_of_items: usize) {
         let out = format!(
-            "Name:\t\t{}\nEmail:\t\t{}\nCredits:\t{}\nItems:\t\t{}\n",
+            "Name:\t\t{}\nEmail:\t\t{}\nCredits:\t{}\nStatus:\t\t{}\nItems:\t\t{}\n",
             member.get_name(),
             member.get_email(),
             member.get_credits(),
+            member.get_status(),
             number_of_items,
         );
         self.console.clear();
@@ -125,8 +171,6 @@ impl MemberView for CliMemberView {
         table.set_titles(head);
         for entry in data {
             let mut row = entry.0.to_row();
-            row.remove_cell(5);
-            row.add_cell(Cell::new(entry.0.get_uuid().get_value()));
             let cell = Cell::new(&entry.1.to_string());
             row.add_cell(cell);
             table.add_row(row);
@@ -154,14 +198,12 @@ impl MemberView for CliMemberView {
                     &item.get_name(),
                     &item.get_description(),
                     &item.get_category(),
-                    &item.get_history().len,
+                    &item.get_history().len(),
                 );
                 buf.push_str(out.as_str())
             }
 
             let mut row = entry.0.to_row();
-            row.remove_cell(5);
-            row.add_cell(Cell::new(entry.0.get_uuid().get_value()));
             let cell = Cell::new(&buf);
             row.add_cell(cell);
             table.add_row(row);
@@ -170,13 +212,76 @@ impl MemberView for CliMemberView {
         self.wait("")
     }
 
-    fn get_member_info(&self) -> Member {
+    fn get_member_info(&self, validate: &dyn Fn(Member) -> ValResult<Member>) -> Option<Member> {
         let new_member = Member::default();
-        self.console.get_model_info(new_member)
+        self.console.get_validated_model_info(new_member, validate)
+    }
+
+    fn edit_member_info(
+        &self,
+        member: &Member,
+        validate: &dyn Fn(Member) -> ValResult<Member>,
+    ) -> Option<Member> {
+        self.console.edit_validated_model_info(member, validate)
+    }
+
+    fn get_moderation(&self, now: usize) -> Option<(MemberStatus, String)> {
+        self.console.title();
+        let choice: ModerationOption = self.console.show_menu(ModerationOption::options());
+        let status = match choice {
+            ModerationOption::Suspend => {
+                let days = self.console.get_str_input("Number of days");
+                match days.parse::<usize>() {
+                    Ok(days) if days > 0 => MemberStatus::Suspended(now + days),
+                    _ => {
+                        self.wait(
+                            "Invalid input: The number of days has to be a positive integer.",
+                        );
+                        return self.get_moderation(now);
+                    }
+                }
+            }
+            ModerationOption::Ban => MemberStatus::Banned,
+            ModerationOption::Reinstate => return Some((MemberStatus::Active, String::new())),
+            ModerationOption::Back => return None,
+            ModerationOption::Other => return self.get_moderation(now),
+        };
+        let reason = self.console.get_str_input("Reason");
+        Some((status, reason))
+    }
+
+    fn display_inbox(&self, member: &Member, notifications: Vec<Notification>) {
+        self.console.clear();
+        if notifications.is_empty() {
+            self.wait(&format!("{} has no notifications.", member.get_name()));
+            return;
+        }
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["Day", "Subject", "Message"]));
+        for notification in notifications.iter().rev() {
+            table.add_row(Row::from(vec![
+                notification.get_day().to_string(),
+                notification.get_subject().to_owned(),
+                notification.get_message().to_owned(),
+            ]));
+        }
+        self.console.display_table(table);
+        self.wait("")
+    }
+
+    fn get_password(&self) -> String {
+        let password = self.console.get_str_input("password");
+        match password.is_empty() {
+            true => {
+                self.wait("The password cannot be empty.");
+                self.get_password()
+            }
+            false => password,
+        }
     }
 
-    fn edit_member_info(&self, member: &Member) -> Option<Member> {
-        self.console.edit_model_info(member)
+    fn get_export_format(&self) -> Option<ExportFormat> {
+        self.console.get_export_format()
     }
 
     fn select_member<'a>(&'a self, members: Vec<&'a Member>) -> Option<&Member> {
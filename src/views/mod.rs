@@ -1,15 +1,26 @@
+use crate::models::export::ExportFormat;
+use shared::DeriveOptions;
+use std::str::FromStr;
 use thiserror::Error;
 
+/// Audit View.
+pub mod audit_view;
 /// The Console.
 pub mod console;
 /// Contraact view.
 pub mod contract_view;
+/// Diagnostics View.
+pub mod diagnostics_view;
+/// History View.
+pub mod history_view;
 /// Item view.
 pub mod item_view;
 /// Main view.
 pub mod main_view;
 /// Member View.
 pub mod member_view;
+/// Report View.
+pub mod report_view;
 /// Simulator View.
 pub mod simulator_view;
 
@@ -23,6 +34,34 @@ pub trait Options {
     fn from_choice(choice: usize) -> Self;
 }
 
+/// All the formats tables can be exported to.
+#[derive(Debug, DeriveOptions)]
+pub enum ExportOption {
+    /// Comma separated values.
+    Csv,
+    /// Markdown tables.
+    Markdown,
+    /// A standalone html page.
+    Html,
+    /// Goes Back.
+    Back,
+    /// Any Other other choice.
+    #[other]
+    Other,
+}
+
+impl ExportOption {
+    /// Returns the chosen format, if any.
+    pub fn format(&self) -> Option<ExportFormat> {
+        match self {
+            ExportOption::Csv => Some(ExportFormat::Csv),
+            ExportOption::Markdown => Some(ExportFormat::Markdown),
+            ExportOption::Html => Some(ExportFormat::Html),
+            ExportOption::Back | ExportOption::Other => None,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 struct InvalidInput;
 
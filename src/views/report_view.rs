@@ -0,0 +1,117 @@
+use super::{
+    console::{Console, Ui},
+    Options,
+};
+use crate::models::{
+    domain::{period::Period, report::ReportSection},
+    export::ExportFormat,
+};
+use prettytable::{Row, Table};
+use shared::{DeriveOptions, View};
+use std::str::FromStr;
+
+/// All the options for the reports page.
+#[derive(Debug, DeriveOptions)]
+pub enum ReportOption {
+    /// Booked days compared to the days an item existed.
+    Utilization,
+    /// Credits earned by every item.
+    ItemRevenue,
+    /// Credits earned by every owner.
+    OwnerRevenue,
+    /// The members that spent the most credits.
+    TopBorrowers,
+    /// How many members have how many credits.
+    CreditDistribution,
+    /// Bookings per category.
+    CategoryPopularity,
+    /// All of the reports above.
+    AllReports,
+    /// Exports all reports to a file.
+    ExportReports,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
+    /// Goes Back.
+    Back,
+    /// Quits The Application.
+    Quit,
+    /// Any Other other choice.
+    #[other]
+    Other,
+}
+
+/// Defines all methods for the report view.
+pub trait ReportView {
+    /// Shows all the options for the reports page.
+    fn report_menu(&self) -> ReportOption;
+    /// Getting the range of days to report on.
+    fn get_range(&self, now: usize) -> Option<Period>;
+    /// Displays the reports for the range of days.
+    fn display_reports(&self, period: &Period, sections: Vec<ReportSection>);
+    /// Getting the format to export to.
+    fn get_export_format(&self) -> Option<ExportFormat>;
+    /// waits for user.
+    fn wait(&self, display: &str);
+}
+
+/// Implementation for report view trait.
+#[derive(View)]
+pub struct CliReportView {
+    console: Console,
+}
+
+impl ReportView for CliReportView {
+    fn report_menu(&self) -> ReportOption {
+        self.console.title();
+        let choice = self.console.show_menu(ReportOption::options());
+        match choice {
+            ReportOption::Other => self.report_menu(),
+            _ => choice,
+        }
+    }
+
+    fn get_range(&self, now: usize) -> Option<Period> {
+        let inp = self.console.get_str_input(
+            format!(
+                "Days as (from..to), enter for 0..{} or (e) to go back",
+                now + 1
+            )
+            .as_str(),
+        );
+        match inp.as_str() {
+            "e" => None,
+            "" => Some(Period::new(0, now + 1)),
+            _ => match Period::from_str(&inp) {
+                Ok(period) if !period.is_empty() => Some(period),
+                _ => {
+                    self.wait("Invalid input: Enter the range like 0..10.");
+                    self.get_range(now)
+                }
+            },
+        }
+    }
+
+    fn display_reports(&self, period: &Period, sections: Vec<ReportSection>) {
+        self.console.clear();
+        self.console.write(format!("Days {}", period).as_str());
+        for section in sections {
+            self.console.write(section.get_title());
+            let mut table = Table::new();
+            table.set_titles(Row::from(section.get_head()));
+            for row in section.get_rows() {
+                table.add_row(Row::from(row));
+            }
+            self.console.display_table(table);
+        }
+    }
+
+    fn get_export_format(&self) -> Option<ExportFormat> {
+        self.console.get_export_format()
+    }
+
+    fn wait(&self, display: &str) {
+        self.console.wait(display)
+    }
+}
@@ -2,7 +2,15 @@ use super::{
     console::{Console, Ui},
     Options,
 };
-use crate::types::View;
+use crate::{
+    models::domain::{
+        community::{CommunityConfig, CommunityStats},
+        member::Member,
+        simulation::{DaySummary, StopCondition},
+    },
+    types::View,
+};
+use prettytable::{Row, Table};
 use shared::DeriveOptions;
 use std::str::FromStr;
 
@@ -10,6 +18,18 @@ use std::str::FromStr;
 pub enum SimulatorOption {
     /// Increments Day for the system.
     IncrDay,
+    /// Advances a number of days.
+    AdvanceDays,
+    /// Advances up to a specific day.
+    AdvanceToDay,
+    /// Advances until a stop condition holds.
+    AdvanceUntil,
+    /// Simulates the activity of a synthetic community.
+    SimulateCommunity,
+    /// Undoes the most recent change.
+    Undo,
+    /// Redoes the most recently undone change.
+    Redo,
     /// Goes Back.
     Back,
     /// Quits The Application.
@@ -19,10 +39,38 @@ pub enum SimulatorOption {
     Other,
 }
 
+/// All the conditions the simulator can wait for.
+#[derive(Debug, DeriveOptions)]
+pub enum StopOption {
+    /// Stops when any contract ends.
+    ContractEnds,
+    /// Stops when the credits of a member drop below a threshold.
+    BalanceBelow,
+    /// Stops when a member could not be settled.
+    SettlementFailure,
+    /// Goes Back.
+    Back,
+    /// Any Other other choice.
+    #[other]
+    Other,
+}
+
 /// Defines all methods for the simulator view.
 pub trait SimulatorView {
     /// Shows all the options for the simulator.
     fn simulator_menu(&self) -> SimulatorOption;
+    /// Getting the number of days to advance.
+    fn get_days(&self) -> Option<usize>;
+    /// Getting a day after today.
+    fn get_day(&self, now: usize) -> Option<usize>;
+    /// Getting the condition to stop at, the member is selected from the list.
+    fn get_stop_condition(&self, members: Vec<&Member>) -> Option<StopCondition>;
+    /// Displays what happened on every simulated day.
+    fn display_summary(&self, days: &[DaySummary]);
+    /// Getting the seed and size of a simulated community.
+    fn get_community_config(&self) -> Option<CommunityConfig>;
+    /// Displays the statistics of a simulated community.
+    fn display_community_stats(&self, stats: &CommunityStats);
     /// waits for user.
     fn wait(&self, display: &str);
 }
@@ -51,6 +99,129 @@ impl SimulatorView for CliSimulatorView {
         }
     }
 
+    fn get_days(&self) -> Option<usize> {
+        let inp = self
+            .console
+            .get_str_input("Number of days (or (e) to go back)");
+        if inp.as_str() == "e" {
+            return None;
+        }
+        match inp.parse::<usize>() {
+            Ok(days) if days > 0 => Some(days),
+            _ => {
+                self.wait("Invalid input: Advance at least one day.");
+                self.get_days()
+            }
+        }
+    }
+
+    fn get_day(&self, now: usize) -> Option<usize> {
+        let inp = self
+            .console
+            .get_str_input(format!("Day after {} (or (e) to go back)", now).as_str());
+        if inp.as_str() == "e" {
+            return None;
+        }
+        match inp.parse::<usize>() {
+            Ok(day) if day > now => Some(day),
+            _ => {
+                self.wait("Invalid input: The day has to be in the future.");
+                self.get_day(now)
+            }
+        }
+    }
+
+    fn get_stop_condition(&self, members: Vec<&Member>) -> Option<StopCondition> {
+        self.console.title();
+        let choice: StopOption = self.console.show_menu(StopOption::options());
+        match choice {
+            StopOption::ContractEnds => Some(StopCondition::ContractEnds),
+            StopOption::SettlementFailure => Some(StopCondition::SettlementFailure),
+            StopOption::BalanceBelow => {
+                let member = self.console.select_model(members)?;
+                let threshold = self.console.get_str_input("Credits");
+                match threshold.parse::<f64>() {
+                    Ok(credits) => Some(StopCondition::BalanceBelow(
+                        member.get_uuid().clone(),
+                        credits,
+                    )),
+                    Err(_) => {
+                        self.wait("Invalid input: The credits have to be a number.");
+                        None
+                    }
+                }
+            }
+            StopOption::Back => None,
+            StopOption::Other => self.get_stop_condition(members),
+        }
+    }
+
+    fn display_summary(&self, days: &[DaySummary]) {
+        self.console.clear();
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec![
+            "Day",
+            "Active Contracts",
+            "Ended Contracts",
+            "Credits Transferred",
+            "Lowest Balance",
+            "Settled",
+        ]));
+        for day in days {
+            let lowest = match day.get_lowest_balance() {
+                Some((name, credits)) => format!("{} ({})", name, credits),
+                None => "-".to_owned(),
+            };
+            table.add_row(Row::from(vec![
+                day.get_day().to_string(),
+                day.get_active_contracts().to_string(),
+                day.get_ended_contracts().to_string(),
+                day.get_credits_transferred().to_string(),
+                lowest,
+                match day.get_settled() {
+                    true => "Yes".to_owned(),
+                    false => "No".to_owned(),
+                },
+            ]));
+        }
+        self.console.display_table(table);
+    }
+
+    fn get_community_config(&self) -> Option<CommunityConfig> {
+        let seed = self.console.get_str_input("Seed (or (e) to go back)");
+        if seed.as_str() == "e" {
+            return None;
+        }
+        let members = self.console.get_str_input("Number of members");
+        let days = self.console.get_str_input("Number of days");
+        match (
+            seed.parse::<u64>(),
+            members.parse::<usize>(),
+            days.parse::<usize>(),
+        ) {
+            (Ok(seed), Ok(members), Ok(days)) if members > 1 => Some(
+                CommunityConfig::default()
+                    .seed(seed)
+                    .members(members)
+                    .days(days)
+                    .build(),
+            ),
+            _ => {
+                self.wait("Invalid input: Enter a seed and at least two members.");
+                self.get_community_config()
+            }
+        }
+    }
+
+    fn display_community_stats(&self, stats: &CommunityStats) {
+        self.console.clear();
+        let mut table = Table::new();
+        for (name, value) in stats.to_pairs() {
+            table.add_row(Row::from(vec![name.to_owned(), value]));
+        }
+        self.console.display_table(table);
+    }
+
     fn wait(&self, display: &str) {
         self.console.wait(display)
     }